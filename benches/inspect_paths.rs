@@ -0,0 +1,38 @@
+//! Compares `inspect_paths` with calling `inspect_path` in a loop.
+//!
+//! Run with `cargo bench --bench inspect_paths`. The inputs are synthetic
+//! files under the temp directory that don't exist, spread over a few
+//! directories, so both variants do the same path resolution and differ only
+//! in how often the mount table is loaded.
+use inspect_path::{inspect_path, inspect_paths};
+use std::{hint::black_box, path::PathBuf, time::Instant};
+
+const PATHS: usize = 50_000;
+
+fn main() {
+    let base = std::env::temp_dir();
+    let paths: Vec<PathBuf> = (0..PATHS)
+        .map(|i| {
+            base.join(format!("dir{}", i % 64))
+                .join(format!("file{i}.dat"))
+        })
+        .collect();
+
+    let start = Instant::now();
+    for path in &paths {
+        let _ = black_box(inspect_path(path));
+    }
+    let naive = start.elapsed();
+
+    let start = Instant::now();
+    let _ = black_box(inspect_paths(paths.iter().map(PathBuf::as_path)));
+    let batch = start.elapsed();
+
+    println!("{PATHS} paths");
+    println!("inspect_path loop: {naive:?}");
+    println!("inspect_paths:     {batch:?}");
+    println!(
+        "speedup:           {:.1}x",
+        naive.as_secs_f64() / batch.as_secs_f64()
+    );
+}
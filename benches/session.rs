@@ -0,0 +1,52 @@
+//! Times repeated inspections of one volume through an `InspectSession`.
+//!
+//! Run with `cargo bench --bench session --features test-util`. On Windows
+//! it also checks, with `record_win32_calls`, that every inspection after the
+//! first makes no Win32 call beyond `GetVolumePathNameW`; elsewhere there is
+//! nothing to count and it only reports the timings.
+use inspect_path::{InspectSession, inspect_path};
+use std::{hint::black_box, path::PathBuf, time::Instant};
+
+const PATHS: usize = 10_000;
+
+fn main() {
+    let base = std::env::temp_dir();
+    let paths: Vec<PathBuf> = (0..PATHS)
+        .map(|i| base.join(format!("file{i}.dat")))
+        .collect();
+
+    let start = Instant::now();
+    for path in &paths {
+        let _ = black_box(inspect_path(path));
+    }
+    let naive = start.elapsed();
+
+    let session = InspectSession::new();
+    let start = Instant::now();
+    for path in &paths {
+        let _ = black_box(session.inspect(path));
+    }
+    let cached = start.elapsed();
+
+    #[cfg(windows)]
+    {
+        let (_, calls) = inspect_path::record_win32_calls(|| {
+            for path in &paths {
+                let _ = black_box(session.inspect(path));
+            }
+        });
+        assert!(
+            calls.iter().all(|&call| call == "GetVolumePathNameW"),
+            "session hits made {calls:?}"
+        );
+        println!("win32 calls per hit: {}", calls.len() / PATHS);
+    }
+
+    println!("{PATHS} paths");
+    println!("inspect_path loop: {naive:?}");
+    println!("session:           {cached:?}");
+    println!(
+        "speedup:           {:.1}x",
+        naive.as_secs_f64() / cached.as_secs_f64()
+    );
+}
@@ -0,0 +1,21 @@
+fn main() {
+    #[cfg(feature = "ffi")]
+    {
+        println!("cargo::rerun-if-changed=src/ffi.rs");
+        println!("cargo::rerun-if-changed=cbindgen.toml");
+
+        let dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+        let out = std::env::var("OUT_DIR").unwrap();
+        let config = cbindgen::Config::from_file(format!("{dir}/cbindgen.toml"))
+            .expect("cbindgen.toml is valid");
+        // only the one module, which lib.rs includes behind the feature;
+        // the checked-in include/inspect_path.h is compared against this
+        // copy by a test rather than rewritten on every build
+        cbindgen::Builder::new()
+            .with_config(config)
+            .with_src(format!("{dir}/src/ffi.rs"))
+            .generate()
+            .expect("src/ffi.rs parses")
+            .write_to_file(format!("{out}/inspect_path.h"));
+    }
+}
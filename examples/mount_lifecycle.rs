@@ -0,0 +1,60 @@
+//! Windows drive mapping lifecycle: detect, mount, inspect, use, unmount.
+//!
+//! Usage: `cargo run --example mount_lifecycle -- Z: \\server\share`
+use std::error::Error;
+
+#[cfg(target_os = "windows")]
+fn main() -> Result<(), Box<dyn Error>> {
+    use inspect_path::{
+        InspectPathError, inspect_path_and_status, try_mount_if_needed, unmount_path,
+    };
+    use std::path::Path;
+
+    // Win32 codes worth handling explicitly.
+    const ERROR_ALREADY_ASSIGNED: i32 = 85;
+    const ERROR_LOGON_FAILURE: i32 = 1326;
+    const ERROR_SESSION_CREDENTIAL_CONFLICT: i32 = 1219;
+
+    let mut args = std::env::args().skip(1);
+    let drive = args.next().unwrap_or_else(|| "Z:".into());
+    let remote = args.next().unwrap_or_else(|| r"\\server\share".into());
+    let file = format!(r"{drive}\");
+
+    match try_mount_if_needed(Path::new(&file), Path::new(&remote)) {
+        Ok(()) => {}
+        Err(InspectPathError::Sys {
+            errno: ERROR_ALREADY_ASSIGNED,
+            ..
+        }) => {
+            println!("{drive} is already mapped to something else; not touching it");
+            return Ok(());
+        }
+        Err(InspectPathError::Sys {
+            errno: ERROR_LOGON_FAILURE | ERROR_SESSION_CREDENTIAL_CONFLICT,
+            ..
+        }) => {
+            println!("{remote} needs credentials, try mount_path_as_user");
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    }
+
+    let info = inspect_path_and_status(Path::new(&file))?;
+    println!("{drive}: {info:#?}");
+
+    if info.is_status_mounted() {
+        let entries = std::fs::read_dir(&file)?.count();
+        println!("{drive} has {entries} entries");
+    }
+
+    unmount_path(&drive, false)?;
+    println!("{drive} unmounted");
+
+    Ok(())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn main() -> Result<(), Box<dyn Error>> {
+    println!("This example maps network drives and only runs on Windows.");
+    Ok(())
+}
@@ -0,0 +1,19 @@
+//! Feeds arbitrary text to the mountinfo parser, one line at a time as the
+//! mount table reader does. Run with `cargo +nightly fuzz run mountinfo`.
+#![no_main]
+
+use inspect_path::MountInfo;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|text: &str| {
+    for line in text.lines() {
+        if let Ok(mount) = MountInfo::parse_line(line) {
+            let _ = (
+                mount.mount_point(),
+                mount.source(),
+                mount.options(),
+                mount.atime_policy(),
+            );
+        }
+    }
+});
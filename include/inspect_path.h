@@ -0,0 +1,99 @@
+#ifndef INSPECT_PATH_H
+#define INSPECT_PATH_H
+
+/* Generated by cbindgen from src/ffi.rs; do not edit. */
+
+#include <stddef.h>
+#include <stdint.h>
+
+/**
+ * Success.
+ */
+#define IP_OK 0
+
+/**
+ * A null pointer, or a path that isn't valid UTF-8.
+ */
+#define IP_ERR_INVALID_ARGUMENT -1
+
+/**
+ * The path could not be inspected.
+ */
+#define IP_ERR_INSPECT -2
+
+/**
+ * The library panicked; a bug.
+ */
+#define IP_ERR_PANIC -3
+
+/**
+ * Size of [`IpInfo::fs_type`], including the terminating NUL.
+ */
+#define IP_FS_TYPE_LEN 64
+
+/**
+ * The result of [`ip_inspect`].
+ *
+ * Enums are stored as their stable codes (`PathType::code` and friends).
+ * Strings are NUL-terminated UTF-8, truncated at a character boundary if
+ * too long, and empty when unknown.
+ */
+typedef struct IpInfo {
+  /**
+   * The path type code.
+   */
+  uint16_t kind;
+  /**
+   * The remote type code, `0` (unknown) for local paths.
+   */
+  uint16_t remote_kind;
+  /**
+   * The path status code; `0` (unknown) unless probed.
+   */
+  uint16_t status;
+  /**
+   * The filesystem type name, e.g. `ext4` or `NTFS`.
+   */
+  char fs_type[IP_FS_TYPE_LEN];
+} IpInfo;
+
+/**
+ * Inspects `path` and fills `out`.
+ *
+ * Returns [`IP_OK`], or a negative error code and leaves `out` untouched.
+ *
+ * # Safety
+ *
+ * `path` must be null or a NUL-terminated string, and `out` null or valid
+ * for writes of one `IpInfo`.
+ */
+int32_t ip_inspect(const char *path, struct IpInfo *out);
+
+/**
+ * Probes whether `path` is reachable and returns its status code
+ * (`PathStatus::code`), or a negative error code.
+ *
+ * With a positive `timeout_ms` a hung probe is abandoned after that long and
+ * reported as timed out; otherwise the probe may block.
+ *
+ * # Safety
+ *
+ * `path` must be null or a NUL-terminated string.
+ */
+int32_t ip_check_status(const char *path, int32_t timeout_ms);
+
+/**
+ * Copies the message of the last error on this thread into `buf` as a
+ * NUL-terminated string, truncated to `len` bytes including the NUL.
+ *
+ * Returns the full length of the message in bytes, so a return value of
+ * `len` or more means it was truncated; `0` if the last call succeeded.
+ * Unlike the other functions, this leaves the message in place.
+ *
+ * # Safety
+ *
+ * `buf` must be null or valid for writes of `len` bytes.
+ */
+size_t ip_last_error_message(char *buf, size_t len);
+
+#endif  /* INSPECT_PATH_H */
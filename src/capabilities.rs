@@ -0,0 +1,66 @@
+use crate::platform;
+
+/// What the current platform and system support, for code that runs on
+/// several OSes and would rather branch at runtime than on `#[cfg]`.
+///
+/// Returned by [`capabilities`]. New fields may be added in minor releases.
+///
+/// # Examples
+///
+/// ```rust
+/// let caps = inspect_path::capabilities();
+/// if caps.has_volume_labels {
+///     println!("labels can be shown");
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct Capabilities {
+    /// `try_mount_if_needed` exists (Windows and Unix, with the `mount`
+    /// feature), and with it `mount_path` on Windows and Linux and
+    /// `unmount_path` on Windows.
+    pub can_mount: bool,
+    /// The mount table comes from a readable mountinfo file, which shows bind
+    /// mounts and mount options (Linux, unless `/proc` is missing).
+    pub has_mountinfo: bool,
+    /// [`PathInfo::device_id`](crate::PathInfo::device_id) is built from
+    /// `major:minor` device numbers rather than a volume name (Unix).
+    pub has_device_numbers: bool,
+    /// [`PathInfo::volume_label`](crate::PathInfo::volume_label) can find
+    /// labels: always on Windows, on Unix when udev's `/dev/disk/by-label`
+    /// exists.
+    pub has_volume_labels: bool,
+    /// [`MountEvents`](crate::MountEvents) reports mounts as they happen
+    /// (Linux and Windows).
+    pub can_watch_mounts: bool,
+    /// [`PathInfo::quota`](crate::PathInfo::quota) can answer (Linux).
+    pub has_quotas: bool,
+}
+
+/// Reports what this platform supports; see [`Capabilities`].
+///
+/// Runtime parts (such as whether `/proc` is mounted) are checked on each
+/// call with a `stat`, which is cheap.
+pub fn capabilities() -> Capabilities {
+    platform::capabilities()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_the_platform() {
+        let caps = capabilities();
+        assert_eq!(
+            caps.can_mount,
+            cfg!(all(any(windows, unix), feature = "mount"))
+        );
+        assert_eq!(caps.has_device_numbers, cfg!(unix));
+        assert_eq!(caps.has_quotas, cfg!(target_os = "linux"));
+        #[cfg(target_os = "linux")]
+        assert!(caps.has_mountinfo && caps.can_watch_mounts);
+    }
+}
@@ -0,0 +1,156 @@
+use crate::PathStatus;
+use std::{
+    sync::mpsc::{Receiver, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+
+/// The time source behind every timeout, poll interval and timestamp.
+///
+/// Public functions always use [`SystemClock`]; tests swap in `MockClock` so
+/// timeout logic runs without sleeping.
+pub(crate) trait Clock: Send + Sync {
+    /// Monotonic time, for deadlines and elapsed durations.
+    fn now(&self) -> Instant;
+
+    /// Wall-clock time, for timestamps handed to the caller.
+    fn system_time(&self) -> SystemTime;
+
+    fn sleep(&self, duration: Duration);
+
+    /// Waits for a probe result until `timeout` after `started`, the time
+    /// the probe was started.
+    fn recv_timeout(
+        &self,
+        rx: &Receiver<PathStatus>,
+        started: Instant,
+        timeout: Duration,
+    ) -> Result<PathStatus, RecvTimeoutError>;
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn system_time(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+
+    fn recv_timeout(
+        &self,
+        rx: &Receiver<PathStatus>,
+        started: Instant,
+        timeout: Duration,
+    ) -> Result<PathStatus, RecvTimeoutError> {
+        rx.recv_timeout(timeout.saturating_sub(started.elapsed()))
+    }
+}
+
+/// Virtual time that only moves when slept on or advanced.
+///
+/// A probe simulates taking a while by calling [`sleep`](Clock::sleep) on
+/// the same clock. `recv_timeout` waits for the probe to actually finish and
+/// then reports a timeout if more virtual time than allowed went by, so
+/// probes used with it must not block for real.
+#[cfg(test)]
+#[derive(Debug)]
+pub(crate) struct MockClock {
+    origin: Instant,
+    elapsed: std::sync::Mutex<Duration>,
+}
+
+#[cfg(test)]
+impl MockClock {
+    pub(crate) fn new() -> Self {
+        Self {
+            origin: Instant::now(),
+            elapsed: std::sync::Mutex::new(Duration::ZERO),
+        }
+    }
+
+    pub(crate) fn advance(&self, by: Duration) {
+        *self.elapsed.lock().unwrap() += by;
+    }
+
+    pub(crate) fn elapsed(&self) -> Duration {
+        *self.elapsed.lock().unwrap()
+    }
+}
+
+#[cfg(test)]
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        self.origin + self.elapsed()
+    }
+
+    fn system_time(&self) -> SystemTime {
+        SystemTime::UNIX_EPOCH + self.elapsed()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.advance(duration);
+    }
+
+    fn recv_timeout(
+        &self,
+        rx: &Receiver<PathStatus>,
+        started: Instant,
+        timeout: Duration,
+    ) -> Result<PathStatus, RecvTimeoutError> {
+        // the probe may have slept on the clock before this was called
+        let status = rx.recv().map_err(|_| RecvTimeoutError::Disconnected)?;
+        if self.now() - started > timeout {
+            return Err(RecvTimeoutError::Timeout);
+        }
+        Ok(status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    #[test]
+    fn mock_clock_moves_only_when_told() {
+        let clock = MockClock::new();
+        let start = clock.now();
+        assert_eq!(clock.system_time(), SystemTime::UNIX_EPOCH);
+
+        clock.sleep(Duration::from_secs(3600));
+        assert_eq!(clock.now() - start, Duration::from_secs(3600));
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(
+            clock.system_time(),
+            SystemTime::UNIX_EPOCH + Duration::from_secs(3601)
+        );
+
+        let (tx, rx) = mpsc::channel();
+        tx.send(PathStatus::Mounted).unwrap();
+        assert_eq!(
+            clock.recv_timeout(&rx, clock.now(), Duration::ZERO),
+            Ok(PathStatus::Mounted)
+        );
+        // time the probe spent before the wait began still counts
+        let started = clock.now();
+        clock.sleep(Duration::from_secs(2));
+        tx.send(PathStatus::Mounted).unwrap();
+        assert_eq!(
+            clock.recv_timeout(&rx, started, Duration::from_secs(1)),
+            Err(RecvTimeoutError::Timeout)
+        );
+        drop(tx);
+        assert_eq!(
+            clock.recv_timeout(&rx, clock.now(), Duration::ZERO),
+            Err(RecvTimeoutError::Disconnected)
+        );
+    }
+}
@@ -0,0 +1,273 @@
+//! Stable integer codes for persisting [`PathType`], [`PathStatus`] and
+//! [`RemoteType`], e.g. in a database column.
+//!
+//! The codes are part of the public API: an existing code never changes
+//! meaning, new variants get new codes, and codes of removed variants are not
+//! reused. `0` is `Unknown` in every table.
+use crate::{PathStatus, PathType, RemoteType};
+
+impl PathType {
+    /// Returns the stable code for this variant.
+    ///
+    /// | code | variant |
+    /// |------|---------|
+    /// | 0 | `Unknown` |
+    /// | 1 | `Removable` |
+    /// | 2 | `Fixed` |
+    /// | 3 | `Remote` |
+    /// | 4 | `CDRom` |
+    /// | 5 | `RamDisk` |
+    /// | 6 | `Virtual` (filesystem name in [`detail`](Self::detail)) |
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use inspect_path::PathType;
+    ///
+    /// let kind = PathType::Virtual("proc".into());
+    /// let (code, detail) = (kind.code(), kind.detail().map(str::to_owned));
+    ///
+    /// let restored = match (PathType::from_code(code), detail) {
+    ///     (Some(PathType::Virtual(_)), Some(fs)) => PathType::Virtual(fs),
+    ///     (restored, _) => restored.unwrap_or(PathType::Unknown),
+    /// };
+    /// assert_eq!(restored, kind);
+    /// ```
+    pub fn code(&self) -> u16 {
+        match self {
+            PathType::Unknown => 0,
+            PathType::Removable => 1,
+            PathType::Fixed => 2,
+            PathType::Remote => 3,
+            PathType::CDRom => 4,
+            PathType::RamDisk => 5,
+            PathType::Virtual(_) => 6,
+        }
+    }
+
+    /// Returns the variant for a [`code`](Self::code), or `None` for a code
+    /// this version doesn't know. `Virtual` comes back with an empty name.
+    pub fn from_code(code: u16) -> Option<Self> {
+        Some(match code {
+            0 => PathType::Unknown,
+            1 => PathType::Removable,
+            2 => PathType::Fixed,
+            3 => PathType::Remote,
+            4 => PathType::CDRom,
+            5 => PathType::RamDisk,
+            6 => PathType::Virtual(String::new()),
+            _ => return None,
+        })
+    }
+
+    /// Returns the filesystem name carried by `Virtual`, which the code
+    /// doesn't capture.
+    pub fn detail(&self) -> Option<&str> {
+        match self {
+            PathType::Virtual(name) => Some(name),
+            _ => None,
+        }
+    }
+}
+
+impl PathStatus {
+    /// Returns the stable code for this variant.
+    ///
+    /// | code | variant |
+    /// |------|---------|
+    /// | 0 | `Unknown` |
+    /// | 1 | `Mounted` |
+    /// | 2 | `Disconnected` |
+    /// | 3 | `Other` (description in [`detail`](Self::detail)) |
+    /// | 4 | `Stale` |
+    /// | 5 | `PermissionLimited` |
+    /// | 6 | `NoMedia` |
+    /// | 7 | `NotMounted` |
+    /// | 8 | `TimedOut` |
+    pub fn code(&self) -> u16 {
+        match self {
+            PathStatus::Unknown => 0,
+            PathStatus::Mounted => 1,
+            PathStatus::Disconnected => 2,
+            PathStatus::Other(_) => 3,
+            PathStatus::Stale => 4,
+            PathStatus::PermissionLimited => 5,
+            PathStatus::NoMedia => 6,
+            PathStatus::NotMounted => 7,
+            PathStatus::TimedOut => 8,
+        }
+    }
+
+    /// Returns the variant for a [`code`](Self::code), or `None` for a code
+    /// this version doesn't know. `Other` comes back with an empty
+    /// description.
+    pub fn from_code(code: u16) -> Option<Self> {
+        Some(match code {
+            0 => PathStatus::Unknown,
+            1 => PathStatus::Mounted,
+            2 => PathStatus::Disconnected,
+            3 => PathStatus::Other(String::new()),
+            4 => PathStatus::Stale,
+            5 => PathStatus::PermissionLimited,
+            6 => PathStatus::NoMedia,
+            7 => PathStatus::NotMounted,
+            8 => PathStatus::TimedOut,
+            _ => return None,
+        })
+    }
+
+    /// Returns the description carried by `Other`, which the code doesn't
+    /// capture.
+    pub fn detail(&self) -> Option<&str> {
+        match self {
+            PathStatus::Other(description) => Some(description),
+            _ => None,
+        }
+    }
+}
+
+impl RemoteType {
+    /// Returns the stable code for this variant.
+    ///
+    /// | code | variant |
+    /// |------|---------|
+    /// | 0 | `Unknown` |
+    /// | 1 | `WebDAV` |
+    /// | 2 | `NFS` |
+    /// | 3 | `SMB` |
+    /// | 4 | `AFS` |
+    /// | 5 | `SSHFS` |
+    /// | 6 | `Ceph` |
+    /// | 7 | `GlusterFS` |
+    /// | 8 | `NinePfs` |
+    /// | 9 | `VirtioFs` |
+    /// | 10 | `Other` (filesystem name in [`detail`](Self::detail)) |
+    pub fn code(&self) -> u16 {
+        match self {
+            RemoteType::Unknown => 0,
+            RemoteType::WebDAV => 1,
+            RemoteType::NFS => 2,
+            RemoteType::SMB => 3,
+            RemoteType::AFS => 4,
+            RemoteType::SSHFS => 5,
+            RemoteType::Ceph => 6,
+            RemoteType::GlusterFS => 7,
+            RemoteType::NinePfs => 8,
+            RemoteType::VirtioFs => 9,
+            RemoteType::Other(_) => 10,
+        }
+    }
+
+    /// Returns the variant for a [`code`](Self::code), or `None` for a code
+    /// this version doesn't know. `Other` comes back with an empty name.
+    pub fn from_code(code: u16) -> Option<Self> {
+        Some(match code {
+            0 => RemoteType::Unknown,
+            1 => RemoteType::WebDAV,
+            2 => RemoteType::NFS,
+            3 => RemoteType::SMB,
+            4 => RemoteType::AFS,
+            5 => RemoteType::SSHFS,
+            6 => RemoteType::Ceph,
+            7 => RemoteType::GlusterFS,
+            8 => RemoteType::NinePfs,
+            9 => RemoteType::VirtioFs,
+            10 => RemoteType::Other(String::new()),
+            _ => return None,
+        })
+    }
+
+    /// Returns the filesystem name carried by `Other`, which the code
+    /// doesn't capture.
+    pub fn detail(&self) -> Option<&str> {
+        match self {
+            RemoteType::Other(name) => Some(name),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // These tables are a stability guarantee: never edit an existing row,
+    // only add rows for new variants.
+
+    const PATH_TYPES: &[(u16, &str)] = &[
+        (0, "Unknown"),
+        (1, "Removable"),
+        (2, "Fixed"),
+        (3, "Remote"),
+        (4, "CDRom"),
+        (5, "RamDisk"),
+        (6, "Virtual"),
+    ];
+
+    const PATH_STATUSES: &[(u16, &str)] = &[
+        (0, "Unknown"),
+        (1, "Mounted"),
+        (2, "Disconnected"),
+        (3, "Other"),
+        (4, "Stale"),
+        (5, "PermissionLimited"),
+        (6, "NoMedia"),
+        (7, "NotMounted"),
+        (8, "TimedOut"),
+    ];
+
+    const REMOTE_TYPES: &[(u16, &str)] = &[
+        (0, "Unknown"),
+        (1, "WebDAV"),
+        (2, "NFS"),
+        (3, "SMB"),
+        (4, "AFS"),
+        (5, "SSHFS"),
+        (6, "Ceph"),
+        (7, "GlusterFS"),
+        (8, "NinePfs"),
+        (9, "VirtioFs"),
+        (10, "Other"),
+    ];
+
+    /// The variant name from its `Debug` form, without any payload.
+    fn name(debug: String) -> String {
+        debug.split('(').next().unwrap().to_string()
+    }
+
+    /// Checks `table` against `from_code` and back, and that the code after
+    /// the last row is unassigned.
+    fn check<T: std::fmt::Debug>(
+        table: &[(u16, &str)],
+        from_code: fn(u16) -> Option<T>,
+        code: fn(&T) -> u16,
+    ) {
+        for &(expected, variant) in table {
+            let value = from_code(expected).unwrap_or_else(|| panic!("code {expected}"));
+            assert_eq!(name(format!("{value:?}")), variant, "code {expected}");
+            assert_eq!(code(&value), expected, "{variant}");
+        }
+        assert!(from_code(table.len() as u16).is_none());
+    }
+
+    #[test]
+    fn codes_are_frozen() {
+        check(PATH_TYPES, PathType::from_code, PathType::code);
+        check(PATH_STATUSES, PathStatus::from_code, PathStatus::code);
+        check(REMOTE_TYPES, RemoteType::from_code, RemoteType::code);
+    }
+
+    #[test]
+    fn payloads_are_separate() {
+        let virt = PathType::Virtual("proc".into());
+        assert_eq!((virt.code(), virt.detail()), (6, Some("proc")));
+        assert_eq!(PathType::Fixed.detail(), None);
+
+        let other = PathStatus::Other("busy".into());
+        assert_eq!((other.code(), other.detail()), (3, Some("busy")));
+
+        let afp = RemoteType::Other("afp".into());
+        assert_eq!((afp.code(), afp.detail()), (10, Some("afp")));
+        assert_eq!(RemoteType::from_code(u16::MAX), None);
+    }
+}
@@ -0,0 +1,114 @@
+//! Default credentials for mounting network shares, behind the `mount`
+//! feature.
+use crate::remote;
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Mutex, OnceLock, PoisonError},
+};
+use zeroize::Zeroizing;
+
+/// Credentials that `mount_path` and `try_mount_if_needed` use for a server,
+/// so a batch of mounts on one server needs them only once. **Windows and
+/// Linux.**
+///
+/// When no credentials are passed explicitly, the server is taken from the
+/// remote name (`nas` in `\\nas\backup` or `//nas/backup`) and looked up
+/// here, ignoring case; if nothing is stored for it, the connection falls
+/// back to the default credentials of the logged-on user. The `_as_user`
+/// variants never consult the store.
+///
+/// On Linux only CIFS mounts use the store, unless their options name a
+/// user or credentials file; a `CORP\backup` user becomes `domain=CORP`.
+///
+/// Passwords are kept in buffers that are zeroed when replaced, removed or
+/// dropped, and `Debug` shows only the host names. They are still held in
+/// plain memory for as long as they are stored.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use inspect_path::{CredentialStore, mount_path};
+///
+/// CredentialStore::global().set_credentials("nas", r"CORP\backup", "hunter2");
+/// mount_path("X:", r"\\nas\backup").unwrap();
+/// mount_path("Y:", r"\\nas\media").unwrap();
+/// CredentialStore::global().clear();
+/// ```
+#[derive(Default)]
+pub struct CredentialStore {
+    hosts: Mutex<HashMap<String, Credentials>>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(not(any(windows, target_os = "linux")), allow(dead_code))]
+pub(crate) struct Credentials {
+    pub(crate) username: String,
+    pub(crate) password: Zeroizing<String>,
+}
+
+static GLOBAL: OnceLock<CredentialStore> = OnceLock::new();
+
+impl CredentialStore {
+    /// The store the mount functions consult.
+    pub fn global() -> &'static CredentialStore {
+        GLOBAL.get_or_init(Default::default)
+    }
+
+    /// Stores credentials for `host`, replacing any stored before.
+    pub fn set_credentials(&self, host: &str, username: &str, password: &str) {
+        let credentials = Credentials {
+            username: username.to_string(),
+            password: Zeroizing::new(password.to_string()),
+        };
+        self.lock().insert(host.to_ascii_lowercase(), credentials);
+    }
+
+    /// Forgets the credentials for `host`. Returns whether any were stored.
+    pub fn remove(&self, host: &str) -> bool {
+        self.lock().remove(&host.to_ascii_lowercase()).is_some()
+    }
+
+    /// Forgets all stored credentials.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// The credentials for the server named in `remote`, e.g.
+    /// `\\nas\backup`.
+    #[cfg_attr(not(any(windows, target_os = "linux")), allow(dead_code))]
+    pub(crate) fn lookup(&self, remote: &str) -> Option<Credentials> {
+        let host = remote::host_of(remote)?.to_ascii_lowercase();
+        self.lock().get(&host).cloned()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Credentials>> {
+        self.hosts.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl fmt::Debug for CredentialStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.lock().keys()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn looks_up_by_host() {
+        let store = CredentialStore::default();
+        store.set_credentials("NAS", "backup", "secret");
+
+        let found = store.lookup(r"\\nas\backup").unwrap();
+        assert_eq!(found.username, "backup");
+        assert_eq!(found.password.as_str(), "secret");
+        assert!(store.lookup(r"\\other\share").is_none());
+        assert!(!format!("{store:?}").contains("secret"));
+
+        assert!(store.remove("nas"));
+        assert!(store.lookup(r"\\nas\backup").is_none());
+    }
+}
@@ -0,0 +1,65 @@
+use std::fmt;
+
+/// Identifies the device (volume) backing a path, see
+/// [`PathInfo::device_id`](crate::PathInfo::device_id).
+///
+/// The contents are platform-specific and only meaningful for comparison on
+/// the same machine. Equal ids mean the same mounted device, which is not
+/// always what [`same_filesystem`](crate::same_filesystem) compares: btrfs
+/// subvolumes share their filesystem's device number in the mount table but
+/// each has its own `st_dev`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct DeviceId(Repr);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+enum Repr {
+    /// `major:minor` of the mounted device.
+    #[cfg(target_family = "unix")]
+    Number { major: u32, minor: u32 },
+    /// `Volume{...}`, the volume GUID name without the `\\?\` prefix.
+    #[cfg(target_os = "windows")]
+    Guid(String),
+    /// Volume serial number, for volumes without a GUID (network shares).
+    #[cfg(target_os = "windows")]
+    Serial(u32),
+}
+
+impl DeviceId {
+    #[cfg(target_family = "unix")]
+    pub(crate) fn from_number(major: u32, minor: u32) -> Self {
+        DeviceId(Repr::Number { major, minor })
+    }
+
+    /// From a volume GUID path such as `\\?\Volume{...}\`.
+    #[cfg(target_os = "windows")]
+    pub(crate) fn from_volume_name(name: &str) -> Self {
+        let guid = name.trim_start_matches(r"\\?\").trim_end_matches('\\');
+        DeviceId(Repr::Guid(guid.to_string()))
+    }
+
+    #[cfg(target_os = "windows")]
+    pub(crate) fn from_serial(serial: u32) -> Self {
+        DeviceId(Repr::Serial(serial))
+    }
+}
+
+/// `8:1` on Unix; `Volume{...}` or a `1A2B-3C4D` serial on Windows.
+impl fmt::Display for DeviceId {
+    #[cfg_attr(not(any(unix, windows)), allow(unused_variables))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // by place, so it compiles where `Repr` has no variants
+        match self.0 {
+            #[cfg(target_family = "unix")]
+            Repr::Number { major, minor } => write!(f, "{major}:{minor}"),
+            #[cfg(target_os = "windows")]
+            Repr::Guid(ref guid) => f.write_str(guid),
+            #[cfg(target_os = "windows")]
+            Repr::Serial(serial) => write!(f, "{:04X}-{:04X}", serial >> 16, serial & 0xffff),
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use crate::{InspectPathError, platform};
+use std::{
+    path::PathBuf,
+    sync::mpsc::{self, Receiver},
+    thread::JoinHandle,
+};
+
+/// What happened to a mount, see [`MountEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum MountEventKind {
+    Added,
+    Removed,
+    /// The mount is still there but its options or source changed, e.g. a
+    /// remount read-only. Only reported on Linux.
+    Changed,
+}
+
+/// A change to the system mount table, delivered by [`MountEvents`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MountEvent {
+    pub kind: MountEventKind,
+    /// The mount point on Linux, the drive root (e.g. `E:\`) on Windows.
+    ///
+    /// Pass it to [`inspect_path`](crate::inspect_path) to classify the new
+    /// mount.
+    pub mount_point: PathBuf,
+    /// The filesystem type (`nfs4`, `NTFS`, ...), when known. Always `None`
+    /// for removed Windows volumes.
+    pub fs_type: Option<String>,
+}
+
+/// A subscription to mount and unmount notifications from the OS.
+///
+/// Instead of polling, a background thread waits for the OS to report a
+/// change and sends one [`MountEvent`] per affected mount:
+///
+/// - **Linux**: the thread `poll`s `/proc/self/mountinfo`, which signals
+///   `POLLPRI` whenever the mount table of the process's namespace changes,
+///   then re-reads and diffs it.
+/// - **Windows**: the thread owns a hidden top-level window receiving
+///   `WM_DEVICECHANGE` volume arrival and removal broadcasts. Message-only
+///   windows don't get broadcasts, so the window is a real (never shown) one.
+///
+/// Dropping `MountEvents` signals the thread and waits for it to exit; the
+/// receiver then reports disconnection. The thread also exits on its own
+/// once the receiver is dropped and the next event fails to send.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use inspect_path::{MountEvents, inspect_path};
+///
+/// let (events, rx) = MountEvents::spawn().unwrap();
+/// for event in rx {
+///     println!("{:?} {}", event.kind, event.mount_point.display());
+///     if let Ok(info) = inspect_path(&event.mount_point) {
+///         println!("  {info}");
+///     }
+/// }
+/// # drop(events);
+/// ```
+#[derive(Debug)]
+pub struct MountEvents {
+    stop: platform::MountWatchStop,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MountEvents {
+    /// Starts the notification thread.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the mount table can't be read (Linux), the notification
+    /// window can't be created (Windows), or the thread can't be spawned.
+    pub fn spawn() -> Result<(Self, Receiver<MountEvent>), InspectPathError> {
+        let (tx, rx) = mpsc::channel();
+        let (stop, handle) = platform::watch_mounts(tx)?;
+        Ok((
+            Self {
+                stop,
+                handle: Some(handle),
+            },
+            rx,
+        ))
+    }
+}
+
+impl Drop for MountEvents {
+    fn drop(&mut self) {
+        self.stop.stop();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
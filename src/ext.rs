@@ -0,0 +1,54 @@
+use crate::{InspectOptions, InspectPathError, PathInfo, PathStatus};
+use std::path::Path;
+
+mod sealed {
+    pub trait Sealed {}
+    impl Sealed for std::path::Path {}
+}
+
+/// Extension methods for [`Path`] (and, through deref, `PathBuf`).
+///
+/// This trait is sealed: it is only implemented for [`Path`] and cannot be
+/// implemented outside this crate.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::path::Path;
+/// use inspect_path::PathExt;
+///
+/// # #[cfg(target_family = "unix")]
+/// # {
+/// let info = Path::new("/mnt/data").inspect().unwrap();
+/// println!("{info}, status {}", Path::new("/mnt/data").mount_status());
+/// # }
+/// # #[cfg(target_os = "windows")]
+/// # {
+/// let info = Path::new(r"Z:\reports").inspect().unwrap();
+/// println!("{info}, status {}", Path::new(r"Z:\reports").mount_status());
+/// # }
+/// ```
+pub trait PathExt: sealed::Sealed {
+    /// Equivalent to [`inspect_path`](crate::inspect_path).
+    fn inspect(&self) -> Result<PathInfo, InspectPathError>;
+
+    /// Equivalent to [`inspect_path_with`](crate::inspect_path_with).
+    fn inspect_with(&self, opts: &InspectOptions) -> Result<PathInfo, InspectPathError>;
+
+    /// Equivalent to [`check_status`](crate::check_status).
+    fn mount_status(&self) -> PathStatus;
+}
+
+impl PathExt for Path {
+    fn inspect(&self) -> Result<PathInfo, InspectPathError> {
+        crate::inspect_path(self)
+    }
+
+    fn inspect_with(&self, opts: &InspectOptions) -> Result<PathInfo, InspectPathError> {
+        crate::inspect_path_with(self, opts)
+    }
+
+    fn mount_status(&self) -> PathStatus {
+        crate::check_status(self)
+    }
+}
@@ -0,0 +1,267 @@
+//! A C interface, built with the `ffi` feature.
+//!
+//! The build script generates the matching header into `OUT_DIR`; the copy
+//! checked in as `include/inspect_path.h` is kept current by a test. Paths
+//! are passed as NUL-terminated UTF-8. Functions return a negative `IP_ERR_*`
+//! code on failure; [`ip_last_error_message`] then describes the failure on
+//! the same thread until the next call. A panic never crosses into C: it is
+//! reported as [`IP_ERR_PANIC`].
+use crate::{check_status, check_status_timeout, inspect_path};
+use std::{
+    cell::RefCell,
+    ffi::{CStr, c_char},
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    time::Duration,
+};
+
+/// Success.
+pub const IP_OK: i32 = 0;
+/// A null pointer, or a path that isn't valid UTF-8.
+pub const IP_ERR_INVALID_ARGUMENT: i32 = -1;
+/// The path could not be inspected.
+pub const IP_ERR_INSPECT: i32 = -2;
+/// The library panicked; a bug.
+pub const IP_ERR_PANIC: i32 = -3;
+
+/// Size of [`IpInfo::fs_type`], including the terminating NUL.
+pub const IP_FS_TYPE_LEN: usize = 64;
+
+/// The result of [`ip_inspect`].
+///
+/// Enums are stored as their stable codes (`PathType::code` and friends).
+/// Strings are NUL-terminated UTF-8, truncated at a character boundary if
+/// too long, and empty when unknown.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct IpInfo {
+    /// The path type code.
+    pub kind: u16,
+    /// The remote type code, `0` (unknown) for local paths.
+    pub remote_kind: u16,
+    /// The path status code; `0` (unknown) unless probed.
+    pub status: u16,
+    /// The filesystem type name, e.g. `ext4` or `NTFS`.
+    pub fs_type: [c_char; IP_FS_TYPE_LEN],
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+fn fail(code: i32, msg: impl ToString) -> i32 {
+    LAST_ERROR.with(|e| *e.borrow_mut() = msg.to_string());
+    code
+}
+
+/// Runs the body of an exported function: clears the last error, so a
+/// message never outlives the call that set it, and turns a panic into
+/// [`IP_ERR_PANIC`] instead of unwinding into C.
+fn call(body: impl FnOnce() -> i32) -> i32 {
+    LAST_ERROR.with(|e| e.borrow_mut().clear());
+    panic::catch_unwind(AssertUnwindSafe(body)).unwrap_or_else(|payload| {
+        let msg = payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("unknown panic");
+        fail(IP_ERR_PANIC, format!("panic: {msg}"))
+    })
+}
+
+/// Reads a C path; `&str` to `PathBuf` is the UTF-16 conversion on Windows.
+unsafe fn path_arg(path: *const c_char) -> Result<PathBuf, i32> {
+    if path.is_null() {
+        return Err(fail(IP_ERR_INVALID_ARGUMENT, "path is null"));
+    }
+    match unsafe { CStr::from_ptr(path) }.to_str() {
+        Ok(path) => Ok(PathBuf::from(path)),
+        Err(e) => Err(fail(
+            IP_ERR_INVALID_ARGUMENT,
+            format!("path is not UTF-8: {e}"),
+        )),
+    }
+}
+
+/// Copies `s` into `buf` as a NUL-terminated string, truncating at a
+/// character boundary. Returns the length of `s` in bytes.
+fn copy_str(s: &str, buf: &mut [c_char]) -> usize {
+    let Some(room) = buf.len().checked_sub(1) else {
+        return s.len();
+    };
+    let mut end = s.len().min(room);
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    for (dst, &src) in buf.iter_mut().zip(&s.as_bytes()[..end]) {
+        *dst = src as c_char;
+    }
+    buf[end] = 0;
+    s.len()
+}
+
+/// Inspects `path` and fills `out`.
+///
+/// Returns [`IP_OK`], or a negative error code and leaves `out` untouched.
+///
+/// # Safety
+///
+/// `path` must be null or a NUL-terminated string, and `out` null or valid
+/// for writes of one `IpInfo`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ip_inspect(path: *const c_char, out: *mut IpInfo) -> i32 {
+    call(|| {
+        let path = match unsafe { path_arg(path) } {
+            Ok(path) => path,
+            Err(code) => return code,
+        };
+        if out.is_null() {
+            return fail(IP_ERR_INVALID_ARGUMENT, "out is null");
+        }
+        let info = match inspect_path(&path) {
+            Ok(info) => info,
+            Err(e) => return fail(IP_ERR_INSPECT, e),
+        };
+
+        let mut result = IpInfo {
+            kind: info.kind().code(),
+            remote_kind: info.remote_type().map_or(0, |r| r.code()),
+            status: info.status().code(),
+            fs_type: [0; IP_FS_TYPE_LEN],
+        };
+        copy_str(info.fs_type_name().unwrap_or_default(), &mut result.fs_type);
+        unsafe { out.write(result) };
+        IP_OK
+    })
+}
+
+/// Probes whether `path` is reachable and returns its status code
+/// (`PathStatus::code`), or a negative error code.
+///
+/// With a positive `timeout_ms` a hung probe is abandoned after that long and
+/// reported as timed out; otherwise the probe may block.
+///
+/// # Safety
+///
+/// `path` must be null or a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ip_check_status(path: *const c_char, timeout_ms: i32) -> i32 {
+    call(|| {
+        let path = match unsafe { path_arg(path) } {
+            Ok(path) => path,
+            Err(code) => return code,
+        };
+        let status = match u64::try_from(timeout_ms) {
+            Ok(ms) if ms > 0 => check_status_timeout(&path, Duration::from_millis(ms)),
+            _ => check_status(&path),
+        };
+        i32::from(status.code())
+    })
+}
+
+/// Copies the message of the last error on this thread into `buf` as a
+/// NUL-terminated string, truncated to `len` bytes including the NUL.
+///
+/// Returns the full length of the message in bytes, so a return value of
+/// `len` or more means it was truncated; `0` if the last call succeeded.
+/// Unlike the other functions, this leaves the message in place.
+///
+/// # Safety
+///
+/// `buf` must be null or valid for writes of `len` bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ip_last_error_message(buf: *mut c_char, len: usize) -> usize {
+    let copy = || {
+        LAST_ERROR.with(|e| {
+            let msg = e.borrow();
+            if buf.is_null() {
+                return msg.len();
+            }
+            let buf = unsafe { std::slice::from_raw_parts_mut(buf, len) };
+            copy_str(&msg, buf)
+        })
+    };
+    panic::catch_unwind(AssertUnwindSafe(copy)).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PathStatus;
+    use std::ffi::CString;
+
+    fn c_str(buf: &[c_char]) -> &str {
+        unsafe { CStr::from_ptr(buf.as_ptr()) }.to_str().unwrap()
+    }
+
+    #[test]
+    fn inspect_through_c_abi() {
+        let tmp = std::env::temp_dir();
+        let path = CString::new(tmp.to_str().unwrap()).unwrap();
+        let mut out = std::mem::MaybeUninit::<IpInfo>::uninit();
+
+        assert_eq!(
+            unsafe { ip_inspect(path.as_ptr(), out.as_mut_ptr()) },
+            IP_OK
+        );
+        let out = unsafe { out.assume_init() };
+        let info = inspect_path(&tmp).unwrap();
+        assert_eq!(out.kind, info.kind().code());
+        assert_eq!(out.status, 0);
+        assert_eq!(c_str(&out.fs_type), info.fs_type_name().unwrap_or_default());
+
+        let status = unsafe { ip_check_status(path.as_ptr(), 5000) };
+        assert_eq!(status, i32::from(PathStatus::Mounted.code()));
+    }
+
+    #[test]
+    fn errors_are_reported() {
+        let mut out = std::mem::MaybeUninit::<IpInfo>::uninit();
+        let code = unsafe { ip_inspect(std::ptr::null(), out.as_mut_ptr()) };
+        assert_eq!(code, IP_ERR_INVALID_ARGUMENT);
+
+        let mut buf = [0 as c_char; 8];
+        let len = unsafe { ip_last_error_message(buf.as_mut_ptr(), buf.len()) };
+        assert_eq!(len, "path is null".len());
+        assert_eq!(c_str(&buf), "path is");
+
+        let invalid = b"\xff\0";
+        assert_eq!(
+            unsafe { ip_check_status(invalid.as_ptr().cast(), 0) },
+            IP_ERR_INVALID_ARGUMENT
+        );
+
+        // a successful call clears the message
+        let tmp = CString::new(std::env::temp_dir().to_str().unwrap()).unwrap();
+        assert!(unsafe { ip_check_status(tmp.as_ptr(), 5000) } >= 0);
+        assert_eq!(unsafe { ip_last_error_message(std::ptr::null_mut(), 0) }, 0);
+    }
+
+    #[test]
+    fn panics_stay_on_the_rust_side() {
+        assert_eq!(call(|| panic!("boom")), IP_ERR_PANIC);
+        let mut buf = [0 as c_char; 32];
+        unsafe { ip_last_error_message(buf.as_mut_ptr(), buf.len()) };
+        assert_eq!(c_str(&buf), "panic: boom");
+    }
+
+    #[test]
+    fn checked_in_header_is_current() {
+        let generated = include_str!(concat!(env!("OUT_DIR"), "/inspect_path.h"));
+        let checked_in = include_str!("../include/inspect_path.h");
+        assert!(
+            generated == checked_in,
+            "include/inspect_path.h is stale; copy {}/inspect_path.h over it",
+            env!("OUT_DIR")
+        );
+    }
+
+    #[test]
+    fn truncates_at_char_boundaries() {
+        let mut buf = [1 as c_char; 3];
+        assert_eq!(copy_str("héllo", &mut buf), 6);
+        // `é` is two bytes and doesn't fit after `h` with the NUL
+        assert_eq!(c_str(&buf), "h");
+        assert_eq!(copy_str("x", &mut []), 1);
+    }
+}
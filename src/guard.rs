@@ -0,0 +1,355 @@
+//! Scoped network mounts, behind the `mount` feature.
+use crate::{InspectPathError, PathInfo};
+use std::{
+    ffi::{OsStr, OsString},
+    fmt,
+    path::Path,
+};
+
+/// The calls a [`MountGuard`] makes, so its state machine can be tested
+/// without a share.
+pub(crate) trait Mounter: Send + Sync {
+    fn mount(&self, local: &OsStr, remote: &OsStr) -> Result<(), InspectPathError>;
+    fn unmount(&self, local: &OsStr, force: bool) -> Result<(), InspectPathError>;
+    fn inspect(&self, path: &Path) -> Result<PathInfo, InspectPathError>;
+}
+
+#[cfg(any(windows, target_os = "linux"))]
+struct SystemMounter;
+
+#[cfg(any(windows, target_os = "linux"))]
+impl Mounter for SystemMounter {
+    fn mount(&self, local: &OsStr, remote: &OsStr) -> Result<(), InspectPathError> {
+        crate::platform::mount_path(local, remote)
+    }
+
+    fn unmount(&self, local: &OsStr, force: bool) -> Result<(), InspectPathError> {
+        #[cfg(windows)]
+        {
+            crate::platform::unmount_path(local, force)
+        }
+        #[cfg(not(windows))]
+        {
+            crate::platform::unmount_syscall(Path::new(local), force)
+        }
+    }
+
+    fn inspect(&self, path: &Path) -> Result<PathInfo, InspectPathError> {
+        crate::platform::inspect_path(path)
+    }
+}
+
+/// **Windows and Linux.** Mounts `remote` on `local` like
+/// [`mount_path`](crate::mount_path) and returns a guard that unmounts it
+/// when dropped.
+///
+/// On Windows `local` is a drive such as `Z:`, which is unmapped again; on
+/// Linux it is a directory, unmounted with `umount2(2)`.
+///
+/// For integration tests and batch jobs that must not leave shares mounted
+/// behind them, even when they fail half-way.
+///
+/// # Errors
+///
+/// Same as [`mount_path`](crate::mount_path). If the new mount can't be
+/// inspected, it is unmounted again and the inspection error returned.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use inspect_path::mount_scoped;
+///
+/// # #[cfg(windows)] {
+/// let guard = mount_scoped("Z:", r"\\server\share")?;
+/// println!("mapped {}", guard.info());
+/// std::fs::copy(r"Z:\report.csv", "report.csv")?;
+/// // Z: is unmapped here, when `guard` is dropped
+/// # }
+/// # #[cfg(target_os = "linux")] {
+/// let guard = mount_scoped("/mnt/share", "//server/share")?;
+/// std::fs::copy("/mnt/share/report.csv", "report.csv")?;
+/// // /mnt/share is unmounted here
+/// # }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[cfg(any(windows, target_os = "linux"))]
+pub fn mount_scoped(
+    local: impl AsRef<OsStr>,
+    remote: impl AsRef<OsStr>,
+) -> Result<MountGuard, InspectPathError> {
+    mount_with(local.as_ref(), remote.as_ref(), Box::new(SystemMounter))
+}
+
+pub(crate) fn mount_with(
+    local: &OsStr,
+    remote: &OsStr,
+    mounter: Box<dyn Mounter>,
+) -> Result<MountGuard, InspectPathError> {
+    mounter.mount(local, remote)?;
+    let mut root = local.to_os_string();
+    // `Z:` alone is the current directory on that drive
+    if let [_, b':'] = local.as_encoded_bytes() {
+        root.push("\\");
+    }
+    match mounter.inspect(Path::new(&root)) {
+        Ok(info) => Ok(MountGuard {
+            info,
+            mount: Mounted {
+                local: local.to_os_string(),
+                mounter: Some(mounter),
+            },
+        }),
+        Err(e) => {
+            let _ = mounter.unmount(local, true);
+            Err(e)
+        }
+    }
+}
+
+/// A mount that is removed when the guard is dropped, returned by
+/// [`mount_scoped`].
+///
+/// Dropping unmounts without forcing, so a mount with open files stays; the
+/// error is lost then, or logged with the `tracing` feature. Use
+/// [`disconnect`](Self::disconnect) to see it, or [`leak`](Self::leak) to
+/// keep the mount.
+#[must_use = "the share is unmounted as soon as the guard is dropped"]
+pub struct MountGuard {
+    info: PathInfo,
+    mount: Mounted,
+}
+
+/// The part of a [`MountGuard`] that unmounts on drop, so that
+/// [`leak`](MountGuard::leak) can move the info out.
+struct Mounted {
+    local: OsString,
+    // `None` once disconnected or leaked
+    mounter: Option<Box<dyn Mounter>>,
+}
+
+impl MountGuard {
+    /// The mount, inspected right after mounting it.
+    pub fn info(&self) -> &PathInfo {
+        &self.info
+    }
+
+    /// The local name the share is mounted on, e.g. `Z:` or `/mnt/share`.
+    pub fn local(&self) -> &OsStr {
+        &self.mount.local
+    }
+
+    /// Whether the guard still owns the mount, i.e. it hasn't been
+    /// disconnected.
+    pub fn is_connected(&self) -> bool {
+        self.mount.mounter.is_some()
+    }
+
+    /// Unmounts now, reporting failure.
+    ///
+    /// With `force`, the mount is removed even with files open on it; on
+    /// Linux it is detached lazily and goes away once they're closed. On
+    /// failure the guard keeps the mount, so the call can be retried (e.g.
+    /// with `force`) and dropping tries once more. Once disconnected, further
+    /// calls do nothing.
+    ///
+    /// # Errors
+    ///
+    /// On Windows, same as [`unmount_path`](crate::unmount_path). On Linux,
+    /// [`InspectPathError::NotPermitted`] without the privileges to unmount,
+    /// or [`InspectPathError::Sys`] with the `umount2` errno, e.g. `EBUSY`.
+    pub fn disconnect(&mut self, force: bool) -> Result<(), InspectPathError> {
+        let mount = &mut self.mount;
+        if let Some(mounter) = &mount.mounter {
+            mounter.unmount(&mount.local, force)?;
+            mount.mounter = None;
+        }
+        Ok(())
+    }
+
+    /// Keeps the mount after the guard is gone, returning its info.
+    pub fn leak(self) -> PathInfo {
+        let MountGuard { info, mut mount } = self;
+        mount.mounter = None;
+        info
+    }
+}
+
+impl Drop for Mounted {
+    fn drop(&mut self) {
+        if let Some(mounter) = self.mounter.take()
+            && let Err(e) = mounter.unmount(&self.local, false)
+        {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(local = ?self.local, error = %e, "failed to unmount on drop");
+            #[cfg(not(feature = "tracing"))]
+            let _ = e;
+        }
+    }
+}
+
+impl fmt::Debug for MountGuard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MountGuard")
+            .field("local", &self.mount.local)
+            .field("path", self.info.path())
+            .field("connected", &self.is_connected())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PathStatus, PathType};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Call {
+        Mount,
+        Unmount { force: bool },
+    }
+
+    /// Records calls; unmounting without `force` fails while `busy` is set.
+    #[derive(Clone, Default)]
+    struct MockMounter {
+        calls: Arc<Mutex<Vec<Call>>>,
+        busy: Arc<Mutex<bool>>,
+        broken: bool,
+    }
+
+    impl Mounter for MockMounter {
+        fn mount(&self, _: &OsStr, _: &OsStr) -> Result<(), InspectPathError> {
+            self.calls.lock().unwrap().push(Call::Mount);
+            Ok(())
+        }
+
+        fn unmount(&self, local: &OsStr, force: bool) -> Result<(), InspectPathError> {
+            self.calls.lock().unwrap().push(Call::Unmount { force });
+            if *self.busy.lock().unwrap() && !force {
+                return Err(InspectPathError::Sys {
+                    path: local.into(),
+                    errno: 2401, // ERROR_OPEN_FILES
+                    context: "WNetCancelConnection2W",
+                });
+            }
+            Ok(())
+        }
+
+        fn inspect(&self, path: &Path) -> Result<PathInfo, InspectPathError> {
+            if self.broken {
+                return Err(InspectPathError::PathTypeError(path.into()));
+            }
+            Ok(PathInfo::synthetic(
+                path.into(),
+                PathType::Remote,
+                None,
+                PathStatus::Mounted,
+            ))
+        }
+    }
+
+    impl MockMounter {
+        fn guard(&self) -> Result<MountGuard, InspectPathError> {
+            self.guard_on("Z:")
+        }
+
+        fn guard_on(&self, local: &str) -> Result<MountGuard, InspectPathError> {
+            mount_with(
+                OsStr::new(local),
+                OsStr::new(r"\\server\share"),
+                Box::new(self.clone()),
+            )
+        }
+
+        fn calls(&self) -> Vec<Call> {
+            self.calls.lock().unwrap().clone()
+        }
+    }
+
+    #[test]
+    fn drop_unmounts_once() {
+        let mock = MockMounter::default();
+        let guard = mock.guard().unwrap();
+        assert_eq!(guard.info().path(), Path::new(r"Z:\"));
+        assert_eq!(guard.local(), "Z:");
+        assert!(guard.is_connected());
+        drop(guard);
+        assert_eq!(mock.calls(), [Call::Mount, Call::Unmount { force: false }]);
+    }
+
+    #[test]
+    fn directories_are_inspected_as_given() {
+        let mock = MockMounter::default();
+        let guard = mock.guard_on("/mnt/share").unwrap();
+        assert_eq!(guard.info().path(), Path::new("/mnt/share"));
+        assert_eq!(guard.local(), "/mnt/share");
+    }
+
+    #[test]
+    fn disconnect_reports_and_can_retry() {
+        let mock = MockMounter::default();
+        *mock.busy.lock().unwrap() = true;
+        let mut guard = mock.guard().unwrap();
+
+        assert!(guard.disconnect(false).is_err());
+        assert!(guard.is_connected());
+        guard.disconnect(true).unwrap();
+        assert!(!guard.is_connected());
+        guard.disconnect(false).unwrap();
+        drop(guard);
+        assert_eq!(
+            mock.calls(),
+            [
+                Call::Mount,
+                Call::Unmount { force: false },
+                Call::Unmount { force: true },
+            ]
+        );
+    }
+
+    #[test]
+    fn failed_drop_and_leak_are_quiet() {
+        let mock = MockMounter::default();
+        *mock.busy.lock().unwrap() = true;
+        drop(mock.guard().unwrap());
+
+        let info = mock.guard().unwrap().leak();
+        assert_eq!(info.kind(), &PathType::Remote);
+        assert_eq!(
+            mock.calls(),
+            [Call::Mount, Call::Unmount { force: false }, Call::Mount]
+        );
+    }
+
+    #[test]
+    fn unmounts_when_inspection_fails() {
+        let mock = MockMounter {
+            broken: true,
+            ..Default::default()
+        };
+        assert!(mock.guard().is_err());
+        assert_eq!(mock.calls(), [Call::Mount, Call::Unmount { force: true }]);
+    }
+
+    /// Maps `INSPECT_PATH_LOOPBACK_SHARE` (e.g. `\\localhost\C$`) to
+    /// `INSPECT_PATH_LOOPBACK_DRIVE` (default `Q:`); skipped without it.
+    #[cfg(windows)]
+    #[test]
+    fn loopback_share() {
+        let Ok(share) = std::env::var("INSPECT_PATH_LOOPBACK_SHARE") else {
+            return;
+        };
+        let drive = std::env::var("INSPECT_PATH_LOOPBACK_DRIVE").unwrap_or_else(|_| "Q:".into());
+        let root = format!(r"{drive}\");
+
+        let mut guard = mount_scoped(&drive, &share).unwrap();
+        assert_eq!(guard.info().kind(), &PathType::Remote);
+        assert!(Path::new(&root).exists());
+        guard.disconnect(false).unwrap();
+        assert!(crate::inspect_path(&root).is_err());
+        drop(guard);
+
+        drop(mount_scoped(&drive, &share).unwrap());
+        assert!(crate::inspect_path(&root).is_err());
+    }
+}
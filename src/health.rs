@@ -0,0 +1,308 @@
+use crate::{
+    PathStatus,
+    clock::{Clock, SystemClock},
+    platform,
+    probe::status_with_timeout,
+};
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    path::Path,
+    sync::Arc,
+    time::Duration,
+};
+
+/// How long [`health_check`] waits between two attempts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Backoff {
+    /// The same delay after every failed attempt.
+    Fixed(Duration),
+    /// `initial`, doubled after every failed attempt up to `max`.
+    ///
+    /// With `jitter`, each delay is picked at random between half and all of
+    /// that value, so many clients retrying the same server spread out.
+    Exponential {
+        initial: Duration,
+        max: Duration,
+        jitter: bool,
+    },
+}
+
+impl Backoff {
+    /// The delay after the `failed`-th failed attempt (1-based), before jitter.
+    fn base_delay(&self, failed: u32) -> Duration {
+        match *self {
+            Backoff::Fixed(delay) => delay,
+            Backoff::Exponential { initial, max, .. } => {
+                let factor = 1u32
+                    .checked_shl(failed.saturating_sub(1))
+                    .unwrap_or(u32::MAX);
+                initial.saturating_mul(factor).min(max)
+            }
+        }
+    }
+
+    fn delay(&self, failed: u32) -> Duration {
+        let base = self.base_delay(failed);
+        match self {
+            Backoff::Exponential { jitter: true, .. } => {
+                let random = RandomState::new().build_hasher().finish();
+                let fraction = 0.5 + (random % 1_000) as f64 / 2_000.0;
+                base.mul_f64(fraction)
+            }
+            _ => base,
+        }
+    }
+}
+
+/// Attempts, per-attempt timeout and backoff for [`health_check`].
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use inspect_path::{Backoff, RetryPolicy};
+///
+/// let policy = RetryPolicy::new()
+///     .attempts(5)
+///     .timeout(Duration::from_secs(1))
+///     .backoff(Backoff::Fixed(Duration::from_millis(500)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    attempts: u32,
+    timeout: Duration,
+    backoff: Backoff,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            attempts: 3,
+            timeout: Duration::from_secs(2),
+            backoff: Backoff::Exponential {
+                initial: Duration::from_millis(200),
+                max: Duration::from_secs(5),
+                jitter: true,
+            },
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maximum number of probes. Defaults to 3; values below 1 are treated
+    /// as 1.
+    pub fn attempts(mut self, attempts: u32) -> Self {
+        self.attempts = attempts.max(1);
+        self
+    }
+
+    /// Deadline for a single probe, see [`check_status_timeout`](crate::check_status_timeout).
+    /// Defaults to 2 seconds.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Delay between attempts. Defaults to exponential backoff from 200 ms
+    /// up to 5 seconds, with jitter.
+    pub fn backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+}
+
+/// The outcome of a [`health_check`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HealthReport {
+    /// The status of the last attempt.
+    pub status: PathStatus,
+    /// How many probes were made.
+    pub attempts: u32,
+    /// Time from the first probe until the last one returned, backoff
+    /// included.
+    pub elapsed: Duration,
+    /// Why the last failed attempt failed, or `None` if every attempt found
+    /// the path mounted.
+    pub last_error: Option<String>,
+}
+
+impl HealthReport {
+    /// Whether the final attempt found the path mounted.
+    pub fn is_healthy(&self) -> bool {
+        self.status == PathStatus::Mounted
+    }
+}
+
+/// Probes `path` until it answers [`PathStatus::Mounted`] or the policy's
+/// attempts are used up.
+///
+/// Each attempt is a [`check_status_timeout`](crate::check_status_timeout)
+/// with the policy's timeout, so a hung mount costs at most one timeout per
+/// attempt and shares the cap on abandoned probe threads. Between attempts
+/// the calling thread sleeps according to the policy's [`Backoff`].
+///
+/// A single failed probe is common on SMB and NFS shares under load; this
+/// tells a hiccup apart from an outage.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::path::Path;
+/// use inspect_path::{RetryPolicy, health_check};
+///
+/// let report = health_check(Path::new(r"\\nas\backup"), &RetryPolicy::new());
+/// if !report.is_healthy() {
+///     eprintln!("nas down after {} attempts: {:?}", report.attempts, report.last_error);
+/// }
+/// ```
+pub fn health_check(path: impl AsRef<Path>, policy: &RetryPolicy) -> HealthReport {
+    health_check_with(path.as_ref(), policy, &SystemClock, |p| {
+        platform::check_status(p)
+    })
+}
+
+pub(crate) fn health_check_with<F>(
+    path: &Path,
+    policy: &RetryPolicy,
+    clock: &dyn Clock,
+    probe: F,
+) -> HealthReport
+where
+    F: Fn(&Path) -> PathStatus + Send + Sync + 'static,
+{
+    let probe = Arc::new(probe);
+    let start = clock.now();
+    let mut last_error = None;
+    let mut attempts = 0;
+
+    loop {
+        attempts += 1;
+        let attempt = Arc::clone(&probe);
+        let status = status_with_timeout(path, policy.timeout, clock, move |p| attempt(p));
+        let done = status == PathStatus::Mounted || attempts >= policy.attempts;
+        if status != PathStatus::Mounted {
+            last_error = Some(status.to_string());
+        }
+        if done {
+            return HealthReport {
+                status,
+                attempts,
+                elapsed: clock.now() - start,
+                last_error,
+            };
+        }
+        clock.sleep(policy.backoff.delay(attempts));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// A probe that fails `failures` times, then succeeds.
+    fn flaky(failures: u32) -> (Arc<AtomicU32>, impl Fn(&Path) -> PathStatus + Send + Sync) {
+        let calls = Arc::new(AtomicU32::new(0));
+        let counter = Arc::clone(&calls);
+        let probe = move |_: &Path| {
+            if counter.fetch_add(1, Ordering::SeqCst) < failures {
+                PathStatus::Disconnected
+            } else {
+                PathStatus::Mounted
+            }
+        };
+        (calls, probe)
+    }
+
+    #[test]
+    fn retries_until_mounted() {
+        let clock = MockClock::new();
+        let policy = RetryPolicy::new()
+            .attempts(5)
+            .backoff(Backoff::Fixed(Duration::from_secs(1)));
+        let (calls, probe) = flaky(2);
+
+        let report = health_check_with(Path::new("/health/flaky"), &policy, &clock, probe);
+        assert_eq!(report.status, PathStatus::Mounted);
+        assert!(report.is_healthy());
+        assert_eq!(report.attempts, 3);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        assert_eq!(report.elapsed, Duration::from_secs(2));
+        assert_eq!(report.last_error.as_deref(), Some("disconnected"));
+    }
+
+    #[test]
+    fn first_success_short_circuits() {
+        let clock = MockClock::new();
+        let (calls, probe) = flaky(0);
+
+        let report = health_check_with(Path::new("/health/ok"), &RetryPolicy::new(), &clock, probe);
+        assert_eq!(report.attempts, 1);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(report.elapsed, Duration::ZERO);
+        assert_eq!(report.last_error, None);
+    }
+
+    #[test]
+    fn gives_up_with_exponential_backoff() {
+        let clock = MockClock::new();
+        let policy = RetryPolicy::new()
+            .attempts(4)
+            .backoff(Backoff::Exponential {
+                initial: Duration::from_millis(100),
+                max: Duration::from_millis(300),
+                jitter: false,
+            });
+        let (calls, probe) = flaky(10);
+
+        let report = health_check_with(Path::new("/health/down"), &policy, &clock, probe);
+        assert_eq!(report.status, PathStatus::Disconnected);
+        assert_eq!(report.attempts, 4);
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+        // 100 + 200 + 300 (capped); no sleep after the last attempt
+        assert_eq!(report.elapsed, Duration::from_millis(600));
+        assert_eq!(report.last_error.as_deref(), Some("disconnected"));
+    }
+
+    #[test]
+    fn timeouts_count_as_failures() {
+        let clock = Arc::new(MockClock::new());
+        let policy = RetryPolicy::new()
+            .attempts(2)
+            .timeout(Duration::from_secs(1))
+            .backoff(Backoff::Fixed(Duration::from_secs(3)));
+        let slow = Arc::clone(&clock);
+        let probe = move |_: &Path| {
+            slow.sleep(Duration::from_secs(2));
+            PathStatus::Mounted
+        };
+
+        let report = health_check_with(Path::new("/health/hung"), &policy, &*clock, probe);
+        assert_eq!(report.status, PathStatus::TimedOut);
+        assert_eq!(report.attempts, 2);
+        assert_eq!(report.elapsed, Duration::from_secs(7));
+    }
+
+    #[test]
+    fn jitter_stays_within_half_to_full_delay() {
+        let backoff = Backoff::Exponential {
+            initial: Duration::from_secs(1),
+            max: Duration::from_secs(60),
+            jitter: true,
+        };
+        for failed in 1..=5 {
+            let base = backoff.base_delay(failed);
+            assert_eq!(base, Duration::from_secs(1 << (failed - 1)));
+            let delay = backoff.delay(failed);
+            assert!(delay >= base / 2 && delay <= base, "{delay:?} vs {base:?}");
+        }
+        assert_eq!(backoff.base_delay(100), Duration::from_secs(60));
+    }
+}
@@ -0,0 +1,297 @@
+use crate::{InspectOptions, InspectPathError, PathInfo, PathStatus, platform};
+use std::ffi::OsStr;
+use std::path::Path;
+#[cfg(feature = "test-util")]
+use {
+    crate::{PathType, RemoteType},
+    std::{
+        collections::HashMap,
+        path::PathBuf,
+        sync::{Mutex, PoisonError},
+    },
+};
+
+/// The system calls behind [`inspect_path`](crate::inspect_path) and
+/// [`check_status`](crate::check_status), as a trait.
+///
+/// Code written against `&dyn PathInspector` runs on [`SystemInspector`] in
+/// production and on a `MockInspector` (with the `test-util` feature) in
+/// tests, so that a disconnected share can be tested without one.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::path::Path;
+/// use inspect_path::{PathInspector, SystemInspector};
+///
+/// fn can_save_to(inspector: &dyn PathInspector, dir: &Path) -> bool {
+///     match inspector.inspect(dir) {
+///         Ok(info) if info.is_remote() => inspector.check_status(dir).into_bool(),
+///         Ok(_) => true,
+///         Err(_) => false,
+///     }
+/// }
+///
+/// assert!(can_save_to(&SystemInspector, Path::new("/tmp")));
+/// ```
+pub trait PathInspector {
+    /// See [`inspect_path`](crate::inspect_path).
+    fn inspect(&self, path: &Path) -> Result<PathInfo, InspectPathError>;
+
+    /// See [`check_status`](crate::check_status).
+    fn check_status(&self, path: &Path) -> PathStatus;
+
+    /// Mounts the network share `remote` on the local name `local`, see
+    /// `mount_path`. Fails with [`InspectPathError::Unsupported`] unless
+    /// implemented, as it is for [`SystemInspector`] on Windows and Linux with
+    /// the `mount` feature.
+    fn mount(&self, local: &OsStr, remote: &OsStr) -> Result<(), InspectPathError> {
+        let _ = (local, remote);
+        Err(InspectPathError::Unsupported)
+    }
+}
+
+/// The [`PathInspector`] that asks the operating system. The free functions
+/// [`inspect_path`](crate::inspect_path),
+/// [`check_status`](crate::check_status) and `mount_path` call it.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemInspector;
+
+impl PathInspector for SystemInspector {
+    fn inspect(&self, path: &Path) -> Result<PathInfo, InspectPathError> {
+        crate::inspect_path_with(path, &InspectOptions::default())
+    }
+
+    fn check_status(&self, path: &Path) -> PathStatus {
+        platform::probe_status(path).unwrap_or(PathStatus::Unknown)
+    }
+
+    #[cfg(all(feature = "mount", any(windows, target_os = "linux")))]
+    fn mount(&self, local: &OsStr, remote: &OsStr) -> Result<(), InspectPathError> {
+        platform::mount_share(local, remote)
+    }
+}
+
+/// A call made on a [`MockInspector`].
+#[cfg(feature = "test-util")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MockCall {
+    Inspect(PathBuf),
+    CheckStatus(PathBuf),
+    Mount {
+        local: std::ffi::OsString,
+        remote: std::ffi::OsString,
+    },
+}
+
+#[cfg(feature = "test-util")]
+#[derive(Debug)]
+enum Canned {
+    Info(Box<PathInfo>),
+    Unavailable(PathStatus),
+}
+
+/// A [`PathInspector`] serving canned answers per path and recording every
+/// call, built with the `test-util` feature.
+///
+/// Paths are looked up exactly as they are passed, without normalizing.
+/// Inspecting a path that wasn't set up fails with
+/// [`PathTypeError`](InspectPathError::PathTypeError), and its status is
+/// [`Unknown`](PathStatus::Unknown). Mounting always succeeds.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::path::Path;
+/// use inspect_path::{MockCall, MockInspector, PathInspector, PathStatus, PathType};
+///
+/// fn can_save_to(inspector: &dyn PathInspector, dir: &Path) -> bool {
+///     match inspector.inspect(dir) {
+///         Ok(info) if info.is_remote() => inspector.check_status(dir).into_bool(),
+///         Ok(_) => true,
+///         Err(_) => false,
+///     }
+/// }
+///
+/// let share = Path::new("/mnt/share");
+/// let mock = MockInspector::new().with_path(share, PathType::Remote, PathStatus::Mounted);
+/// assert!(can_save_to(&mock, share));
+///
+/// mock.set_status(share, PathStatus::Disconnected);
+/// assert!(!can_save_to(&mock, share));
+/// assert_eq!(
+///     mock.calls()[2..],
+///     [MockCall::Inspect(share.into()), MockCall::CheckStatus(share.into())]
+/// );
+/// ```
+#[cfg(feature = "test-util")]
+#[derive(Debug, Default)]
+pub struct MockInspector {
+    canned: Mutex<HashMap<PathBuf, Canned>>,
+    calls: Mutex<Vec<MockCall>>,
+}
+
+#[cfg(feature = "test-util")]
+impl MockInspector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answers for `path` with a `PathInfo` of `kind` and `status` and no
+    /// volume details. A [`Remote`](PathType::Remote) path gets an
+    /// [`Unknown`](RemoteType::Unknown) remote type.
+    pub fn with_path(self, path: impl Into<PathBuf>, kind: PathType, status: PathStatus) -> Self {
+        let path = path.into();
+        let remote_kind = (kind == PathType::Remote).then_some(RemoteType::Unknown);
+        let info = PathInfo::synthetic(path.clone(), kind, remote_kind, status);
+        self.set(path, Canned::Info(Box::new(info)));
+        self
+    }
+
+    /// Answers for `info.path()` with `info`, e.g. one captured from a real
+    /// system or deserialized.
+    pub fn with_info(self, info: PathInfo) -> Self {
+        self.set(info.path().clone(), Canned::Info(Box::new(info)));
+        self
+    }
+
+    /// Fails inspecting `path` with
+    /// [`Unavailable`](InspectPathError::Unavailable), and reports `status`
+    /// from `check_status`.
+    pub fn with_unavailable(self, path: impl Into<PathBuf>, status: PathStatus) -> Self {
+        self.set(path.into(), Canned::Unavailable(status));
+        self
+    }
+
+    /// Changes the status reported for `path` from now on, e.g. to simulate
+    /// a share dropping mid-test. A path that wasn't set up gets an
+    /// [`Unknown`](PathType::Unknown) `PathInfo`.
+    pub fn set_status(&self, path: impl Into<PathBuf>, status: PathStatus) {
+        let path = path.into();
+        let mut canned = self.canned.lock().unwrap_or_else(PoisonError::into_inner);
+        match canned.get_mut(&path) {
+            Some(Canned::Info(info)) => info.status = status,
+            Some(Canned::Unavailable(old)) => *old = status,
+            None => {
+                let info = PathInfo::synthetic(path.clone(), PathType::Unknown, None, status);
+                canned.insert(path, Canned::Info(Box::new(info)));
+            }
+        }
+    }
+
+    /// Every call made so far, oldest first.
+    pub fn calls(&self) -> Vec<MockCall> {
+        self.calls
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    fn set(&self, path: PathBuf, answer: Canned) {
+        self.canned
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(path, answer);
+    }
+
+    fn record(&self, call: MockCall) {
+        self.calls
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(call);
+    }
+}
+
+#[cfg(feature = "test-util")]
+impl PathInspector for MockInspector {
+    fn inspect(&self, path: &Path) -> Result<PathInfo, InspectPathError> {
+        self.record(MockCall::Inspect(path.to_path_buf()));
+        let canned = self.canned.lock().unwrap_or_else(PoisonError::into_inner);
+        match canned.get(path) {
+            Some(Canned::Info(info)) => Ok(PathInfo::clone(info)),
+            Some(Canned::Unavailable(status)) => Err(InspectPathError::Unavailable {
+                path: path.to_path_buf(),
+                status: status.clone(),
+            }),
+            None => Err(InspectPathError::PathTypeError(path.to_path_buf())),
+        }
+    }
+
+    fn check_status(&self, path: &Path) -> PathStatus {
+        self.record(MockCall::CheckStatus(path.to_path_buf()));
+        let canned = self.canned.lock().unwrap_or_else(PoisonError::into_inner);
+        match canned.get(path) {
+            Some(Canned::Info(info)) => info.status().clone(),
+            Some(Canned::Unavailable(status)) => status.clone(),
+            None => PathStatus::Unknown,
+        }
+    }
+
+    fn mount(&self, local: &OsStr, remote: &OsStr) -> Result<(), InspectPathError> {
+        self.record(MockCall::Mount {
+            local: local.into(),
+            remote: remote.into(),
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn system_matches_free_functions() {
+        let tmp = std::env::temp_dir();
+        let inspector: &dyn PathInspector = &SystemInspector;
+        assert_eq!(
+            inspector.inspect(&tmp).unwrap(),
+            crate::inspect_path(&tmp).unwrap()
+        );
+        assert_eq!(inspector.check_status(&tmp), PathStatus::Mounted);
+        assert_eq!(
+            inspector.check_status(Path::new("")),
+            crate::check_status("")
+        );
+    }
+
+    #[test]
+    fn mount_is_unsupported_by_default() {
+        struct Inspect;
+        impl PathInspector for Inspect {
+            fn inspect(&self, path: &Path) -> Result<PathInfo, InspectPathError> {
+                Err(InspectPathError::PathTypeError(path.to_path_buf()))
+            }
+            fn check_status(&self, _path: &Path) -> PathStatus {
+                PathStatus::Unknown
+            }
+        }
+        let err = Inspect.mount(OsStr::new("/mnt/share"), OsStr::new("//srv/share"));
+        assert!(matches!(err, Err(InspectPathError::Unsupported)));
+    }
+
+    #[cfg(feature = "test-util")]
+    #[test]
+    fn mock_serves_and_records() {
+        let gone = Path::new("/mnt/gone");
+        let mock = MockInspector::new().with_unavailable(gone, PathStatus::Stale);
+
+        assert!(matches!(
+            mock.inspect(gone),
+            Err(InspectPathError::Unavailable {
+                status: PathStatus::Stale,
+                ..
+            })
+        ));
+        assert_eq!(
+            mock.check_status(Path::new("/elsewhere")),
+            PathStatus::Unknown
+        );
+        assert!(mock.inspect(Path::new("/elsewhere")).is_err());
+
+        mock.set_status(gone, PathStatus::Mounted);
+        assert_eq!(mock.check_status(gone), PathStatus::Mounted);
+        assert_eq!(mock.calls().len(), 4);
+    }
+}
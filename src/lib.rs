@@ -15,13 +15,16 @@
 //!   * Network shares (UNC paths and mapped drives)
 //!
 //! * **Unix**
-//!   * Basic filesystem detection (expanding in future releases)
+//!   * Local, removable and optical devices, RAM disks and network mounts
+//!   * Virtual filesystems (`proc`, `sysfs`, ...) as [`PathType::Virtual`]
+//!   * Linux reads `/proc/self/mountinfo`; FreeBSD, OpenBSD and macOS
+//!     classify mounts from `getfsstat` and their `f_fstypename`
 //!
 //! # Examples
 //!
 //! ```rust
 //! use std::path::Path;
-//! use inspect_path::inspect_path;
+//! use inspect_path::{PathStatus, inspect_path};
 //!
 //! # #[cfg(target_os = "windows")]
 //! # {
@@ -32,12 +35,16 @@
 //! }
 //! # }
 //!
-//! # #[cfg(target_os = "unix")]
+//! # #[cfg(target_family = "unix")]
 //! # {
 //! let mut info = inspect_path(Path::new("/home/")).unwrap();
 //!
+//! // /proc, /sys and friends have no storage behind them
+//! assert!(inspect_path(Path::new("/proc")).unwrap().is_virtual());
+//!
 //! if info.is_status_unknown() {
-//!     info.check_status();
+//!     let previous = info.check_status();
+//!     assert_eq!(previous, PathStatus::Unknown);
 //!     if info.is_status_mounted() {
 //!         println!("Path Mounted!")
 //!     }
@@ -49,82 +56,489 @@
 //!
 //! Some operations (such as determining network mount status) may perform
 //! blocking I/O depending on the platform and filesystem.
+//!
+//! # Symlinks
+//!
+//! A path can be matched against the mount table in three ways:
+//!
+//! * **As written:** [`MountTable`], [`is_network_path_with`] and, on Linux,
+//!   `inspect_in_namespace` match the path lexically without touching it, so
+//!   no symlink is followed.
+//! * **Parents resolved:** [`inspect_path_lexical`] follows symlinks in the
+//!   parent directories but not in the final component, classifying where a
+//!   symlink lives rather than where it points. On Windows this covers
+//!   junctions too.
+//! * **Fully resolved:** [`inspect_path`] and everything built on it
+//!   canonicalize the path first on Unix, following every symlink.
+//!
+//! # Matching on enums
+//!
+//! [`PathType`], [`RemoteType`], [`PathStatus`] and [`InspectPathError`] are
+//! `#[non_exhaustive]`: new filesystems and failure modes get new variants in
+//! minor releases. Always include a `_` arm when matching on them:
+//!
+//! ```rust
+//! use inspect_path::RemoteType;
+//!
+//! fn describe(remote: &RemoteType) -> &'static str {
+//!     match remote {
+//!         RemoteType::SMB => "windows share",
+//!         RemoteType::NFS => "nfs export",
+//!         _ => "other network filesystem",
+//!     }
+//! }
+//! # assert_eq!(describe(&RemoteType::NFS), "nfs export");
+//! ```
+//!
+//! # Serde
+//!
+//! With the `serde` feature enabled, [`PathInfo`], [`PathType`], [`RemoteType`],
+//! [`PathKind`] and [`PathStatus`] implement `Serialize` and `Deserialize`. Enums use serde's
+//! externally-tagged form with lowercase variant names (`"fixed"`,
+//! `{"virtual":"proc"}`, `{"other":"..."}`) and paths are plain strings.
+//!
+//! The field and variant names are a wire format: they will only change in a
+//! semver-breaking release.
+//!
+//! The `schemars` feature (which implies `serde`) additionally derives
+//! `schemars::JsonSchema` for the same types, [`SpaceInfo`] and [`Quota`],
+//! matching the serde representation, so payloads can be validated against a
+//! JSON Schema. The Unix mount table entry inside a [`PathInfo`] is described
+//! only as an opaque object.
+//!
+//! # Read-only builds
+//!
+//! Everything that changes system state (on Windows, mapping and unmapping
+//! network drives with `mount_path`, `unmount_path` and
+//! `try_mount_if_needed`; on Unix, mounting shares with
+//! `try_mount_if_needed`) is behind the default `mount` feature. With
+//! `default-features = false` those functions don't exist, so a dependent can
+//! show at compile time that it only inspects.
+//!
+//! On targets that are neither Windows nor Unix, such as
+//! `wasm32-unknown-unknown`, the crate still compiles so that its types can
+//! be shared, but inspecting fails with [`InspectPathError::Unsupported`] and
+//! [`check_status`] returns [`PathStatus::Unknown`].
 use std::{
+    fmt,
     num::ParseIntError,
     path::{Path, PathBuf},
 };
 use thiserror::Error;
 
+mod capabilities;
+mod clock;
+mod codes;
+#[cfg(all(any(windows, target_os = "linux", docsrs), feature = "mount"))]
+mod credentials;
+mod device;
+#[cfg(any(target_os = "linux", target_os = "windows"))]
+mod events;
+mod ext;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+// tests on targets that can't mount drive it with a mock
+#[cfg(all(feature = "mount", any(windows, target_os = "linux", test)))]
+mod guard;
+mod health;
+mod inspector;
+mod netpath;
+mod normalize;
+mod options;
 pub mod platform;
+pub mod prelude;
+mod probe;
+mod remote;
+mod report;
+mod session;
+mod space;
+mod transfer;
+mod uri;
+mod wait;
+mod watcher;
+
+pub use capabilities::{Capabilities, capabilities};
+pub use device::DeviceId;
+#[cfg(any(target_os = "linux", target_os = "windows"))]
+pub use events::{MountEvent, MountEventKind, MountEvents};
+pub use ext::PathExt;
+pub use health::{Backoff, HealthReport, RetryPolicy, health_check};
+#[cfg(feature = "test-util")]
+pub use inspector::{MockCall, MockInspector};
+pub use inspector::{PathInspector, SystemInspector};
+pub use netpath::{is_network_path, is_network_path_with};
+pub use normalize::{normalize_for_display, normalize_path};
+pub use options::{InspectOptions, VolumeFilter};
+#[cfg(all(windows, feature = "test-util"))]
+pub use platform::record_win32_calls;
+pub use probe::{
+    check_status_batch, check_status_timeout, check_statuses, clear_slow_probe_handler,
+    set_slow_probe_handler,
+};
+pub use report::{InspectReport, inspect_report};
+pub use session::InspectSession;
+pub use space::{Quota, SpaceInfo, SpaceThreshold};
+pub use transfer::{MovePlan, MoveRefusal, execute_move, plan_move};
+pub use uri::inspect_uri;
+#[cfg(feature = "tokio")]
+pub use wait::wait_for_status_async;
+pub use wait::{WaitError, wait_for_status};
+pub use watcher::{PathWatcher, StatusChange, WatchOptions};
 
 /// Always available APIs
-pub use platform::{check_status, inspect_path};
+pub use platform::{
+    MountTable, check_status, crosses_mount, inspect_path, inspect_paths, list_volumes,
+    probe_status, same_filesystem,
+};
+
+#[cfg(all(any(windows, target_os = "linux", docsrs), feature = "mount"))]
+pub use credentials::CredentialStore;
+#[cfg(all(any(windows, target_os = "linux"), feature = "mount"))]
+pub use guard::{MountGuard, mount_scoped};
+#[cfg(all(any(windows, target_os = "linux", docsrs), feature = "mount"))]
+pub use platform::mount_path;
+/// APIs that mount network shares, behind the `mount` feature
+#[cfg(all(any(windows, unix), feature = "mount"))]
+pub use platform::try_mount_if_needed;
+#[cfg(all(unix, feature = "mount"))]
+pub use platform::{MountMethod, MountSpec, try_mount_if_needed_with};
+/// Windows-only APIs that map and unmap network drives, behind the `mount`
+/// feature
+#[cfg(all(any(windows, docsrs), feature = "mount"))]
+pub use platform::{mount_path_as_user, try_mount_if_needed_as_user, unmount_path};
 
 /// Windows-only APIs
-#[cfg(any(windows, docsrs))]
-pub use platform::{mount_path, mount_path_as_user, try_mount_if_needed, try_mount_if_needed_as_user};
+#[cfg(windows)]
+pub use platform::{first_free_drive_letter, used_drive_letters};
+
+#[cfg(any(unix, windows))]
+pub use platform::inspect_path_lexical;
+
+/// Unix-only APIs
+#[cfg(unix)]
+pub use platform::{DeviceNumber, MountInfo, inspect_path_verbose, mounts};
 
-// Unix-only APIs
-//#[cfg(unix)]
-//#[cfg_attr(docsrs, doc(cfg(unix)))]
+/// Linux-only APIs
+#[cfg(target_os = "linux")]
+pub use platform::{check_status_fast, inspect_in_namespace};
 
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum InspectPathError {
-    #[error("Parse Int Error")]
-    ParseInt(#[from] ParseIntError),
-    #[error("Parse General Error")]
-    ParseGen,
-    #[error("I/O Error")]
-    Io(#[from] std::io::Error),
-    #[error("Failed to get path type")]
-    PathTypeError,
+    /// A number in a system file (e.g. a sysfs attribute) didn't parse.
+    #[error("Invalid number in '{}'", path.display())]
+    ParseInt {
+        path: PathBuf,
+        source: ParseIntError,
+    },
+    /// A malformed mount table, with the file it was read from, or a path
+    /// no mount covers.
+    #[error("Parse error on '{}': {msg}", path.display())]
+    ParseGen { path: PathBuf, msg: &'static str },
+    /// A system file, e.g. a mountinfo line, that is larger than this crate
+    /// accepts; `what` names the limit.
+    #[error("{what} in '{}' exceeds the limit of {limit}", path.display())]
+    TooLarge {
+        path: PathBuf,
+        what: &'static str,
+        limit: usize,
+    },
+    #[error("I/O error on '{}'", path.display())]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("{context} failed for '{}' (os error {errno})", path.display())]
+    Sys {
+        path: PathBuf,
+        errno: i32,
+        context: &'static str,
+    },
+    #[error("Failed to get path type of '{}'", .0.display())]
+    PathTypeError(PathBuf),
     #[error("Invalid path '{0}'")]
     InvalidPath(String),
-    #[error("General Error '{0}'")]
-    General(String),
+    /// Any other failure, with the path it concerns if there is one.
+    #[error(
+        "General Error '{msg}'{}",
+        path.as_ref().map(|p| format!(" on '{}'", p.display())).unwrap_or_default()
+    )]
+    General { path: Option<PathBuf>, msg: String },
+    #[error("'{}' is unavailable ({status})", path.display())]
+    Unavailable { path: PathBuf, status: PathStatus },
+    /// Mounting needs privileges the process lacks, e.g. `CAP_SYS_ADMIN` for
+    /// `mount(2)`, or the `user` option on an fstab entry.
+    #[error("Not permitted to mount on '{}'", path.display())]
+    NotPermitted { path: PathBuf },
+    /// A path needed mounting, but no remote was given to mount on it.
+    #[error("No remote given to mount on '{}'", .0.display())]
+    NoRemote(PathBuf),
+    /// The mount command or system call failed; `msg` is its error output.
+    #[error("Mounting on '{}' failed: {msg}", path.display())]
+    MountFailed { path: PathBuf, msg: String },
+    /// The target has no backend, e.g. `wasm32-unknown-unknown`; the crate
+    /// compiles there, but nothing can be inspected.
+    #[error("Not supported on this platform")]
+    Unsupported,
+}
+
+impl InspectPathError {
+    /// Returns the path the error is about: the path being inspected, or for
+    /// a malformed mount table or system file, the file it was read from.
+    ///
+    /// Empty for a line handed to `MountInfo::parse_line`, which has no file,
+    /// and for [`PathStatus::ok_or_disconnected`], which has only the status.
+    /// `None` only for a [`General`](Self::General) error without a path and
+    /// for [`Unsupported`](Self::Unsupported).
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            InspectPathError::ParseInt { path, .. }
+            | InspectPathError::ParseGen { path, .. }
+            | InspectPathError::TooLarge { path, .. }
+            | InspectPathError::Io { path, .. }
+            | InspectPathError::Sys { path, .. }
+            | InspectPathError::PathTypeError(path)
+            | InspectPathError::Unavailable { path, .. }
+            | InspectPathError::NotPermitted { path }
+            | InspectPathError::NoRemote(path)
+            | InspectPathError::MountFailed { path, .. } => Some(path),
+            InspectPathError::InvalidPath(path) => Some(Path::new(path)),
+            InspectPathError::General { path, .. } => path.as_deref(),
+            InspectPathError::Unsupported => None,
+        }
+    }
 }
 
 /// The connection status of a path.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+#[non_exhaustive]
 pub enum PathStatus {
     Mounted,
     Disconnected,
     Unknown,
     Other(String),
+    /// A stale network file handle; the server restarted or the export changed.
+    Stale,
+    /// The path exists but the caller may not access it.
+    #[cfg_attr(feature = "serde", serde(rename = "permission_limited"))]
+    PermissionLimited,
+    /// A removable or optical drive without media inserted.
+    #[cfg_attr(feature = "serde", serde(rename = "no_media"))]
+    NoMedia,
+    /// No device or network connection is attached where one is expected.
+    #[cfg_attr(feature = "serde", serde(rename = "not_mounted"))]
+    NotMounted,
+    /// The probe did not answer within the caller's deadline.
+    #[cfg_attr(feature = "serde", serde(rename = "timed_out"))]
+    TimedOut,
+}
+
+impl PathStatus {
+    /// Returns `Ok` for [`Mounted`](Self::Mounted) and an
+    /// [`InspectPathError::Unavailable`] error for every other status,
+    /// including [`Unknown`](Self::Unknown), for code that only asks whether a
+    /// path is usable.
+    ///
+    /// A status doesn't know its path, so the error's path is empty;
+    /// [`PathInfo::ok_or_disconnected`] fills it in.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use inspect_path::PathStatus;
+    ///
+    /// assert!(PathStatus::Mounted.ok_or_disconnected().is_ok());
+    /// assert!(PathStatus::Stale.ok_or_disconnected().is_err());
+    /// ```
+    pub fn ok_or_disconnected(&self) -> Result<(), InspectPathError> {
+        match self {
+            PathStatus::Mounted => Ok(()),
+            status => Err(InspectPathError::Unavailable {
+                path: PathBuf::new(),
+                status: status.clone(),
+            }),
+        }
+    }
+
+    /// `true` for [`Mounted`](Self::Mounted), `false` for every other status.
+    ///
+    /// ```rust
+    /// use inspect_path::PathStatus;
+    ///
+    /// assert!(PathStatus::Mounted.into_bool());
+    /// assert!(!PathStatus::Unknown.into_bool());
+    /// ```
+    pub fn into_bool(self) -> bool {
+        self == PathStatus::Mounted
+    }
 }
 
 /// The underlying remote filesystem type, if applicable.
 ///
-/// This value is meaningful only when the path is classified as remote.
-#[derive(Debug, PartialEq)]
+/// This value is meaningful only when the path is classified as remote. Both
+/// platforms map what they see through the same table, so a variant means
+/// the same thing everywhere:
+///
+/// | Variant | Unix fs type | Windows |
+/// |---|---|---|
+/// | `SMB` | `cifs`, `smb3` | any other `\\server\share` |
+/// | `WebDAV` | `davfs` | `DavWWWRoot` in the UNC name |
+/// | `NFS` | `nfs`, `nfs4` | `NFS` filesystem name |
+/// | `AFS` | `afs` | `\\afs\...` |
+/// | `SSHFS` | `fuse.sshfs` | `\\sshfs\...` (SSHFS-Win) |
+/// | `NinePfs` | `9p` | `\\wsl$\...`, `\\wsl.localhost\...` |
+/// | `Ceph`, `GlusterFS`, `VirtioFs`, `Other` | `ceph`, `glusterfs`, `virtiofs`, `afp`, ... | not produced |
+/// | `Unknown` | not produced | a drive whose UNC name can't be read |
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+#[non_exhaustive]
 pub enum RemoteType {
     WebDAV,
     NFS,
     SMB,
     AFS,
+    SSHFS,
+    Ceph,
+    GlusterFS,
+    /// Plan 9 (`9p`) shares, common for VM and WSL host directories.
+    #[cfg_attr(feature = "serde", serde(rename = "9p"))]
+    NinePfs,
+    /// `virtiofs`, a host directory shared into a VM. Local latency, but
+    /// owned and possibly changed by the host.
+    VirtioFs,
+    /// A recognized network filesystem without a dedicated variant, carrying
+    /// its filesystem type name.
     Other(String),
     Unknown,
 }
 
 /// The general category of a filesystem path.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+#[non_exhaustive]
 pub enum PathType {
     Unknown,
     Removable,
     Fixed,
     Remote,
     CDRom,
+    /// Storage held in memory and lost on reboot: a RAM disk drive on
+    /// Windows, a `tmpfs` mount such as `/tmp` on Unix.
     RamDisk,
-    #[cfg(any(target_family = "unix", docsrs))]
-    /// unix only
+    /// A pseudo filesystem with no storage behind it (`proc`, `sysfs`,
+    /// `devtmpfs`, `cgroup2`, `overlay`, ...), carrying its filesystem type
+    /// name. `tmpfs` mounted under `/dev`, `/proc`, `/run` or `/sys` counts as
+    /// virtual too; elsewhere it is a [`PathType::RamDisk`].
+    ///
+    /// Only produced on Unix: every Windows volume has a drive type.
     Virtual(String),
 }
 
+/// A flattened category combining [`PathType`] and [`RemoteType`], for UIs
+/// and metrics labels that want a single value.
+///
+/// Built from a [`PathInfo`] with `PathKind::from(&info)`; the detailed
+/// fields stay available on the info itself.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::path::Path;
+/// use inspect_path::{PathKind, inspect_path};
+///
+/// let info = inspect_path(Path::new("/mnt/share")).unwrap();
+/// match PathKind::from(&info) {
+///     PathKind::NetworkNfs | PathKind::NetworkSmb | PathKind::NetworkOther => {
+///         println!("network path")
+///     }
+///     kind => println!("{kind}"),
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[non_exhaustive]
+pub enum PathKind {
+    LocalFixed,
+    LocalRemovable,
+    Optical,
+    Ram,
+    NetworkNfs,
+    NetworkSmb,
+    /// Any other or unidentified network filesystem.
+    NetworkOther,
+    /// Only produced on Unix.
+    Virtual,
+    Unknown,
+}
+
+impl From<&PathInfo> for PathKind {
+    fn from(info: &PathInfo) -> Self {
+        match (&info.kind, &info.remote_kind) {
+            (PathType::Fixed, _) => PathKind::LocalFixed,
+            (PathType::Removable, _) => PathKind::LocalRemovable,
+            (PathType::CDRom, _) => PathKind::Optical,
+            (PathType::RamDisk, _) => PathKind::Ram,
+            (PathType::Remote, Some(RemoteType::NFS)) => PathKind::NetworkNfs,
+            (PathType::Remote, Some(RemoteType::SMB)) => PathKind::NetworkSmb,
+            (PathType::Remote, _) => PathKind::NetworkOther,
+            (PathType::Virtual(_), _) => PathKind::Virtual,
+            (PathType::Unknown, _) => PathKind::Unknown,
+        }
+    }
+}
+
+/// How reliable a [`PathType`] classification is.
+///
+/// Variants are ordered, so `info.confidence() >= Confidence::Medium` works.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Confidence {
+    /// A fallback guess, e.g. a fuse mount of unknown subtype.
+    Low,
+    /// Inferred indirectly, e.g. an anonymous device treated as virtual.
+    Medium,
+    /// Reported directly by the OS, e.g. an explicit `nfs` fs type or a
+    /// `GetDriveTypeW` result.
+    High,
+}
+
+/// When a mount updates access times, from its `noatime`, `relatime` or
+/// `strictatime` option.
+///
+/// See [`PathInfo::atime_policy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[non_exhaustive]
+pub enum AtimePolicy {
+    /// `noatime`: reads never update the access time.
+    NoAtime,
+    /// `relatime`: the access time is updated only when it is older than the
+    /// modification or change time, or more than a day old.
+    RelAtime,
+    /// `strictatime`: every read updates the access time.
+    StrictAtime,
+}
+
 /// Information about a filesystem path, including its type and mount status.
 ///
 /// `PathInfo` represents both local and remote paths and provides methods
 /// to inspect their characteristics in a platform-independent way.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PathInfo {
     path: PathBuf,
     #[cfg(target_family = "unix")]
@@ -134,6 +548,25 @@ pub struct PathInfo {
     kind: PathType,
     remote_kind: Option<RemoteType>,
     status: PathStatus,
+    confidence: Confidence,
+    #[cfg(target_family = "unix")]
+    #[cfg_attr(feature = "schemars", schemars(schema_with = "mount_schema"))]
+    mount: Option<platform::MountInfo>,
+    #[cfg(not(target_family = "unix"))]
+    fs_type: Option<String>,
+    #[cfg(not(target_family = "unix"))]
+    mount_point: Option<PathBuf>,
+    #[cfg(not(target_family = "unix"))]
+    device_id: Option<DeviceId>,
+    #[cfg(not(target_family = "unix"))]
+    read_only: Option<bool>,
+}
+
+/// The mount table entry is an implementation detail, so the schema only
+/// promises an object (or `null`) rather than describing its fields.
+#[cfg(all(feature = "schemars", target_family = "unix"))]
+fn mount_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+    schemars::json_schema!({ "type": ["object", "null"] })
 }
 
 impl PathInfo {
@@ -152,7 +585,16 @@ impl PathInfo {
     pub fn is_ramdisk(&self) -> bool {
         matches!(self.kind, PathType::RamDisk)
     }
-    #[cfg(target_family = "unix")]
+    /// Returns whether this is a host directory shared into a VM, over
+    /// `virtiofs` or a `9p` share (QEMU `virtio-9p`, WSL drives).
+    pub fn is_vm_shared(&self) -> bool {
+        matches!(
+            self.remote_kind,
+            Some(RemoteType::VirtioFs | RemoteType::NinePfs)
+        )
+    }
+    /// Returns whether this is a [`PathType::Virtual`] filesystem. Always
+    /// `false` on Windows.
     pub fn is_virtual(&self) -> bool {
         matches!(self.kind, PathType::Virtual(_))
     }
@@ -169,6 +611,28 @@ impl PathInfo {
     pub fn is_status_unknown(&self) -> bool {
         matches!(self.status, PathStatus::Unknown)
     }
+    /// Like [`PathStatus::ok_or_disconnected`], with this path in the error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use inspect_path::{InspectPathError, inspect_path_and_status};
+    ///
+    /// fn backup_target(path: &str) -> Result<(), InspectPathError> {
+    ///     let info = inspect_path_and_status(path)?;
+    ///     info.ok_or_disconnected()?;
+    ///     // ... write the backup
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn ok_or_disconnected(&self) -> Result<(), InspectPathError> {
+        self.status
+            .ok_or_disconnected()
+            .map_err(|_| InspectPathError::Unavailable {
+                path: self.path.clone(),
+                status: self.status.clone(),
+            })
+    }
     pub fn path(&self) -> &PathBuf {
         &self.path
     }
@@ -185,9 +649,1047 @@ impl PathInfo {
     pub fn remote_type(&self) -> Option<&RemoteType> {
         self.remote_kind.as_ref()
     }
+    /// Returns whether the volume is mounted read-only.
+    ///
+    /// - **Unix:** `ro` in the mount or superblock options of the matched
+    ///   mount, or an inherently read-only format such as `squashfs` or
+    ///   `iso9660`.
+    /// - **Windows:** the `FILE_READ_ONLY_VOLUME` flag from
+    ///   `GetVolumeInformationW`.
+    ///
+    /// Recorded at inspection time. Returns `None` if it couldn't be
+    /// determined, e.g. for a disconnected share.
+    pub fn is_read_only(&self) -> Option<bool> {
+        #[cfg(target_family = "unix")]
+        {
+            self.mount.as_ref().map(|m| m.is_read_only())
+        }
+        #[cfg(not(target_family = "unix"))]
+        {
+            self.read_only
+        }
+    }
+    /// Returns whether the kernel has forced this path's filesystem
+    /// read-only, a strong sign of a failing disk.
+    ///
+    /// When a filesystem mounted with `errors=remount-ro` hits an error, the
+    /// kernel makes its superblock read-only and logs it, but leaves the mount
+    /// options alone. This is `true` for a [`Fixed`](PathType::Fixed) `ext2`,
+    /// `ext3`, `ext4`, `xfs`, `btrfs` or `f2fs` filesystem in that state: `ro`
+    /// in the superblock options, `rw` in the mount options. Media that is
+    /// read-only by nature (a CD, a `squashfs` image) and filesystems remounted
+    /// `ro` on purpose don't count.
+    ///
+    /// Read from the mount recorded at inspection time, so
+    /// [`refresh`](Self::refresh) first when polling. Always `false` on
+    /// Windows.
+    pub fn detect_forced_readonly(&self) -> bool {
+        #[cfg(target_family = "unix")]
+        {
+            self.is_fixed() && self.mount.as_ref().is_some_and(|m| m.is_forced_read_only())
+        }
+        #[cfg(not(target_family = "unix"))]
+        {
+            false
+        }
+    }
+    /// Returns whether file names differing only in case name different files
+    /// at this path.
+    ///
+    /// - **Unix:** the default of the filesystem type (`ext4`, `xfs`, ...
+    ///   sensitive; `vfat`, `exfat`, `ntfs` not), and on `ext4`, `f2fs` and
+    ///   `tmpfs` whether the nearest existing directory has case folding
+    ///   enabled.
+    /// - **Windows:** `FILE_CASE_SENSITIVE_SEARCH` from
+    ///   `GetVolumeInformationW`, and on volumes that set it, the per-directory
+    ///   case sensitivity flag of the nearest existing directory.
+    ///
+    /// Queried on each call. Returns `None` when it can't be determined, e.g.
+    /// for network shares whose behavior depends on the server; use
+    /// [`probe_case_sensitivity`](Self::probe_case_sensitivity) to find out
+    /// by experiment.
+    pub fn is_case_sensitive(&self) -> Option<bool> {
+        #[cfg(target_family = "unix")]
+        {
+            let path = self.resolved_path.as_deref().unwrap_or(&self.path);
+            platform::is_case_sensitive(self.mount.as_ref()?, path)
+        }
+        #[cfg(not(target_family = "unix"))]
+        {
+            platform::is_case_sensitive(self)
+        }
+    }
+    /// Finds out whether names are case-sensitive at this path by creating
+    /// two empty files whose names differ only in case in the nearest
+    /// existing directory, then removing them.
+    ///
+    /// Unlike [`is_case_sensitive`](Self::is_case_sensitive) this works on
+    /// any filesystem, including network shares, but it needs write access
+    /// and touches the directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InspectPathError::Io`] if no ancestor of the path exists or
+    /// the probe file can't be created.
+    pub fn probe_case_sensitivity(&self) -> Result<bool, InspectPathError> {
+        #[cfg(target_family = "unix")]
+        let path = self.resolved_path.as_deref().unwrap_or(&self.path);
+        #[cfg(not(target_family = "unix"))]
+        let path = self.path.as_path();
+
+        let io_error = |source| InspectPathError::Io {
+            path: path.to_path_buf(),
+            source,
+        };
+        let dir = path
+            .ancestors()
+            .find(|p| p.is_dir())
+            .ok_or_else(|| io_error(std::io::ErrorKind::NotFound.into()))?;
+
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .subsec_nanos();
+        let name = format!(".inspect_path_case_{}_{nanos}", std::process::id());
+        let lower = dir.join(&name);
+        let upper = dir.join(name.to_uppercase());
+
+        let create = |p: &Path| {
+            std::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(p)
+        };
+        create(&lower).map_err(io_error)?;
+        let sensitive = match create(&upper) {
+            Ok(_) => {
+                let _ = std::fs::remove_file(&upper);
+                Ok(true)
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => Ok(false),
+            Err(e) => Err(io_error(e)),
+        };
+        let _ = std::fs::remove_file(&lower);
+        sensitive
+    }
+    /// Returns whether the filesystem at this path supports hard links.
+    ///
+    /// - **Unix:** from the filesystem type: native Unix formats, NTFS and
+    ///   NFS do, `vfat` and `exfat` don't.
+    /// - **Windows:** `FILE_SUPPORTS_HARD_LINKS` from `GetVolumeInformationW`.
+    ///
+    /// Returns `None` when it can't be determined, e.g. for SMB shares on
+    /// Unix.
+    pub fn supports_hardlinks(&self) -> Option<bool> {
+        self.link_support().map(|(hard, _)| hard)
+    }
+    /// Returns whether the filesystem at this path supports symbolic links.
+    ///
+    /// - **Unix:** from the filesystem type, as for
+    ///   [`supports_hardlinks`](Self::supports_hardlinks).
+    /// - **Windows:** `FILE_SUPPORTS_REPARSE_POINTS` from
+    ///   `GetVolumeInformationW`. Creating one may still need
+    ///   `SeCreateSymbolicLinkPrivilege` or Developer Mode.
+    pub fn supports_symlinks(&self) -> Option<bool> {
+        self.link_support().map(|(_, sym)| sym)
+    }
+    fn link_support(&self) -> Option<(bool, bool)> {
+        #[cfg(target_family = "unix")]
+        {
+            platform::link_support(self.mount.as_ref()?)
+        }
+        #[cfg(not(target_family = "unix"))]
+        {
+            platform::link_support(self)
+        }
+    }
+    /// Returns the root of the mount this path is on, e.g. `/mnt/nfs` for
+    /// `/mnt/nfs/projects/a.txt`.
+    ///
+    /// - **Unix:** the mount point of the deepest matching mount.
+    /// - **Windows:** the volume root from `GetVolumePathNameW` (`C:\` or a
+    ///   folder mount point), or `\\server\share\` for a UNC path.
+    pub fn mount_point(&self) -> Option<&Path> {
+        #[cfg(target_family = "unix")]
+        {
+            self.mount.as_ref().map(|m| m.mount_point())
+        }
+        #[cfg(not(target_family = "unix"))]
+        {
+            self.mount_point.as_deref()
+        }
+    }
+    /// Returns the mount this path's mount is mounted on, classified as if
+    /// its mount point had been inspected, e.g. the `/var/lib/docker` mount
+    /// under a container's overlay.
+    ///
+    /// Calling it on the result walks further up the mount tree. The parent
+    /// is looked up by its mount id in a fresh read of the mount table the
+    /// path was classified from, e.g. `/proc/{pid}/mountinfo` after
+    /// `inspect_in_namespace`.
+    ///
+    /// Returns `None` for the root of the mount namespace, whose parent isn't
+    /// in the table, when the parent has been unmounted since, and on Windows
+    /// and the BSDs, where mounts don't record a parent.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the mount table can't be read, or the parent
+    /// can't be classified.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use inspect_path::inspect_path;
+    ///
+    /// let mut mount = inspect_path("/var/lib/docker/overlay2/abc/merged").unwrap();
+    /// while let Some(parent) = mount.parent_mount().unwrap() {
+    ///     println!("{:?} on {}", parent.mount_point(), parent.kind());
+    ///     mount = parent;
+    /// }
+    /// ```
+    pub fn parent_mount(&self) -> Result<Option<PathInfo>, InspectPathError> {
+        #[cfg(target_family = "unix")]
+        {
+            match &self.mount {
+                Some(mount) => platform::parent_mount(mount),
+                None => Ok(None),
+            }
+        }
+        #[cfg(not(target_family = "unix"))]
+        {
+            Ok(None)
+        }
+    }
+
+    /// Returns the exact `/proc/self/mountinfo` line of the mount that
+    /// matched this path.
+    ///
+    /// All mount-derived fields are parsed from this line, so it is the thing
+    /// to include when reporting a misclassification.
+    #[cfg(target_family = "unix")]
+    pub fn raw_mountinfo_line(&self) -> Option<&str> {
+        self.mount.as_ref().map(|m| m.raw_line())
+    }
+    /// Returns the mount that matched this path, parsed.
+    #[cfg(target_family = "unix")]
+    pub fn mount_info(&self) -> Option<&MountInfo> {
+        self.mount.as_ref()
+    }
+    /// Returns the filesystem type name as the OS reports it, e.g. `ext4`,
+    /// `nfs4`, `fuse.rclone` or `NTFS`.
+    ///
+    /// - **Unix:** the type field of the matched mount. On Linux the `statfs`
+    ///   magic number, mapped to a name or `unknown(0x...)`, is only shown by
+    ///   [`inspect_report`], since mountinfo always names the type.
+    /// - **Windows:** the filesystem name from `GetVolumeInformationW`, e.g.
+    ///   `NTFS`, `ReFS` (including Dev Drives, see
+    ///   [`is_dev_drive`](Self::is_dev_drive)), `exFAT` or `FAT32`, which
+    ///   `GetDriveTypeW` alone reports as a plain fixed or removable drive.
+    ///
+    /// Returns `None` if the volume could not be queried, and on Windows for a
+    /// UNC path, whose volume isn't queried.
+    pub fn fs_type_name(&self) -> Option<&str> {
+        #[cfg(target_family = "unix")]
+        {
+            self.mount
+                .as_ref()
+                .map(|m| m.fs_type())
+                .filter(|t| !t.is_empty())
+        }
+        #[cfg(not(target_family = "unix"))]
+        {
+            self.fs_type.as_deref()
+        }
+    }
+
+    /// Returns whether an NFS mount is `hard` (`Some(true)`) or `soft`
+    /// (`Some(false)`).
+    ///
+    /// On a hard mount, I/O — including [`check_status`](Self::check_status) —
+    /// blocks indefinitely while the server is unreachable; a soft mount
+    /// returns an error after its retry timeout. NFS mounts are hard unless
+    /// `soft` is given.
+    ///
+    /// Returns `None` for anything that isn't an NFS mount, and on Windows.
+    pub fn is_hard_mount(&self) -> Option<bool> {
+        #[cfg(target_family = "unix")]
+        {
+            self.mount.as_ref()?.is_hard_mount()
+        }
+        #[cfg(not(target_family = "unix"))]
+        {
+            None
+        }
+    }
+
+    /// Returns when the mount this path is on updates access times.
+    ///
+    /// A mount still doing [`StrictAtime`](AtimePolicy::StrictAtime) updates
+    /// turns every read into a metadata write, which matters on read-heavy
+    /// workloads.
+    ///
+    /// Returns `None` when the mount options name no policy, which leaves it
+    /// to the kernel default, and on Windows. Linux lists `relatime` and
+    /// `noatime` in mountinfo but not `strictatime`, so there `None` is what
+    /// a strict mount looks like.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use inspect_path::{AtimePolicy, inspect_path};
+    ///
+    /// let info = inspect_path("/var/lib/postgresql").unwrap();
+    /// if info.atime_policy() != Some(AtimePolicy::NoAtime) {
+    ///     println!("consider mounting {:?} with noatime", info.mount_point());
+    /// }
+    /// ```
+    pub fn atime_policy(&self) -> Option<AtimePolicy> {
+        #[cfg(target_family = "unix")]
+        {
+            self.mount.as_ref()?.atime_policy()
+        }
+        #[cfg(not(target_family = "unix"))]
+        {
+            None
+        }
+    }
+
+    /// Returns the btrfs subvolume this path is mounted from, e.g. `"/@home"`.
+    ///
+    /// Read from the `subvol=` mount option, falling back to the numeric
+    /// `subvolid=`. Subvolumes share a device, so this is what tells `@home`
+    /// and `@` apart for snapshot and backup tools.
+    ///
+    /// Returns `None` for anything that isn't a btrfs mount, and on Windows.
+    pub fn btrfs_subvolume(&self) -> Option<String> {
+        #[cfg(target_family = "unix")]
+        {
+            self.mount.as_ref()?.btrfs_subvolume().map(String::from)
+        }
+        #[cfg(not(target_family = "unix"))]
+        {
+            None
+        }
+    }
+
+    /// Returns the UUID of the filesystem this path is on, e.g.
+    /// `"3f0c2a9e-..."` for ext4 or `"1234-ABCD"` for FAT.
+    ///
+    /// Resolved by matching the mount's block device against udev's
+    /// `/dev/disk/by-uuid` links, so it identifies a disk wherever it is
+    /// mounted. Reads from the system on each call.
+    ///
+    /// Returns `None` for virtual and network mounts, which have no block
+    /// device, and on Windows.
+    pub fn filesystem_uuid(&self) -> Option<String> {
+        #[cfg(target_family = "unix")]
+        {
+            platform::filesystem_uuid(self.mount.as_ref()?)
+        }
+        #[cfg(not(target_family = "unix"))]
+        {
+            None
+        }
+    }
+
+    /// Identifies the device backing this path, for grouping paths by
+    /// underlying storage.
+    ///
+    /// - **Unix:** the `major:minor` device number of the matched mount.
+    /// - **Windows:** the volume GUID name (`Volume{...}`), or the volume
+    ///   serial number for network shares.
+    ///
+    /// Returns `None` if the volume could not be queried. Equal ids mean the
+    /// same device, not necessarily the same filesystem for
+    /// [`same_filesystem`]; see [`DeviceId`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use std::{collections::HashMap, path::Path};
+    /// use inspect_path::inspect_path;
+    ///
+    /// let mut by_device = HashMap::new();
+    /// for path in ["/data/a", "/data/b", "/mnt/usb/c"] {
+    ///     let info = inspect_path(Path::new(path)).unwrap();
+    ///     by_device.entry(info.device_id()).or_insert_with(Vec::new).push(path);
+    /// }
+    /// ```
+    pub fn device_id(&self) -> Option<DeviceId> {
+        #[cfg(target_family = "unix")]
+        {
+            self.mount.as_ref().map(|m| m.device_id())
+        }
+        #[cfg(not(target_family = "unix"))]
+        {
+            self.device_id.clone()
+        }
+    }
+
+    /// A hex digest identifying the mount across runs and reboots, e.g. to
+    /// recognize "the backup drive" again next week.
+    ///
+    /// The digest combines the most stable identifiers available:
+    ///
+    /// - **Unix:** the filesystem type and the mount's root within the
+    ///   filesystem, plus for network mounts the source (`server:/export`,
+    ///   `//server/share`); for local mounts the filesystem UUID from
+    ///   `/dev/disk/by-uuid`, falling back to the device path.
+    /// - **Windows:** the filesystem type, plus for network drives the UNC
+    ///   name of the share (so the drive letter doesn't matter); for local
+    ///   volumes the volume GUID, or the serial number for volumes without
+    ///   one.
+    ///
+    /// This is best-effort: reformatting a drive gives it a new id, and a
+    /// device path fallback changes if the kernel renames the device. Paths
+    /// on the same mount share an id. Without mount information, the path
+    /// itself is hashed.
+    pub fn stable_id(&self) -> String {
+        #[cfg(target_family = "unix")]
+        let parts = match &self.mount {
+            Some(mount) => platform::identity_parts(mount),
+            None => vec!["path".into(), self.path.to_string_lossy().into_owned()],
+        };
+        #[cfg(not(target_family = "unix"))]
+        let parts = platform::identity_parts(self);
+
+        let hash = platform::fnv1a(parts.join("\0").bytes());
+        format!("{hash:016x}")
+    }
+
+    /// Returns whether `self` and `other` live on the same device.
+    ///
+    /// Same device means a rename between them can succeed without copying.
+    /// Returns `None` if either [`device_id`](Self::device_id) is unknown.
+    pub fn same_device(&self, other: &PathInfo) -> Option<bool> {
+        Some(self.device_id()? == other.device_id()?)
+    }
+
+    /// Returns whether this path is on the root filesystem: the mount at `/`
+    /// on Unix, or the system drive (`%SystemDrive%`) on Windows.
+    ///
+    /// Useful as a guard before unmounting or bulk-writing. On Windows this
+    /// looks up the volume root of the path, so it performs a system call.
+    pub fn is_root_filesystem(&self) -> bool {
+        #[cfg(target_family = "unix")]
+        {
+            self.mount
+                .as_ref()
+                .is_some_and(|m| m.mount_point() == Path::new("/"))
+        }
+        #[cfg(not(target_family = "unix"))]
+        {
+            platform::is_system_volume(&self.path)
+        }
+    }
+
+    /// Returns whether this path is on a Windows Dev Drive, the ReFS volume
+    /// type Windows 11 tunes for source trees and build output.
+    ///
+    /// Reads the volume's persistent state with
+    /// `FSCTL_QUERY_PERSISTENT_VOLUME_STATE`, so it performs system calls.
+    /// Always `false` on other volumes, on Windows versions without Dev
+    /// Drives, and on Unix.
+    pub fn is_dev_drive(&self) -> bool {
+        #[cfg(target_family = "unix")]
+        {
+            false
+        }
+        #[cfg(not(target_family = "unix"))]
+        {
+            platform::is_dev_drive(self)
+        }
+    }
+
+    /// Returns whether this is a path inside a Volume Shadow Copy snapshot,
+    /// e.g. `\\?\GLOBALROOT\Device\HarddiskVolumeShadowCopy3\Users`, as
+    /// backup tools read them.
+    ///
+    /// Snapshots are classified [`PathType::Fixed`] and read-only. Decided
+    /// from the shape of the path; always `false` on Unix.
+    pub fn is_snapshot(&self) -> bool {
+        #[cfg(target_family = "unix")]
+        {
+            false
+        }
+        #[cfg(not(target_family = "unix"))]
+        {
+            platform::is_snapshot(&self.path)
+        }
+    }
+
+    /// Returns the volume a [snapshot](Self::is_snapshot) was taken of: its
+    /// drive root, e.g. `C:\`, or its `\\?\GLOBALROOT\Device\...` path when
+    /// it has no drive letter.
+    ///
+    /// Asks the snapshot device with `IOCTL_VOLSNAP_QUERY_ORIGINAL_VOLUME_NAME`,
+    /// which may need administrator rights. Returns `None` if that fails, for
+    /// anything that isn't a snapshot, and on Unix.
+    pub fn snapshot_origin(&self) -> Option<PathBuf> {
+        #[cfg(target_family = "unix")]
+        {
+            None
+        }
+        #[cfg(not(target_family = "unix"))]
+        {
+            platform::snapshot_origin(&self.path)
+        }
+    }
+
+    /// Returns whether this path is on the boot or EFI System partition.
+    ///
+    /// On Unix that is a mount at `/boot`, `/boot/efi` or `/efi`, or a FAT
+    /// filesystem mounted below `/boot` or `/efi`. On Windows it is a volume
+    /// whose partition type is EFI System or Windows Recovery, read with
+    /// `IOCTL_DISK_GET_PARTITION_INFO_EX`, so it performs system calls; those
+    /// volumes are usually reachable only through a `\\?\Volume{...}\` path.
+    pub fn is_system_partition(&self) -> bool {
+        #[cfg(target_family = "unix")]
+        {
+            self.mount.as_ref().is_some_and(platform::is_boot_partition)
+        }
+        #[cfg(not(target_family = "unix"))]
+        {
+            platform::is_boot_partition(&self.path)
+        }
+    }
+
+    /// Returns the label of the volume this path is on, e.g. `"KINGSTON 16GB"`.
+    ///
+    /// On Unix the label is looked up among udev's `/dev/disk/by-label`
+    /// links, so only block devices have one; on Windows it is the volume
+    /// name from `GetVolumeInformationW`. Either way this reads from the
+    /// system on each call. Returns `None` for unlabeled volumes.
+    pub fn volume_label(&self) -> Option<String> {
+        #[cfg(target_family = "unix")]
+        {
+            platform::volume_label(self.mount.as_ref()?)
+        }
+        #[cfg(not(target_family = "unix"))]
+        {
+            platform::volume_label(&self.path)
+        }
+    }
+
+    /// Returns whether the volume is a mounted disk image (a loop device on
+    /// Linux, an `md` or `vnd` device on the BSDs, an `hdiutil`-attached DMG
+    /// on macOS) rather than a physical disk.
+    ///
+    /// Tools that shouldn't treat a temporarily attached image as permanent
+    /// storage can check this. Reads sysfs on Linux and runs `diskutil info`
+    /// on macOS, on each call. Returns `None` when it can't be determined,
+    /// and always on Windows, where image-backed volumes aren't detected
+    /// yet.
+    pub fn is_disk_image(&self) -> Option<bool> {
+        #[cfg(target_family = "unix")]
+        {
+            platform::is_disk_image(self.mount.as_ref()?)
+        }
+        #[cfg(not(target_family = "unix"))]
+        {
+            None
+        }
+    }
+
+    /// Returns whether the volume is on an iSCSI LUN, a block device whose
+    /// storage is reached over the network.
+    ///
+    /// Such volumes are still classified [`PathType::Fixed`], since they
+    /// behave like a local disk until the network goes away; failover logic
+    /// can use this, or [`depends_on_network`](Self::depends_on_network), to
+    /// treat them like network mounts. Multipath and LVM volumes count when
+    /// any disk beneath them is iSCSI. Reads sysfs on each call. Returns
+    /// `None` when it can't be determined, and always outside Linux.
+    pub fn is_iscsi(&self) -> Option<bool> {
+        #[cfg(target_family = "unix")]
+        {
+            platform::is_iscsi(self.mount.as_ref()?)
+        }
+        #[cfg(not(target_family = "unix"))]
+        {
+            None
+        }
+    }
+
+    /// Returns whether reaching this path needs the network: it is
+    /// [`Remote`](PathType::Remote), or a fixed disk on an
+    /// [iSCSI LUN](Self::is_iscsi). Reads sysfs on each call for fixed disks.
+    pub fn depends_on_network(&self) -> bool {
+        self.is_remote() || (self.is_fixed() && self.is_iscsi() == Some(true))
+    }
+
+    /// Returns a name for the volume like Explorer shows it, e.g.
+    /// `"Local Disk (C:)"`, `"KINGSTON (E:)"` or `"Network Drive (Z:)"`.
+    ///
+    /// The [`volume_label`](Self::volume_label) if there is one, else a word
+    /// for the kind of volume ("Removable Disk", "CD Drive", ...), followed by
+    /// the drive letter on Windows or the mount point on Unix
+    /// (`"Removable Disk (/media/usb)"`). Reads the label on each call.
+    pub fn display_name(&self) -> String {
+        self.name_with_label(self.volume_label())
+    }
+
+    fn name_with_label(&self, label: Option<String>) -> String {
+        let name = label.unwrap_or_else(|| {
+            match &self.kind {
+                PathType::Fixed => "Local Disk",
+                PathType::Removable => "Removable Disk",
+                PathType::Remote => "Network Drive",
+                PathType::CDRom => "CD Drive",
+                PathType::RamDisk => "RAM Disk",
+                PathType::Virtual(fs_type) => fs_type,
+                PathType::Unknown => "Drive",
+            }
+            .to_string()
+        });
+        match self.mount_point() {
+            // `C:\` reads as `C:`, but `/` stays `/`
+            Some(root) if root.as_os_str().len() > 1 => {
+                let root = root.to_string_lossy();
+                format!("{name} ({})", root.trim_end_matches(['\\', '/']))
+            }
+            Some(root) => format!("{name} ({})", root.display()),
+            None => name,
+        }
+    }
+
+    /// Returns the `(logical, physical)` sector sizes in bytes of the disk
+    /// this path is on, e.g. `(512, 4096)` for a 512e drive.
+    ///
+    /// Writes aligned to the physical size avoid read-modify-write cycles.
+    /// On Linux the sizes come from the disk's `queue` directory in sysfs; on
+    /// Windows from `IOCTL_STORAGE_QUERY_PROPERTY`. Returns `None` for
+    /// network shares and virtual filesystems, which have no block device.
+    pub fn sector_sizes(&self) -> Option<(u32, u32)> {
+        #[cfg(target_family = "unix")]
+        {
+            platform::sector_sizes(self.mount.as_ref()?)
+        }
+        #[cfg(not(target_family = "unix"))]
+        {
+            platform::sector_sizes(&self.path)
+        }
+    }
+
+    /// Returns the size and free space of the filesystem this path is on.
+    ///
+    /// This queries the filesystem on each call (`statvfs` on Unix,
+    /// `GetDiskFreeSpaceExW` on Windows) and may block on an unreachable
+    /// network mount.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InspectPathError::Unavailable`] if the volume is
+    /// disconnected, stale or has no media, and [`InspectPathError::Io`] for
+    /// other failures.
+    pub fn space(&self) -> Result<SpaceInfo, InspectPathError> {
+        #[cfg(target_family = "unix")]
+        {
+            // space is per filesystem, and the mount point exists even when
+            // the path doesn't yet
+            let target = self
+                .mount
+                .as_ref()
+                .map_or(self.path.as_path(), |m| m.mount_point());
+            platform::space_info(target)
+        }
+        #[cfg(not(target_family = "unix"))]
+        {
+            platform::space_info(&self.path)
+        }
+    }
+
+    /// Returns the calling user's block quota on this path's filesystem.
+    ///
+    /// On a shared server the limit can be far below what [`space`](Self::space)
+    /// reports as free. Queried with `quotactl(Q_GETQUOTA)` on each call, which
+    /// works for filesystems on a block device with quota support (`ext4`,
+    /// `xfs`). Returns `None` when quotas aren't enabled, the user has no
+    /// limit, or the query fails, and always on platforms other than Linux.
+    pub fn quota(&self) -> Option<Quota> {
+        #[cfg(target_os = "linux")]
+        {
+            platform::quota(self.mount.as_ref()?)
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            None
+        }
+    }
+
+    /// Returns the longest file name the filesystem at this path accepts, e.g.
+    /// `255` on `ext4`, NTFS and FAT with long file names.
+    ///
+    /// This is `f_namemax` from `statvfs` on Unix, counted in bytes, and the
+    /// maximum component length from `GetVolumeInformationW` on Windows,
+    /// counted in UTF-16 units. Queried on each call; returns `None` if the
+    /// filesystem doesn't answer.
+    pub fn max_filename_length(&self) -> Option<u64> {
+        #[cfg(target_family = "unix")]
+        {
+            let target = self
+                .mount
+                .as_ref()
+                .map_or(self.path.as_path(), |m| m.mount_point());
+            platform::max_filename_length(target)
+        }
+        #[cfg(not(target_family = "unix"))]
+        {
+            platform::max_filename_length(&self.path)
+        }
+    }
+
+    /// Fraction of the volume available to the caller, from `0.0` to `1.0`.
+    ///
+    /// See [`SpaceInfo::free_fraction`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`space`](Self::space).
+    pub fn free_fraction(&self) -> Result<f64, InspectPathError> {
+        Ok(self.space()?.free_fraction())
+    }
+
+    /// Returns whether the space available to the caller is below
+    /// `threshold`, e.g. before starting a large write.
+    ///
+    /// Uses [`SpaceInfo::available`], which accounts for quotas and reserved
+    /// blocks, rather than the raw free space.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`space`](Self::space); a disconnected volume is an error, not
+    /// `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use std::path::Path;
+    /// use inspect_path::{SpaceThreshold, inspect_path};
+    ///
+    /// let dest = inspect_path(Path::new("/media/usb")).unwrap();
+    /// if dest.is_nearly_full(SpaceThreshold::MinFreePercent(5.0)).unwrap() {
+    ///     eprintln!("destination is almost full");
+    /// }
+    /// ```
+    pub fn is_nearly_full(&self, threshold: SpaceThreshold) -> Result<bool, InspectPathError> {
+        Ok(self.space()?.is_below(threshold))
+    }
+
+    /// How much the [`kind`](Self::kind) classification can be trusted.
+    ///
+    /// Callers that need certainty can fall back to a deeper probe when this
+    /// is below [`Confidence::High`].
+    pub fn confidence(&self) -> Confidence {
+        self.confidence
+    }
+
+    /// Returns the protocol version of a remote mount, if it can be determined.
+    ///
+    /// On Unix this is read from the `vers=` or `nfsvers=` mount option, e.g.
+    /// `"3.1.1"` for an SMB 3.1.1 share or `"4.2"` for NFSv4.2. This is useful
+    /// for flagging insecure SMB1 (`"1.0"`) mounts.
+    ///
+    /// Returns `None` for non-remote paths, when the option is absent, and
+    /// currently always on Windows.
+    pub fn protocol_version(&self) -> Option<String> {
+        if !self.is_remote() {
+            return None;
+        }
+        #[cfg(target_family = "unix")]
+        {
+            self.mount
+                .as_ref()
+                .and_then(|m| m.protocol_version())
+                .map(String::from)
+        }
+        #[cfg(not(target_family = "unix"))]
+        {
+            None
+        }
+    }
+
+    /// Probes the path with [`check_status`], stores the result and returns
+    /// the previous status.
+    ///
+    /// Compare the returned value with [`status`](Self::status) to see
+    /// whether it changed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use std::path::Path;
+    /// use inspect_path::inspect_path;
+    ///
+    /// let mut info = inspect_path(Path::new("/mnt/nfs")).unwrap();
+    /// loop {
+    ///     let previous = info.check_status();
+    ///     if previous != *info.status() {
+    ///         println!("{previous} -> {}", info.status());
+    ///     }
+    ///     std::thread::sleep(std::time::Duration::from_secs(10));
+    /// }
+    /// ```
+    pub fn check_status(&mut self) -> PathStatus {
+        self.check_status_using(|path| platform::check_status(path))
+    }
+
+    /// Like [`check_status`](Self::check_status), but gives up after
+    /// `timeout` and sets [`PathStatus::TimedOut`]. Returns the previous
+    /// status.
+    ///
+    /// See [`check_status_timeout`] for how abandoned probes are handled.
+    pub fn check_status_with(&mut self, timeout: std::time::Duration) -> PathStatus {
+        self.check_status_using(|path| check_status_timeout(path, timeout))
+    }
+
+    fn check_status_using(&mut self, probe: impl FnOnce(&Path) -> PathStatus) -> PathStatus {
+        let status = probe(&self.path);
+        std::mem::replace(&mut self.status, status)
+    }
+
+    /// Re-runs the full inspection for the stored path and updates this
+    /// `PathInfo` in place, returning whether anything changed.
+    ///
+    /// Use this when mounts may have changed since the path was inspected: a
+    /// drive letter remapped, a USB stick swapped at the same mount point, or
+    /// an automount finally mounted. The status is re-probed if it was known
+    /// before.
+    ///
+    /// If the path can no longer be inspected, the previous classification is
+    /// kept and the status becomes [`PathStatus::Disconnected`]. Use
+    /// [`refresh_with`](Self::refresh_with) with
+    /// [`InspectOptions::require_existing`] to get the error instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use std::path::Path;
+    /// use inspect_path::inspect_path;
+    ///
+    /// let mut info = inspect_path(Path::new("/media/usb")).unwrap();
+    /// // ... later
+    /// if info.refresh().unwrap() {
+    ///     println!("now: {info}");
+    /// }
+    /// ```
+    pub fn refresh(&mut self) -> Result<bool, InspectPathError> {
+        self.refresh_with(&InspectOptions::default())
+    }
+
+    /// Like [`refresh`](Self::refresh), but inspects according to `opts`.
+    ///
+    /// With [`InspectOptions::require_existing`] set, inspection errors are
+    /// returned and `self` is left untouched.
+    pub fn refresh_with(&mut self, opts: &InspectOptions) -> Result<bool, InspectPathError> {
+        match inspect_path_with(&self.path, opts) {
+            Err(e) if opts.require_existing => Err(e),
+            fresh => Ok(self.apply_refresh(fresh, opts.probe_status)),
+        }
+    }
+
+    fn apply_refresh(
+        &mut self,
+        fresh: Result<PathInfo, InspectPathError>,
+        probe_status: bool,
+    ) -> bool {
+        let old = self.clone();
+        match fresh {
+            Ok(mut fresh) => {
+                if !probe_status && !self.is_status_unknown() {
+                    fresh.check_status();
+                }
+                fresh.path = std::mem::take(&mut self.path);
+                *self = fresh;
+            }
+            Err(_) => self.status = PathStatus::Disconnected,
+        }
+        *self != old
+    }
+
+    /// A `PathInfo` that wasn't read from the system, with every volume field
+    /// unset.
+    pub(crate) fn synthetic(
+        path: PathBuf,
+        kind: PathType,
+        remote_kind: Option<RemoteType>,
+        status: PathStatus,
+    ) -> Self {
+        PathInfo {
+            path,
+            #[cfg(target_family = "unix")]
+            resolved_path: None,
+            #[cfg(target_family = "unix")]
+            is_symlink: false,
+            kind,
+            remote_kind,
+            status,
+            confidence: Confidence::High,
+            #[cfg(target_family = "unix")]
+            mount: None,
+            #[cfg(not(target_family = "unix"))]
+            fs_type: None,
+            #[cfg(not(target_family = "unix"))]
+            mount_point: None,
+            #[cfg(not(target_family = "unix"))]
+            device_id: None,
+            #[cfg(not(target_family = "unix"))]
+            read_only: None,
+        }
+    }
+}
+
+/// Inspects a path via [`inspect_path`].
+///
+/// This is not a cheap constructor: it performs the same system calls as
+/// [`inspect_path`].
+///
+/// ```rust,no_run
+/// use std::path::Path;
+/// use inspect_path::PathInfo;
+///
+/// let info: PathInfo = Path::new("/mnt/data").try_into()?;
+/// # Ok::<(), inspect_path::InspectPathError>(())
+/// ```
+impl TryFrom<&Path> for PathInfo {
+    type Error = InspectPathError;
+
+    fn try_from(path: &Path) -> Result<Self, Self::Error> {
+        inspect_path(path)
+    }
+}
+
+/// Inspects a path via [`inspect_path`].
+///
+/// This is not a cheap constructor: it performs the same system calls as
+/// [`inspect_path`].
+///
+/// ```rust,no_run
+/// use inspect_path::PathInfo;
+///
+/// let info = PathInfo::try_from("/mnt/data")?;
+/// # Ok::<(), inspect_path::InspectPathError>(())
+/// ```
+impl TryFrom<&str> for PathInfo {
+    type Error = InspectPathError;
+
+    fn try_from(path: &str) -> Result<Self, Self::Error> {
+        inspect_path(Path::new(path))
+    }
+}
+
+/// Inspects a path via [`inspect_path`], keeping the given `PathBuf` as
+/// [`PathInfo::path`].
+///
+/// This is not a cheap constructor: it performs the same system calls as
+/// [`inspect_path`].
+///
+/// ```rust,no_run
+/// use std::path::PathBuf;
+/// use inspect_path::PathInfo;
+///
+/// let info = PathInfo::try_from(PathBuf::from("/mnt/data"))?;
+/// # Ok::<(), inspect_path::InspectPathError>(())
+/// ```
+impl TryFrom<PathBuf> for PathInfo {
+    type Error = InspectPathError;
 
-    pub fn check_status(&mut self) {
-        self.status = platform::check_status(&self.path);
+    fn try_from(path: PathBuf) -> Result<Self, Self::Error> {
+        let mut info = inspect_path(&path)?;
+        info.path = path;
+        Ok(info)
+    }
+}
+
+/// Short lowercase names such as `mounted` or `other:busy`.
+///
+/// These strings are stable and safe to match on in logs.
+impl fmt::Display for PathStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathStatus::Mounted => f.write_str("mounted"),
+            PathStatus::Disconnected => f.write_str("disconnected"),
+            PathStatus::Unknown => f.write_str("unknown"),
+            PathStatus::Other(s) => write!(f, "other:{s}"),
+            PathStatus::Stale => f.write_str("stale"),
+            PathStatus::PermissionLimited => f.write_str("permission_limited"),
+            PathStatus::NoMedia => f.write_str("no_media"),
+            PathStatus::NotMounted => f.write_str("not_mounted"),
+            PathStatus::TimedOut => f.write_str("timed_out"),
+        }
+    }
+}
+
+/// Short lowercase names such as `nfs` or `other:afp`.
+///
+/// These strings are stable and safe to match on in logs.
+impl fmt::Display for RemoteType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RemoteType::WebDAV => f.write_str("webdav"),
+            RemoteType::NFS => f.write_str("nfs"),
+            RemoteType::SMB => f.write_str("smb"),
+            RemoteType::AFS => f.write_str("afs"),
+            RemoteType::SSHFS => f.write_str("sshfs"),
+            RemoteType::Ceph => f.write_str("ceph"),
+            RemoteType::GlusterFS => f.write_str("glusterfs"),
+            RemoteType::NinePfs => f.write_str("9p"),
+            RemoteType::VirtioFs => f.write_str("virtiofs"),
+            RemoteType::Other(s) => write!(f, "other:{s}"),
+            RemoteType::Unknown => f.write_str("unknown"),
+        }
+    }
+}
+
+/// Snake-case names such as `local_fixed` or `network_nfs`, matching the
+/// serde representation.
+impl fmt::Display for PathKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PathKind::LocalFixed => "local_fixed",
+            PathKind::LocalRemovable => "local_removable",
+            PathKind::Optical => "optical",
+            PathKind::Ram => "ram",
+            PathKind::NetworkNfs => "network_nfs",
+            PathKind::NetworkSmb => "network_smb",
+            PathKind::NetworkOther => "network_other",
+            PathKind::Virtual => "virtual",
+            PathKind::Unknown => "unknown",
+        })
+    }
+}
+
+/// Short lowercase names such as `fixed` or `virtual:proc`.
+///
+/// These strings are stable and safe to match on in logs.
+impl fmt::Display for PathType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathType::Unknown => f.write_str("unknown"),
+            PathType::Removable => f.write_str("removable"),
+            PathType::Fixed => f.write_str("fixed"),
+            PathType::Remote => f.write_str("remote"),
+            PathType::CDRom => f.write_str("cdrom"),
+            PathType::RamDisk => f.write_str("ramdisk"),
+            PathType::Virtual(s) => write!(f, "virtual:{s}"),
+        }
+    }
+}
+
+/// A compact one-line summary, e.g. `/mnt/data: remote (nfs), mounted`.
+///
+/// The remote type and status are omitted while they are unknown.
+impl fmt::Display for PathInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path.display(), self.kind)?;
+        if let Some(remote) = self.remote_type().filter(|r| **r != RemoteType::Unknown) {
+            write!(f, " ({remote})")?;
+        }
+        if !self.is_status_unknown() {
+            write!(f, ", {}", self.status)?;
+        }
+        Ok(())
     }
 }
 
@@ -237,18 +1739,467 @@ impl PathInfo {
 ///
 /// - **Windows:** Uses Win32 APIs and filesystem probing
 /// - **Unix:** Uses `statfs` and filesystem metadata probing
-pub fn inspect_path_and_status(path: &Path) -> Result<PathInfo, InspectPathError> {
-    let mut inspect = inspect_path(path)?;
-    inspect.check_status();
+pub fn inspect_path_and_status(path: impl AsRef<Path>) -> Result<PathInfo, InspectPathError> {
+    inspect_path_with(path, &InspectOptions::new().probe_status(true))
+}
+
+/// Lists the currently connected removable drives (USB sticks, SD cards).
+///
+/// Shorthand for [`list_volumes`] filtered to [`PathType::Removable`]: drive
+/// letters of type `DRIVE_REMOVABLE` on Windows, block devices with the sysfs
+/// `removable` flag on Linux. Use [`PathInfo::volume_label`] to show them to
+/// a user.
+///
+/// # Errors
+///
+/// Returns an error if the mount table can't be read.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use inspect_path::removable_drives;
+///
+/// for drive in removable_drives().unwrap() {
+///     let label = drive.volume_label().unwrap_or_else(|| "unnamed".into());
+///     println!("backup to '{label}' at {}?", drive.path().display());
+/// }
+/// ```
+pub fn removable_drives() -> Result<Vec<PathInfo>, InspectPathError> {
+    list_volumes(&VolumeFilter::new().kind(PathType::Removable))
+}
+
+/// Inspects a filesystem path according to `opts`.
+///
+/// With [`InspectOptions::default`] this is identical to [`inspect_path`].
+///
+/// # Errors
+///
+/// Returns an error if the path type cannot be determined or if the platform
+/// inspection call fails.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::path::Path;
+/// use inspect_path::{InspectOptions, inspect_path_with};
+///
+/// let opts = InspectOptions::new().probe_status(true);
+/// let info = inspect_path_with(Path::new("/"), &opts).unwrap();
+///
+/// assert!(!info.is_status_unknown());
+/// ```
+pub fn inspect_path_with(
+    path: impl AsRef<Path>,
+    opts: &InspectOptions,
+) -> Result<PathInfo, InspectPathError> {
+    inspect_path_with_inner(path.as_ref(), opts)
+}
+
+fn inspect_path_with_inner(
+    path: &Path,
+    opts: &InspectOptions,
+) -> Result<PathInfo, InspectPathError> {
+    if opts.require_existing {
+        std::fs::metadata(path).map_err(|source| InspectPathError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+    }
+    let mut inspect = platform::inspect_with(path, opts)?;
+    if opts.probe_status {
+        match opts.timeout {
+            Some(timeout) => inspect.check_status_with(timeout),
+            None => inspect.check_status(),
+        };
+    }
     Ok(inspect)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::hash::Hash;
     use std::path::Path;
     //use crate::platform::inspect_path;
 
+    // Compile-time checks so the derived bounds don't silently regress.
+    const _: () = {
+        const fn enum_bounds<T: Clone + Eq + Hash + Send + Sync>() {}
+        const fn info_bounds<T: Clone + Eq + Send + Sync>() {}
+        enum_bounds::<PathType>();
+        enum_bounds::<RemoteType>();
+        enum_bounds::<PathStatus>();
+        enum_bounds::<Confidence>();
+        enum_bounds::<PathKind>();
+        info_bounds::<PathInfo>();
+    };
+
+    #[test]
+    fn display_strings() {
+        assert_eq!(PathType::Fixed.to_string(), "fixed");
+        assert_eq!(PathType::CDRom.to_string(), "cdrom");
+        assert_eq!(PathType::RamDisk.to_string(), "ramdisk");
+        assert_eq!(PathType::Virtual("proc".into()).to_string(), "virtual:proc");
+        assert_eq!(RemoteType::NFS.to_string(), "nfs");
+        assert_eq!(RemoteType::WebDAV.to_string(), "webdav");
+        assert_eq!(RemoteType::NinePfs.to_string(), "9p");
+        assert_eq!(RemoteType::VirtioFs.to_string(), "virtiofs");
+        assert_eq!(RemoteType::Other("afp".into()).to_string(), "other:afp");
+        assert_eq!(PathStatus::Disconnected.to_string(), "disconnected");
+        assert_eq!(PathStatus::Other("busy".into()).to_string(), "other:busy");
+        assert_eq!(
+            PathStatus::PermissionLimited.to_string(),
+            "permission_limited"
+        );
+
+        let mut info = PathInfo {
+            path: PathBuf::from("/mnt/data"),
+            #[cfg(target_family = "unix")]
+            resolved_path: None,
+            #[cfg(target_family = "unix")]
+            is_symlink: false,
+            kind: PathType::Remote,
+            remote_kind: Some(RemoteType::NFS),
+            status: PathStatus::Mounted,
+            confidence: Confidence::High,
+            #[cfg(target_family = "unix")]
+            mount: None,
+            #[cfg(not(target_family = "unix"))]
+            fs_type: None,
+            #[cfg(not(target_family = "unix"))]
+            mount_point: None,
+            #[cfg(not(target_family = "unix"))]
+            device_id: None,
+            #[cfg(not(target_family = "unix"))]
+            read_only: None,
+        };
+        assert_eq!(info.to_string(), "/mnt/data: remote (nfs), mounted");
+
+        assert!(!info.is_vm_shared());
+        info.remote_kind = Some(RemoteType::VirtioFs);
+        assert!(info.is_vm_shared());
+
+        info.remote_kind = Some(RemoteType::Unknown);
+        info.status = PathStatus::Unknown;
+        assert_eq!(info.to_string(), "/mnt/data: remote");
+
+        assert_eq!(info.name_with_label(None), "Network Drive");
+        assert_eq!(info.name_with_label(Some("DATA".into())), "DATA");
+        info.kind = PathType::Virtual("proc".into());
+        assert_eq!(info.name_with_label(None), "proc");
+    }
+
+    #[test]
+    fn case_sensitivity_probe_cleans_up() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let info = inspect_path(dir.join("not/yet/there.txt")).unwrap();
+
+        let probed = info.probe_case_sensitivity().unwrap();
+        if let Some(known) = info.is_case_sensitive() {
+            assert_eq!(probed, known);
+        }
+        assert_eq!(std::fs::read_dir(dir).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn status_conversions() {
+        assert!(PathStatus::Mounted.ok_or_disconnected().is_ok());
+        for status in [PathStatus::Stale, PathStatus::Unknown, PathStatus::TimedOut] {
+            match status.ok_or_disconnected() {
+                Err(InspectPathError::Unavailable { path, status: s }) => {
+                    assert_eq!(path, PathBuf::new());
+                    assert_eq!(s, status);
+                }
+                other => panic!("{status}: {other:?}"),
+            }
+            assert!(!status.into_bool());
+        }
+        assert!(PathStatus::Mounted.into_bool());
+
+        let mut info = inspect_path(std::env::temp_dir()).unwrap();
+        info.status = PathStatus::Stale;
+        let err = info.ok_or_disconnected().unwrap_err();
+        assert_eq!(err.path(), Some(info.path().as_path()));
+        info.status = PathStatus::Mounted;
+        assert!(info.ok_or_disconnected().is_ok());
+    }
+
+    #[test]
+    fn check_status_returns_previous() {
+        let mut info = inspect_path(std::env::temp_dir()).unwrap();
+        let mut answers = [PathStatus::Mounted, PathStatus::Disconnected]
+            .into_iter()
+            .cycle();
+        let mut probe = || {
+            let answer = answers.next().unwrap();
+            info.check_status_using(|_| answer)
+        };
+
+        assert_eq!(probe(), PathStatus::Unknown);
+        assert_eq!(probe(), PathStatus::Mounted);
+        assert_eq!(probe(), PathStatus::Disconnected);
+        assert_eq!(info.status(), &PathStatus::Mounted);
+    }
+
+    #[test]
+    fn refresh_replaces_stale_classification() {
+        let dir = std::env::temp_dir();
+        let mut info = inspect_path(&dir).unwrap();
+        let fresh = info.clone();
+
+        info.kind = PathType::Remote;
+        info.remote_kind = Some(RemoteType::SMB);
+        info.status = PathStatus::Disconnected;
+        assert!(info.refresh().unwrap());
+        assert_eq!(info.kind, fresh.kind);
+        assert_eq!(info.remote_kind, fresh.remote_kind);
+        assert_eq!(info.status, PathStatus::Mounted);
+        assert_eq!(info.path(), dir.as_path());
+        assert!(!info.refresh().unwrap());
+
+        let mut gone = info.clone();
+        gone.path = dir.join(format!("inspect_path_refresh_{}/x", std::process::id()));
+        let strict = InspectOptions::new().require_existing(true);
+        assert!(gone.refresh_with(&strict).is_err());
+        assert_eq!(gone.status, PathStatus::Mounted);
+    }
+
+    #[test]
+    fn device_ids_group_paths() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        std::fs::write(dir.join("a"), "a").unwrap();
+        std::fs::write(dir.join("b"), "b").unwrap();
+
+        let a = inspect_path(dir.join("a")).unwrap().device_id();
+        let b = inspect_path(dir.join("b")).unwrap().device_id();
+        assert!(a.is_some());
+        assert_eq!(a, b);
+        assert!(same_filesystem(dir.join("a"), dir.join("b")).unwrap());
+        assert!(!a.unwrap().to_string().is_empty());
+    }
+
+    #[cfg(target_family = "unix")]
+    #[test]
+    fn filesystem_uuids() {
+        assert_eq!(
+            inspect_path(Path::new("/proc")).unwrap().filesystem_uuid(),
+            None
+        );
+    }
+
+    #[test]
+    fn stable_ids() {
+        let dir = std::env::temp_dir();
+        let a = inspect_path(&dir).unwrap().stable_id();
+        assert_eq!(a.len(), 16);
+        assert!(a.bytes().all(|b| b.is_ascii_hexdigit()));
+        assert_eq!(
+            a,
+            inspect_path(dir.join("inspect_path_x"))
+                .unwrap()
+                .stable_id()
+        );
+    }
+
+    #[test]
+    fn path_kind_summary() {
+        let mut info = inspect_path(std::env::temp_dir()).unwrap();
+        let mut kind_of = |kind, remote_kind| {
+            info.kind = kind;
+            info.remote_kind = remote_kind;
+            PathKind::from(&info)
+        };
+
+        assert_eq!(kind_of(PathType::Fixed, None), PathKind::LocalFixed);
+        assert_eq!(kind_of(PathType::Removable, None), PathKind::LocalRemovable);
+        assert_eq!(kind_of(PathType::CDRom, None), PathKind::Optical);
+        assert_eq!(kind_of(PathType::RamDisk, None), PathKind::Ram);
+        assert_eq!(kind_of(PathType::Unknown, None), PathKind::Unknown);
+        assert_eq!(
+            kind_of(PathType::Remote, Some(RemoteType::NFS)),
+            PathKind::NetworkNfs
+        );
+        assert_eq!(
+            kind_of(PathType::Remote, Some(RemoteType::SMB)),
+            PathKind::NetworkSmb
+        );
+        assert_eq!(
+            kind_of(PathType::Remote, Some(RemoteType::SSHFS)),
+            PathKind::NetworkOther
+        );
+        assert_eq!(kind_of(PathType::Remote, None), PathKind::NetworkOther);
+        assert_eq!(
+            kind_of(PathType::Virtual("proc".into()), None),
+            PathKind::Virtual
+        );
+
+        assert_eq!(PathKind::NetworkSmb.to_string(), "network_smb");
+        assert_eq!(PathKind::LocalRemovable.to_string(), "local_removable");
+    }
+
+    #[cfg(target_family = "unix")]
+    #[test]
+    fn path_ext_delegates() {
+        let path = PathBuf::from("/");
+        assert_eq!(path.inspect().unwrap(), inspect_path(&path).unwrap());
+        assert_eq!(path.mount_status(), PathStatus::Mounted);
+
+        let opts = InspectOptions::new().probe_status(true);
+        assert!(path.inspect_with(&opts).unwrap().is_status_mounted());
+    }
+
+    #[test]
+    fn network_dependence() {
+        let info = |kind| PathInfo::synthetic("/mnt/x".into(), kind, None, PathStatus::Mounted);
+        assert!(info(PathType::Remote).depends_on_network());
+        // synthetic infos have no mount to look the disk up from
+        assert_eq!(info(PathType::Fixed).is_iscsi(), None);
+        assert!(!info(PathType::Fixed).depends_on_network());
+    }
+
+    #[cfg(target_family = "unix")]
+    #[test]
+    fn timeout_bounds_only_the_status_probe() {
+        let opts = InspectOptions::new().timeout(std::time::Duration::from_secs(10));
+        assert!(inspect_path_with("/", &opts).unwrap().is_status_unknown());
+        let probed = inspect_path_with("/", &opts.probe_status(true)).unwrap();
+        assert!(probed.is_status_mounted());
+    }
+
+    #[cfg(target_family = "unix")]
+    #[test]
+    fn inspect_path_and_status_probes() {
+        let info = inspect_path_and_status(Path::new("/")).unwrap();
+        assert!(info.is_status_mounted());
+    }
+
+    #[cfg(target_family = "unix")]
+    #[test]
+    fn try_from_conversions() {
+        let expected = inspect_path(Path::new("/etc")).unwrap();
+
+        assert_eq!(PathInfo::try_from(Path::new("/etc")).unwrap(), expected);
+        assert_eq!(PathInfo::try_from("/etc").unwrap(), expected);
+        assert_eq!(PathInfo::try_from(PathBuf::from("/etc")).unwrap(), expected);
+    }
+
+    #[cfg(target_family = "unix")]
+    #[test]
+    fn require_existing_option() {
+        let missing = Path::new("/etc/inspect_path_missing/file.txt");
+
+        assert!(inspect_path(missing).unwrap().is_fixed());
+
+        let strict = InspectOptions::new().require_existing(true);
+        assert!(matches!(
+            inspect_path_with(missing, &strict),
+            Err(InspectPathError::Io { .. })
+        ));
+    }
+
+    #[test]
+    fn nonexistent_path_error_keeps_os_code() {
+        use std::error::Error;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let missing = tmp.path().join("missing/file.txt");
+        let strict = InspectOptions::new().require_existing(true);
+        let err = inspect_path_with(&missing, &strict).unwrap_err();
+
+        assert_eq!(err.path(), Some(missing.as_path()));
+        let io = err
+            .source()
+            .unwrap()
+            .downcast_ref::<std::io::Error>()
+            .unwrap();
+        assert_eq!(io.kind(), std::io::ErrorKind::NotFound);
+        #[cfg(unix)]
+        assert_eq!(io.raw_os_error(), Some(libc::ENOENT));
+        // ERROR_PATH_NOT_FOUND: the parent directory is missing too
+        #[cfg(windows)]
+        assert_eq!(io.raw_os_error(), Some(3));
+    }
+
+    #[cfg(feature = "serde")]
+    fn round_trip<T>(value: &T) -> T
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned,
+    {
+        serde_json::from_str(&serde_json::to_string(value).unwrap()).unwrap()
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_enums() {
+        let kinds = vec![
+            PathType::Unknown,
+            PathType::Removable,
+            PathType::Fixed,
+            PathType::Remote,
+            PathType::CDRom,
+            PathType::RamDisk,
+            PathType::Virtual("proc".into()),
+        ];
+        for kind in kinds {
+            assert_eq!(round_trip(&kind), kind);
+        }
+
+        let remotes = [
+            RemoteType::WebDAV,
+            RemoteType::NFS,
+            RemoteType::SMB,
+            RemoteType::AFS,
+            RemoteType::SSHFS,
+            RemoteType::Ceph,
+            RemoteType::GlusterFS,
+            RemoteType::NinePfs,
+            RemoteType::VirtioFs,
+            RemoteType::Other("afp".into()),
+            RemoteType::Unknown,
+        ];
+        for remote in remotes {
+            assert_eq!(round_trip(&remote), remote);
+        }
+
+        let statuses = [
+            PathStatus::Mounted,
+            PathStatus::Disconnected,
+            PathStatus::Unknown,
+            PathStatus::Other("busy".into()),
+            PathStatus::Stale,
+            PathStatus::PermissionLimited,
+            PathStatus::NoMedia,
+            PathStatus::NotMounted,
+            PathStatus::TimedOut,
+        ];
+        for status in statuses {
+            assert_eq!(round_trip(&status), status);
+        }
+
+        assert_eq!(
+            serde_json::to_string(&PathType::Fixed).unwrap(),
+            r#""fixed""#
+        );
+        assert_eq!(
+            serde_json::to_string(&RemoteType::Other("x".into())).unwrap(),
+            r#"{"other":"x"}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&PathKind::NetworkNfs).unwrap(),
+            format!(r#""{}""#, PathKind::NetworkNfs)
+        );
+        assert_eq!(round_trip(&PathKind::LocalFixed), PathKind::LocalFixed);
+    }
+
+    #[cfg(all(feature = "serde", target_family = "unix"))]
+    #[test]
+    fn serde_round_trip_path_info() {
+        let info = inspect_path_and_status(Path::new("/")).unwrap();
+        assert_eq!(round_trip(&info), info);
+    }
+
     #[cfg(target_os = "windows")]
     #[test]
     fn fixed_path_type() {
@@ -257,10 +2208,24 @@ mod tests {
             kind: PathType::Fixed,
             remote_kind: None,
             status: PathStatus::Unknown,
+            confidence: Confidence::High,
+            fs_type: None,
+            mount_point: None,
+            device_id: None,
+            read_only: None,
         };
 
         let path = Path::new(r"C:\");
-        let answer = inspect_path(path).unwrap();
+        let mut answer = inspect_path(path).unwrap();
+        assert!(
+            answer
+                .device_id
+                .take()
+                .is_some_and(|id| id.to_string().starts_with("Volume{"))
+        );
+        assert_eq!(answer.fs_type.take().as_deref(), Some("NTFS"));
+        assert_eq!(answer.read_only.take(), Some(false));
+        assert_eq!(answer.mount_point.take(), Some(PathBuf::from(r"C:\")));
 
         assert_eq!(path_type, answer);
     }
@@ -275,10 +2240,13 @@ mod tests {
             kind: PathType::Fixed,
             remote_kind: None,
             status: PathStatus::Unknown,
+            confidence: Confidence::High,
+            mount: None,
         };
 
         let path = Path::new(r"/etc/");
-        let answer = inspect_path(path).unwrap();
+        let mut answer = inspect_path(path).unwrap();
+        assert!(answer.mount.take().is_some());
 
         assert_eq!(path_type, answer);
     }
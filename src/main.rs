@@ -0,0 +1,399 @@
+//! `inspect-path`: a command line front end for diagnosing paths and mounts.
+//!
+//! ```text
+//! inspect-path info [--status] [--format json|table] <PATH>...
+//! inspect-path mounts [--all] [--format json|table]
+//! inspect-path watch <PATH>... [--interval 10s] [--format json|table]
+//! ```
+//!
+//! Exit codes: 0 if every path is mounted (or wasn't probed), 2 if any path
+//! is not, 1 on errors, which win over 2. `--` ends the options, so
+//! `info -- -h` inspects a path named `-h`. Built only with the `cli`
+//! feature.
+use inspect_path::{
+    InspectOptions, PathInfo, PathStatus, PathWatcher, StatusChange, VolumeFilter, WatchOptions,
+    inspect_path_with, list_volumes,
+};
+use serde_json::{Value, json};
+use std::{
+    path::PathBuf,
+    process::ExitCode,
+    time::{Duration, UNIX_EPOCH},
+};
+
+const USAGE: &str = "\
+usage: inspect-path info [--status] [--format json|table] <PATH>...
+       inspect-path mounts [--all] [--format json|table]
+       inspect-path watch <PATH>... [--interval 10s] [--format json|table]";
+
+const EXIT_ERROR: u8 = 1;
+const EXIT_UNAVAILABLE: u8 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Table,
+    Json,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+    Help,
+    Info {
+        paths: Vec<PathBuf>,
+        status: bool,
+    },
+    Mounts {
+        all: bool,
+    },
+    Watch {
+        paths: Vec<PathBuf>,
+        interval: Duration,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Args {
+    command: Command,
+    format: Format,
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(msg) => {
+            eprintln!("inspect-path: {msg}\n{USAGE}");
+            return ExitCode::from(EXIT_ERROR);
+        }
+    };
+
+    match args.command {
+        Command::Help => {
+            println!("{USAGE}");
+            ExitCode::SUCCESS
+        }
+        Command::Info { paths, status } => info(&paths, status, args.format),
+        Command::Mounts { all } => mounts(all, args.format),
+        Command::Watch { paths, interval } => watch(&paths, interval, args.format),
+    }
+}
+
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Args, String> {
+    let mut args = args.into_iter();
+    let command = args.next().ok_or("missing command")?;
+    let help = Args {
+        command: Command::Help,
+        format: Format::Table,
+    };
+    if command == "-h" || command == "--help" {
+        return Ok(help);
+    }
+
+    let mut format = Format::Table;
+    let mut paths = Vec::new();
+    let (mut status, mut all) = (false, false);
+    let mut interval = Duration::from_secs(10);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(help),
+            "--" => {
+                paths.extend(args.by_ref().map(PathBuf::from));
+                break;
+            }
+            "--format" => {
+                format = match args.next().as_deref() {
+                    Some("json") => Format::Json,
+                    Some("table") => Format::Table,
+                    other => return Err(format!("unknown format {other:?}")),
+                }
+            }
+            "--status" if command == "info" => status = true,
+            "--all" if command == "mounts" => all = true,
+            "--interval" if command == "watch" => {
+                let value = args.next().ok_or("--interval needs a value")?;
+                interval =
+                    parse_duration(&value).ok_or_else(|| format!("invalid interval {value:?}"))?;
+            }
+            flag if flag.starts_with("--") => return Err(format!("unknown option {flag}")),
+            path => paths.push(PathBuf::from(path)),
+        }
+    }
+
+    let command = match command.as_str() {
+        "info" | "watch" if paths.is_empty() => return Err(format!("{command} needs a path")),
+        "info" => Command::Info { paths, status },
+        "watch" => Command::Watch { paths, interval },
+        "mounts" if paths.is_empty() => Command::Mounts { all },
+        "mounts" => return Err("mounts takes no paths".into()),
+        other => return Err(format!("unknown command {other:?}")),
+    };
+    Ok(Args { command, format })
+}
+
+/// Parses `500ms`, `10s`, `2m` or a bare number of seconds. `None` if the
+/// unit is unknown or the value overflows.
+fn parse_duration(s: &str) -> Option<Duration> {
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let n: u64 = number.parse().ok()?;
+    match unit {
+        "ms" => Some(Duration::from_millis(n)),
+        "" | "s" => Some(Duration::from_secs(n)),
+        "m" => n.checked_mul(60).map(Duration::from_secs),
+        "h" => n.checked_mul(3600).map(Duration::from_secs),
+        _ => None,
+    }
+}
+
+fn info(paths: &[PathBuf], status: bool, format: Format) -> ExitCode {
+    let opts = InspectOptions::new().probe_status(status);
+    let (mut failed, mut unavailable) = (false, false);
+    let mut infos = Vec::new();
+
+    for path in paths {
+        match inspect_path_with(path, &opts) {
+            Ok(info) => {
+                unavailable |= !matches!(info.status(), PathStatus::Mounted | PathStatus::Unknown);
+                infos.push(info);
+            }
+            Err(e) => {
+                eprintln!("inspect-path: {}: {e}", path.display());
+                failed = true;
+            }
+        }
+    }
+
+    match format {
+        Format::Json => println!("{:#}", infos_json(&infos)),
+        Format::Table => print_table(&infos),
+    }
+    ExitCode::from(exit_code(failed, unavailable))
+}
+
+/// An error beats an unavailable path: the output is incomplete either way,
+/// and scripts check for 1 first.
+fn exit_code(failed: bool, unavailable: bool) -> u8 {
+    if failed {
+        EXIT_ERROR
+    } else if unavailable {
+        EXIT_UNAVAILABLE
+    } else {
+        0
+    }
+}
+
+fn mounts(all: bool, format: Format) -> ExitCode {
+    let filter = VolumeFilter::new().include_virtual(all);
+    match list_volumes(&filter) {
+        Ok(volumes) => {
+            match format {
+                Format::Json => println!("{:#}", infos_json(&volumes)),
+                Format::Table => print_table(&volumes),
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("inspect-path: {e}");
+            ExitCode::from(EXIT_ERROR)
+        }
+    }
+}
+
+fn watch(paths: &[PathBuf], interval: Duration, format: Format) -> ExitCode {
+    let opts = WatchOptions::new().interval(interval);
+    let (_watcher, changes) = match PathWatcher::spawn(paths, &opts) {
+        Ok(watch) => watch,
+        Err(e) => {
+            eprintln!("inspect-path: {e}");
+            return ExitCode::from(EXIT_ERROR);
+        }
+    };
+
+    for change in changes {
+        match format {
+            Format::Json => println!("{}", change_json(&change)),
+            Format::Table => println!(
+                "{}  {}: {} -> {}",
+                change
+                    .at
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs(),
+                change.path.display(),
+                change.old,
+                change.new
+            ),
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+/// Builds the output from public accessors only, so the JSON doesn't change
+/// shape with `PathInfo`'s private fields. `resolved_path` and `is_symlink`
+/// are only tracked, and so only reported, on Unix.
+fn infos_json(infos: &[PathInfo]) -> Value {
+    infos
+        .iter()
+        .map(|info| {
+            #[cfg_attr(not(unix), allow(unused_mut))]
+            let mut obj = json!({
+                "path": info.path(),
+                "kind": info.kind(),
+                "remote_kind": info.remote_type(),
+                "status": info.status(),
+                "confidence": info.confidence(),
+                "fs_type": info.fs_type_name(),
+                "mount_point": info.mount_point(),
+                "read_only": info.is_read_only(),
+                "device_id": info.device_id().map(|id| id.to_string()),
+            });
+            #[cfg(unix)]
+            {
+                obj["resolved_path"] = json!(info.resolved_path());
+                obj["is_symlink"] = json!(info.is_symlink());
+            }
+            obj
+        })
+        .collect()
+}
+
+fn change_json(change: &StatusChange) -> Value {
+    json!({
+        "path": change.path,
+        "old": change.old,
+        "new": change.new,
+        "at": change.at.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs_f64(),
+    })
+}
+
+fn print_table(infos: &[PathInfo]) {
+    println!(
+        "{:<32} {:<12} {:<10} {:<8} STATUS",
+        "PATH", "KIND", "REMOTE", "FS"
+    );
+    for info in infos {
+        println!(
+            "{:<32} {:<12} {:<10} {:<8} {}",
+            info.path().display().to_string(),
+            info.kind().to_string(),
+            info.remote_type()
+                .map_or_else(|| "-".into(), |r| r.to_string()),
+            info.fs_type_name().unwrap_or("-"),
+            info.status()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(line: &str) -> Result<Args, String> {
+        parse_args(line.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn parses_subcommands() {
+        assert_eq!(
+            parse("info --status /a /b --format json").unwrap(),
+            Args {
+                command: Command::Info {
+                    paths: vec!["/a".into(), "/b".into()],
+                    status: true
+                },
+                format: Format::Json
+            }
+        );
+        assert_eq!(
+            parse("mounts --all").unwrap().command,
+            Command::Mounts { all: true }
+        );
+        assert_eq!(
+            parse("watch /mnt/nfs --interval 500ms").unwrap().command,
+            Command::Watch {
+                paths: vec!["/mnt/nfs".into()],
+                interval: Duration::from_millis(500)
+            }
+        );
+
+        assert!(parse("").is_err());
+        assert!(parse("info").is_err());
+        assert!(parse("mounts /a").is_err());
+        assert!(parse("info --all /a").is_err());
+        assert!(parse("info /a --format yaml").is_err());
+        assert!(parse("watch /a --interval soon").is_err());
+        assert!(parse("frobnicate").is_err());
+
+        assert_eq!(parse("--help").unwrap().command, Command::Help);
+        assert_eq!(parse("info /a -h").unwrap().command, Command::Help);
+        assert_eq!(
+            parse("info -- -h --status").unwrap().command,
+            Command::Info {
+                paths: vec!["-h".into(), "--status".into()],
+                status: false
+            }
+        );
+    }
+
+    #[test]
+    fn errors_win_over_unavailable_paths() {
+        assert_eq!(exit_code(false, false), 0);
+        assert_eq!(exit_code(false, true), EXIT_UNAVAILABLE);
+        assert_eq!(exit_code(true, false), EXIT_ERROR);
+        assert_eq!(exit_code(true, true), EXIT_ERROR);
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(parse_duration("10s"), Some(Duration::from_secs(10)));
+        assert_eq!(parse_duration("10"), Some(Duration::from_secs(10)));
+        assert_eq!(parse_duration("2m"), Some(Duration::from_secs(120)));
+        assert_eq!(parse_duration("1h"), Some(Duration::from_secs(3600)));
+        assert_eq!(parse_duration("250ms"), Some(Duration::from_millis(250)));
+        assert_eq!(parse_duration("s"), None);
+        assert_eq!(parse_duration("5d"), None);
+        assert_eq!(parse_duration(&format!("{}h", u64::MAX)), None);
+    }
+
+    /// Replaces everything that depends on the machine with placeholders.
+    #[cfg(target_family = "unix")]
+    fn redact(value: &mut Value, tmp: &std::path::Path) {
+        let Value::Object(map) = value else {
+            return;
+        };
+        for (key, field) in map.iter_mut() {
+            *field = match key.as_str() {
+                "path" if field == &json!(tmp) => json!("<tmp>"),
+                "status" | "remote_kind" | "is_symlink" => continue,
+                _ if field.is_null() => continue,
+                _ => json!("<redacted>"),
+            };
+        }
+    }
+
+    #[cfg(target_family = "unix")]
+    #[test]
+    fn info_json_snapshot() {
+        let tmp = std::env::temp_dir();
+        let info = inspect_path_with(&tmp, &InspectOptions::new()).unwrap();
+        let mut json = infos_json(&[info]);
+        redact(&mut json[0], &tmp);
+
+        let expected = r#"[
+  {
+    "confidence": "<redacted>",
+    "device_id": "<redacted>",
+    "fs_type": "<redacted>",
+    "is_symlink": false,
+    "kind": "<redacted>",
+    "mount_point": "<redacted>",
+    "path": "<tmp>",
+    "read_only": "<redacted>",
+    "remote_kind": null,
+    "resolved_path": "<redacted>",
+    "status": "unknown"
+  }
+]"#;
+        assert_eq!(format!("{json:#}"), expected);
+    }
+}
@@ -0,0 +1,149 @@
+use crate::{MountTable, remote};
+use std::path::Path;
+
+/// Returns whether `path` looks like a network location, from its spelling
+/// alone.
+///
+/// No system calls are made, so this never blocks and is cheap enough for
+/// thousands of strings. Both path flavors are recognized on every platform:
+///
+/// - UNC paths: `\\server\share`, `//server/share` (either separator)
+/// - verbatim UNC paths: `\\?\UNC\server\share`
+/// - URLs with a network scheme: `smb://`, `nfs://`, `sftp://`, ...
+///
+/// Device and verbatim local paths (`\\.\pipe\x`, `\\?\C:\`) are not network
+/// paths.
+///
+/// A mapped drive letter (`Z:\`) or a Unix path under a network mount
+/// (`/mnt/nfs/x`) looks local; use [`is_network_path_with`] and a
+/// [`MountTable`] to catch those.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::path::Path;
+/// use inspect_path::is_network_path;
+///
+/// assert!(is_network_path(Path::new(r"\\nas\media")));
+/// assert!(is_network_path(Path::new("smb://nas/media")));
+/// assert!(!is_network_path(Path::new("/home/me")));
+/// ```
+pub fn is_network_path(path: impl AsRef<Path>) -> bool {
+    is_network_str(&path.as_ref().to_string_lossy())
+}
+
+/// Like [`is_network_path`], but also consults `table`: a path covered by a
+/// network mount (Unix) or on a mapped network drive (Windows) counts too.
+///
+/// The table is matched lexically, without touching `path`: symlinks are not
+/// followed and relative paths only match lexically.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::path::Path;
+/// use inspect_path::{MountTable, is_network_path_with};
+///
+/// let table = MountTable::load().unwrap();
+/// for line in ["/mnt/nfs/projects", "/home/me", r"\\nas\media"] {
+///     println!("{line}: {}", is_network_path_with(&table, Path::new(line)));
+/// }
+/// ```
+pub fn is_network_path_with(table: &MountTable, path: impl AsRef<Path>) -> bool {
+    let path = path.as_ref();
+    is_network_path(path) || table.is_network(path)
+}
+
+fn is_network_str(s: &str) -> bool {
+    let is_sep = |c: char| c == '\\' || c == '/';
+    let has_server = |rest: &str| rest.split(is_sep).next().is_some_and(|s| !s.is_empty());
+
+    // URL-style locations found in config files
+    if let Some((scheme, _)) = s.split_once("://")
+        && remote::from_scheme(scheme).is_some()
+    {
+        return true;
+    }
+
+    let verbatim_unc = r"\\?\UNC\";
+    if let Some(prefix) = s.get(..verbatim_unc.len())
+        && prefix.eq_ignore_ascii_case(verbatim_unc)
+    {
+        return has_server(&s[verbatim_unc.len()..]);
+    }
+
+    let mut chars = s.chars();
+    if !(chars.next().is_some_and(is_sep) && chars.next().is_some_and(is_sep)) {
+        return false;
+    }
+    let rest = &s[2..];
+    // `\\.\` devices and other `\\?\` verbatim paths are local
+    let mut rest_chars = rest.chars();
+    if matches!(rest_chars.next(), Some('.' | '?')) && rest_chars.next().is_none_or(is_sep) {
+        return false;
+    }
+    has_server(rest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn windows_prefix_shapes() {
+        let cases = [
+            (r"\\server\share", true),
+            (r"\\server\share\dir\file.txt", true),
+            (r"\\server", true),
+            (r"\\192.168.1.10\c$", true),
+            (r"\\server/share", true),
+            (r"\\?\UNC\server\share\file", true),
+            (r"\\?\unc\server\share", true),
+            (r"\\?\UNC\", false),
+            (r"\\?\C:\Windows", false),
+            (r"\\?\Volume{0b7b1c5e-0000-0000-0000-100000000000}\", false),
+            (r"\\.\pipe\name", false),
+            (r"\\.\C:", false),
+            (r"\\.", false),
+            (r"\\?", false),
+            (r"\\", false),
+            (r"\\\server", false),
+            (r"\server\share", false),
+            (r"C:\Users", false),
+            (r"Z:\mapped", false),
+            (r"C:", false),
+            ("", false),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(is_network_str(input), expected, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn unix_and_url_shapes() {
+        let cases = [
+            ("//server/share", true),
+            ("//server/share/dir", true),
+            ("//server", true),
+            ("/\\server\\share", true),
+            ("///server/share", false),
+            ("/", false),
+            ("//", false),
+            ("/mnt/nfs/share", false),
+            ("relative/path", false),
+            ("server:/export", false),
+            ("smb://nas/media", true),
+            ("SMB://NAS/media", true),
+            ("nfs://srv/export", true),
+            ("sftp://host/home", true),
+            ("davs://host/remote.php/dav", true),
+            ("file:///tmp/x", false),
+            ("https://example.com", false),
+            ("smb:", false),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(is_network_str(input), expected, "{input:?}");
+        }
+        assert!(is_network_path(Path::new("//server/share")));
+    }
+}
@@ -0,0 +1,325 @@
+use crate::InspectPathError;
+use std::{
+    ffi::{OsStr, OsString},
+    path::{Path, PathBuf},
+};
+
+/// Normalizes a path the way the inspectors see it, without touching the
+/// filesystem.
+///
+/// - a leading `~` expands to `HOME` (or `USERPROFILE`)
+/// - a relative path is joined to the current directory; on Windows a
+///   drive-relative path (`D:notes`) is resolved against that drive's current
+///   directory, the one case that needs a system call
+/// - `.` components, repeated separators and trailing separators are dropped
+/// - `..` removes the component before it; at the root it is dropped, so
+///   `/../etc` is `/etc`
+/// - on Windows, `/` becomes `\`, and a UNC prefix written with extra
+///   separators (`\\\server\\share`) is collapsed to `\\server\share`
+///
+/// Symlinks are not resolved, so `a/link/..` is `a`, which is not always
+/// where the OS would end up. A verbatim (`\\?\`) prefix is kept, since it
+/// changes how Windows parses the rest; see [`normalize_for_display`] to drop
+/// it.
+///
+/// # Errors
+///
+/// [`InspectPathError::InvalidPath`] for an empty path, and
+/// [`InspectPathError::Io`] if the current directory can't be read.
+///
+/// # Examples
+///
+/// ```rust
+/// use inspect_path::normalize_path;
+///
+/// # #[cfg(unix)]
+/// assert_eq!(
+///     normalize_path("/srv//data/./logs/../").unwrap(),
+///     std::path::Path::new("/srv/data")
+/// );
+/// ```
+pub fn normalize_path(path: impl AsRef<Path>) -> Result<PathBuf, InspectPathError> {
+    let path = path.as_ref();
+    if path.as_os_str().is_empty() {
+        return Err(InspectPathError::InvalidPath(String::new()));
+    }
+
+    let expanded = expand_tilde(path);
+    let absolute = std::path::absolute(&expanded).map_err(|source| InspectPathError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let normalized = lexical(absolute.as_os_str().as_encoded_bytes(), cfg!(windows));
+    // only split and joined at ASCII separators, so still a valid encoding
+    Ok(PathBuf::from(unsafe {
+        OsString::from_encoded_bytes_unchecked(normalized)
+    }))
+}
+
+/// Normalizes a path for showing to users and comparing spellings.
+///
+/// Applies the lexical part of [`normalize_path`] only: relative paths stay
+/// relative, `~` is kept, and nothing is read from the system. On Windows
+/// the verbatim prefix is removed where it can be (`\\?\C:\x` becomes
+/// `C:\x`, `\\?\UNC\server\share` becomes `\\server\share`) and the drive
+/// letter is lowercased.
+///
+/// # Examples
+///
+/// ```rust
+/// use inspect_path::normalize_for_display;
+///
+/// # #[cfg(unix)]
+/// assert_eq!(normalize_for_display("./logs//today/"), "logs/today");
+/// # #[cfg(windows)]
+/// assert_eq!(normalize_for_display(r"\\?\C:\Users\me\"), r"c:\Users\me");
+/// ```
+pub fn normalize_for_display(path: impl AsRef<Path>) -> String {
+    display(&path.as_ref().to_string_lossy(), cfg!(windows))
+}
+
+/// Expands a leading `~` to the home directory, if one is set.
+pub(crate) fn expand_tilde(path: &Path) -> PathBuf {
+    let s = path.as_os_str().as_encoded_bytes();
+    let rest = if s == b"~" {
+        Some(&s[1..])
+    } else {
+        s.strip_prefix(b"~/")
+            .or_else(|| s.strip_prefix(br"~\").filter(|_| cfg!(windows)))
+    };
+    match (
+        rest,
+        std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE")),
+    ) {
+        // split right after an ASCII separator, so still a valid encoding
+        (Some(rest), Some(home)) => {
+            PathBuf::from(home).join(unsafe { OsStr::from_encoded_bytes_unchecked(rest) })
+        }
+        _ => path.to_path_buf(),
+    }
+}
+
+fn display(s: &str, windows: bool) -> String {
+    let mut out = String::from_utf8_lossy(&lexical(s.as_bytes(), windows)).into_owned();
+    if !windows {
+        return out;
+    }
+    if let Some(rest) = out.strip_prefix(r"\\?\UNC\") {
+        out = format!(r"\\{rest}");
+    } else if let Some(rest) = out.strip_prefix(r"\\?\")
+        && is_drive(rest.as_bytes())
+    {
+        out = rest.to_string();
+    }
+    if is_drive(out.as_bytes()) {
+        out[..1].make_ascii_lowercase();
+    }
+    out
+}
+
+fn is_drive(b: &[u8]) -> bool {
+    b.len() >= 2 && b[0].is_ascii_alphabetic() && b[1] == b':'
+}
+
+/// The lexical normalization behind both public functions, for a Windows
+/// (`windows`) or Unix spelling, so either can be tested anywhere. Works on
+/// the encoded bytes so paths that aren't UTF-8 keep their names; only ASCII
+/// separators, `.` and `..` are looked at.
+fn lexical(s: &[u8], windows: bool) -> Vec<u8> {
+    let (prefix, rooted, rest) = split_prefix(s, windows);
+    // verbatim paths take `/` literally
+    let verbatim = prefix.starts_with(br"\\?\");
+    let is_sep = |c: &u8| {
+        if verbatim {
+            *c == b'\\'
+        } else {
+            *c == b'/' || (windows && *c == b'\\')
+        }
+    };
+
+    let mut parts: Vec<&[u8]> = Vec::new();
+    for part in rest.split(is_sep) {
+        match part {
+            b"" | b"." => {}
+            b".." => match parts.last() {
+                Some(&last) if last != b".." => {
+                    parts.pop();
+                }
+                _ if rooted => {}
+                _ => parts.push(b".."),
+            },
+            part => parts.push(part),
+        }
+    }
+
+    let sep: &[u8] = if windows { b"\\" } else { b"/" };
+    let mut out = prefix;
+    if rooted {
+        out.extend_from_slice(sep);
+    }
+    out.extend_from_slice(&parts.join(sep));
+    if out.is_empty() {
+        out.push(b'.');
+    }
+    out
+}
+
+/// Splits off the part of `s` that `..` can't climb out of: the verbatim,
+/// device, UNC or drive prefix (normalized), whether a root follows it, and
+/// the rest.
+fn split_prefix(s: &[u8], windows: bool) -> (Vec<u8>, bool, &[u8]) {
+    if !windows {
+        return match s.strip_prefix(b"/") {
+            Some(rest) => (Vec::new(), true, rest),
+            None => (Vec::new(), false, s),
+        };
+    }
+
+    let is_sep = |c: &u8| *c == b'/' || *c == b'\\';
+    // the next `\`-separated component and where it ends
+    let component = |s: &[u8]| -> usize { s.iter().position(|&c| c == b'\\').unwrap_or(s.len()) };
+
+    for device in [br"\\?\".as_slice(), br"\\.\"] {
+        let Some(rest) = s.strip_prefix(device) else {
+            continue;
+        };
+        if device == br"\\?\"
+            && rest
+                .get(..4)
+                .is_some_and(|p| p.eq_ignore_ascii_case(br"UNC\"))
+        {
+            let rest = &rest[4..];
+            let end = component(rest);
+            let server = &rest[..end];
+            let rest = trim_start(&rest[end..], |c| *c == b'\\');
+            let end = component(rest);
+            let prefix = [br"\\?\UNC\".as_slice(), server, b"\\", &rest[..end]].concat();
+            return (prefix, true, &rest[end..]);
+        }
+        let end = component(rest);
+        return ([device, &rest[..end]].concat(), true, &rest[end..]);
+    }
+
+    if s.len() >= 2 && is_sep(&s[0]) && is_sep(&s[1]) {
+        let rest = trim_start(s, is_sep);
+        let end = rest.iter().position(is_sep).unwrap_or(rest.len());
+        let server = &rest[..end];
+        let rest = trim_start(&rest[end..], is_sep);
+        let end = rest.iter().position(is_sep).unwrap_or(rest.len());
+        let prefix = [br"\\".as_slice(), server, b"\\", &rest[..end]].concat();
+        return (prefix, true, &rest[end..]);
+    }
+
+    if is_drive(s) {
+        let rest = &s[2..];
+        return (s[..2].to_vec(), rest.first().is_some_and(is_sep), rest);
+    }
+    match s.first() {
+        Some(c) if is_sep(c) => (Vec::new(), true, &s[1..]),
+        _ => (Vec::new(), false, s),
+    }
+}
+
+fn trim_start(s: &[u8], sep: impl Fn(&u8) -> bool) -> &[u8] {
+    &s[s.iter().position(|c| !sep(c)).unwrap_or(s.len())..]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unix_spellings() {
+        let cases = [
+            ("/srv//data/./logs/../", "/srv/data"),
+            ("/../../etc", "/etc"),
+            ("/..", "/"),
+            ("//", "/"),
+            ("a/b/", "a/b"),
+            ("a/../..", ".."),
+            ("../x/./y", "../x/y"),
+            ("./", "."),
+            // not a separator on Unix
+            (r"a\b/c", r"a\b/c"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(
+                lexical(input.as_bytes(), false),
+                expected.as_bytes(),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn windows_spellings() {
+        let cases = [
+            (r"C:\Users\me\..\you\", r"C:\Users\you"),
+            (r"C:/Users\me/docs", r"C:\Users\me\docs"),
+            (r"C:\..\..\Windows", r"C:\Windows"),
+            (r"C:\", r"C:\"),
+            (r"C:notes\..\..", r"C:.."),
+            (r"\\server\\share\\\dir\.\", r"\\server\share\dir"),
+            (r"//server/share/../x", r"\\server\share\x"),
+            (r"\\\server\share", r"\\server\share\"),
+            (r"\temp\..\..", r"\"),
+            (r"\\?\C:\a\..\b", r"\\?\C:\b"),
+            (r"\\?\unc\server\share\x\..", r"\\?\UNC\server\share\"),
+            // `/` is part of the name in a verbatim path
+            (r"\\?\C:\a/b", r"\\?\C:\a/b"),
+            (r"\\.\pipe\name", r"\\.\pipe\name"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(
+                lexical(input.as_bytes(), true),
+                expected.as_bytes(),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn display_strips_verbatim_and_lowercases_drives() {
+        assert_eq!(display(r"\\?\C:\Users\me\", true), r"c:\Users\me");
+        assert_eq!(display(r"D:/data", true), r"d:\data");
+        assert_eq!(
+            display(r"\\?\UNC\server\share\x", true),
+            r"\\server\share\x"
+        );
+        let volume = r"\\?\Volume{0b7b1c5e-0000-0000-0000-100000000000}\";
+        assert_eq!(display(volume, true), volume);
+        assert_eq!(display("C:/x", false), "C:/x");
+    }
+
+    #[test]
+    fn normalize_path_makes_absolute() {
+        let cwd = std::env::current_dir().unwrap();
+        let normalized = normalize_path("x/./y/..").unwrap();
+        assert!(normalized.is_absolute());
+        assert_eq!(normalized, cwd.join("x"));
+
+        assert!(matches!(
+            normalize_path(""),
+            Err(InspectPathError::InvalidPath(_))
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn keeps_names_that_are_not_utf8() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = Path::new(OsStr::from_bytes(b"/srv/\xff\xfe/./x/.."));
+        assert_eq!(
+            normalize_path(path).unwrap().as_os_str().as_bytes(),
+            b"/srv/\xff\xfe"
+        );
+        let home = Path::new(OsStr::from_bytes(b"~/\xff"));
+        assert!(
+            expand_tilde(home)
+                .as_os_str()
+                .as_bytes()
+                .ends_with(b"/\xff")
+        );
+    }
+}
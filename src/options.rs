@@ -0,0 +1,183 @@
+use crate::PathType;
+use std::{path::PathBuf, time::Duration};
+
+/// Options controlling how [`inspect_path_with`](crate::inspect_path_with)
+/// inspects a path.
+///
+/// Built with chained setters starting from [`InspectOptions::new`], which is
+/// equivalent to [`InspectOptions::default`] and matches the behavior of
+/// [`inspect_path`](crate::inspect_path).
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::{path::Path, time::Duration};
+/// use inspect_path::{InspectOptions, inspect_path_with};
+///
+/// let opts = InspectOptions::new()
+///     .follow_symlinks(false)
+///     .probe_status(true)
+///     .timeout(Duration::from_secs(2));
+/// let info = inspect_path_with(Path::new("/mnt/data"), &opts).unwrap();
+/// ```
+///
+/// Options a platform can't honor are documented as such and ignored there,
+/// rather than failing.
+///
+/// There is no option to leave automounts alone: classifying a path resolves
+/// it, which triggers an autofs mount on Linux. [`MountTable`](crate::MountTable)
+/// matches paths without touching them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InspectOptions {
+    pub(crate) probe_status: bool,
+    pub(crate) require_existing: bool,
+    #[cfg_attr(not(any(unix, windows)), allow(dead_code))]
+    pub(crate) follow_symlinks: bool,
+    pub(crate) timeout: Option<Duration>,
+    #[cfg_attr(not(unix), allow(dead_code))]
+    pub(crate) mount_source: Option<PathBuf>,
+}
+
+impl Default for InspectOptions {
+    fn default() -> Self {
+        Self {
+            probe_status: false,
+            require_existing: false,
+            follow_symlinks: true,
+            timeout: None,
+            mount_source: None,
+        }
+    }
+}
+
+impl InspectOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run [`check_status`](crate::check_status) after classifying the path,
+    /// so the returned status is not [`PathStatus::Unknown`](crate::PathStatus::Unknown).
+    ///
+    /// Defaults to `false`. Probing may block on unreachable network mounts.
+    pub fn probe_status(mut self, probe: bool) -> Self {
+        self.probe_status = probe;
+        self
+    }
+
+    /// Fail with [`InspectPathError::Io`](crate::InspectPathError::Io) if the
+    /// path does not exist.
+    ///
+    /// Defaults to `false`: a path that doesn't exist yet is classified by its
+    /// nearest existing ancestor, i.e. by the filesystem a new file at that
+    /// path would be created on.
+    pub fn require_existing(mut self, require: bool) -> Self {
+        self.require_existing = require;
+        self
+    }
+
+    /// Classify the filesystem a symlink at `path` points into, rather than
+    /// the one the link itself lives on.
+    ///
+    /// Defaults to `true`. With `false`, symlinks in the parent directories
+    /// are still followed but the final component is left as it is, as with
+    /// [`inspect_path_lexical`](crate::inspect_path_lexical). On Windows a
+    /// junction counts as a symlink.
+    pub fn follow_symlinks(mut self, follow: bool) -> Self {
+        self.follow_symlinks = follow;
+        self
+    }
+
+    /// Give up on the status probe after `timeout`, reporting
+    /// [`PathStatus::TimedOut`](crate::PathStatus::TimedOut), like
+    /// [`PathInfo::check_status_with`](crate::PathInfo::check_status_with).
+    ///
+    /// Unset by default, so probing waits as long as the system does. Only
+    /// applies together with [`probe_status`](Self::probe_status); reading
+    /// the mount table isn't bounded.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Read the mount table from `file`, in `/proc/self/mountinfo` format,
+    /// instead of the live system's.
+    ///
+    /// Takes precedence over the `INSPECT_PATH_MOUNTINFO` environment
+    /// variable, e.g. to inspect against a captured table or another
+    /// process's `/proc/<pid>/mountinfo`. Ignored on Windows, which has no
+    /// mount table.
+    pub fn mount_source(mut self, file: impl Into<PathBuf>) -> Self {
+        self.mount_source = Some(file.into());
+        self
+    }
+}
+
+/// Selects which volumes [`list_volumes`](crate::list_volumes) returns.
+///
+/// By default every kind of volume is listed, except purely virtual
+/// filesystems (`proc`, `sysfs`, `overlay`, ...) on Unix, and bind mounts of
+/// a device that is already listed are skipped.
+///
+/// # Examples
+///
+/// ```rust
+/// use inspect_path::{PathType, VolumeFilter};
+///
+/// // destinations for a backup: USB sticks and network shares
+/// let filter = VolumeFilter::new()
+///     .kind(PathType::Removable)
+///     .kind(PathType::Remote);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VolumeFilter {
+    kinds: Vec<PathType>,
+    // only consulted for Unix mount tables
+    #[cfg_attr(windows, allow(dead_code))]
+    pub(crate) include_virtual: bool,
+    #[cfg_attr(windows, allow(dead_code))]
+    pub(crate) dedup_bind_mounts: bool,
+}
+
+impl Default for VolumeFilter {
+    fn default() -> Self {
+        Self {
+            kinds: Vec::new(),
+            include_virtual: false,
+            dedup_bind_mounts: true,
+        }
+    }
+}
+
+impl VolumeFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only list volumes of this kind. Can be called repeatedly to allow
+    /// several kinds; the payload of [`PathType::Virtual`] is ignored.
+    pub fn kind(mut self, kind: PathType) -> Self {
+        self.kinds.push(kind);
+        self
+    }
+
+    /// Also list virtual filesystems. Defaults to `false`.
+    pub fn include_virtual(mut self, include: bool) -> Self {
+        self.include_virtual = include;
+        self
+    }
+
+    /// Skip mounts whose device and root were already listed (bind mounts of
+    /// the same tree). Defaults to `true`. Has no effect on Windows.
+    pub fn dedup_bind_mounts(mut self, dedup: bool) -> Self {
+        self.dedup_bind_mounts = dedup;
+        self
+    }
+
+    #[cfg(any(unix, windows))]
+    pub(crate) fn matches(&self, info: &crate::PathInfo) -> bool {
+        use std::mem::discriminant;
+
+        let kind = discriminant(info.kind());
+        self.kinds.is_empty() || self.kinds.iter().any(|k| discriminant(k) == kind)
+    }
+}
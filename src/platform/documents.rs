@@ -1,5 +1,5 @@
-use crate::{InspectPathError, inspect_path, inspect_path_and_status};
-use std::path::Path;
+use crate::{CredentialStore, InspectPathError, inspect_path, inspect_path_and_status};
+use std::{ffi::OsStr, path::Path};
 
 /// **Windows only.** This function is not available on Unix platforms yet.
 ///
@@ -36,14 +36,20 @@ use std::path::Path;
 ///
 /// - This call may prompt for credentials depending on system configuration.
 /// - Existing mappings using the same drive letter may cause failure.
-/// - The connection is created using default credentials unless otherwise configured.
+/// - The connection uses the credentials stored for the server in
+///   [`CredentialStore::global`], or the default credentials if there are none.
 /// - This function performs a system-level change.
 ///
 /// # See also
 ///
 /// - [`inspect_path`] — inspect mapped drives after connecting
 /// - [`inspect_path_and_status`] — inspect and verify availability
-pub fn mount_path(local: &str, remote: &str) -> Result<(), InspectPathError> {}
+#[cfg(not(target_os = "linux"))]
+pub fn mount_path(
+    local: impl AsRef<OsStr>,
+    remote: impl AsRef<OsStr>,
+) -> Result<(), InspectPathError> {
+}
 
 /// **Windows only.** This function is not available on Unix platforms yet.
 ///
@@ -89,21 +95,51 @@ pub fn mount_path(local: &str, remote: &str) -> Result<(), InspectPathError> {}
 /// - [`inspect_path`] — inspect mapped drives after connecting
 /// - [`inspect_path_and_status`] — inspect and verify availability
 pub fn mount_path_as_user(
-    local: &str,
-    remote: &str,
+    local: impl AsRef<OsStr>,
+    remote: impl AsRef<OsStr>,
     user: &str,
     password: &str,
 ) -> Result<(), InspectPathError> {
 }
 
+/// **Windows only.** This function is not available on Unix platforms yet.
+///
+/// Disconnects (unmaps) a network drive on Windows.
+///
+/// This function wraps the Win32 `WNetCancelConnection2W` API and removes the
+/// mapping from the user profile so it is not restored at next logon.
+///
+/// # Parameters
+///
+/// * `local` — Local drive name such as `"Z:"`
+/// * `force` — Disconnect even if files are open on the share
+///
+/// # Errors
+///
+/// Returns an error if the Win32 API call fails. The error variant will
+/// contain the raw Win32 error code as text.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use inspect_path::unmount_path;
+///
+/// unmount_path("Z:", false).unwrap();
+/// ```
+///
+/// # See also
+///
+/// - [`mount_path`](crate::mount_path) — create the mapping
+pub fn unmount_path(local: impl AsRef<OsStr>, force: bool) -> Result<(), InspectPathError> {}
+
 /// **Windows only.** This function is not available on Unix platforms yet.
 ///
 /// Attempts to mount a drive/share if the given path is not currently available.
 ///
 /// This helper checks whether `path` is accessible using [`inspect_path`]. If the path
 /// is reported as disconnected — or not found due to a missing mount — this function
-/// will attempt to mount the associated drive using [`mount_path`] and the provided
-/// `remote` target.
+/// will attempt to mount the associated drive using
+/// [`mount_path`](crate::mount_path) and the provided `remote` target.
 ///
 /// This is intended for workflows where files may live on removable or network drives
 /// (for example `Z:\file.csv`) that are not always mounted at runtime.
@@ -123,18 +159,20 @@ pub fn mount_path_as_user(
 ///
 /// - Path inspection fails with a non-mount-related error
 /// - The drive prefix cannot be determined
-/// - The remote path cannot be converted to a valid string
 /// - The mount operation fails
 ///
 /// # Platform Notes
 ///
 /// Drive mounting is platform-specific. This function is primarily intended for
-/// Windows drive-letter mounts and network shares.
+/// Windows drive-letter mounts and network shares. Like
+/// [`mount_path`](crate::mount_path), it uses the credentials stored for the
+/// server in [`CredentialStore::global`].
 ///
 /// # Examples
 ///
 /// ```rust,no_run
 /// use std::path::Path;
+/// use inspect_path::{InspectPathError, try_mount_if_needed};
 ///
 /// try_mount_if_needed(
 ///     Path::new("Z:\\partcount.csv"),
@@ -146,15 +184,20 @@ pub fn mount_path_as_user(
 /// # See Also
 ///
 /// - [`inspect_path`]
-/// - [`mount_path`]
-pub fn try_mount_if_needed(path: &Path, remote: &Path) -> Result<(), InspectPathError> {}
+/// - [`mount_path`](crate::mount_path)
+#[cfg(not(unix))]
+pub fn try_mount_if_needed(
+    path: impl AsRef<Path>,
+    remote: impl AsRef<Path>,
+) -> Result<(), InspectPathError> {
+}
 
 /// Attempts to mount a drive/share if the given path is not currently available.
 ///
 /// This helper checks whether `path` is accessible using [`inspect_path`]. If the path
 /// is reported as disconnected — or not found due to a missing mount — this function
-/// will attempt to mount the associated drive using [`mount_path`] and the provided
-/// `remote` target.
+/// will attempt to mount the associated drive using
+/// [`mount_path`](crate::mount_path) and the provided `remote` target.
 ///
 /// This is intended for workflows where files may live on removable or network drives
 /// (for example `Z:\file.csv`) that are not always mounted at runtime.
@@ -174,7 +217,6 @@ pub fn try_mount_if_needed(path: &Path, remote: &Path) -> Result<(), InspectPath
 ///
 /// - Path inspection fails with a non-mount-related error
 /// - The drive prefix cannot be determined
-/// - The remote path cannot be converted to a valid string
 /// - The mount operation fails
 ///
 /// # Platform Notes
@@ -186,6 +228,7 @@ pub fn try_mount_if_needed(path: &Path, remote: &Path) -> Result<(), InspectPath
 ///
 /// ```rust,no_run
 /// use std::path::Path;
+/// use inspect_path::{InspectPathError, try_mount_if_needed_as_user};
 ///
 /// try_mount_if_needed_as_user(
 ///     Path::new("Z:\\partcount.csv"),
@@ -199,5 +242,11 @@ pub fn try_mount_if_needed(path: &Path, remote: &Path) -> Result<(), InspectPath
 /// # See Also
 ///
 /// - [`inspect_path`]
-/// - [`mount_path`]
-pub fn try_mount_if_needed_as_user(path: &Path, remote: &Path, user: &str, password: &str) -> Result<(), InspectPathError> {}
+/// - [`mount_path`](crate::mount_path)
+pub fn try_mount_if_needed_as_user(
+    path: impl AsRef<Path>,
+    remote: impl AsRef<Path>,
+    user: &str,
+    password: &str,
+) -> Result<(), InspectPathError> {
+}
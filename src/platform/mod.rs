@@ -1,21 +1,57 @@
-use crate::PathStatus;
+use crate::{InspectPathError, PathInspector, PathStatus};
+#[cfg(any(unix, windows))]
+use std::io::{self, ErrorKind};
 use std::path::Path;
 
-#[cfg(docsrs)]
+#[cfg(all(docsrs, feature = "mount"))]
 mod documents;
-#[cfg(docsrs)]
-pub use documents::{mount_path, mount_path_as_user, try_mount_if_needed, try_mount_if_needed_as_user};
+#[cfg(all(docsrs, feature = "mount"))]
+pub use documents::{mount_path_as_user, try_mount_if_needed_as_user, unmount_path};
+// the Unix backend documents these for both platforms
+#[cfg(all(docsrs, feature = "mount", not(target_os = "linux")))]
+pub use documents::mount_path;
+#[cfg(all(docsrs, feature = "mount", not(unix)))]
+pub use documents::try_mount_if_needed;
 
 cfg_if::cfg_if! {
     if #[cfg(target_os = "windows")] {
         mod windows;
-        pub use windows::{inspect_path, mount_path, mount_path_as_user, try_mount_if_needed, try_mount_if_needed_as_user};
-        pub fn check_status(path: &Path) -> PathStatus {
-            windows::check_status(path)
+        pub use windows::{MountTable, crosses_mount, inspect_path, inspect_paths, list_volumes, probe_status, same_filesystem};
+        pub use windows::{first_free_drive_letter, used_drive_letters};
+        pub use windows::inspect_path_lexical;
+        #[cfg(feature = "mount")]
+        pub use windows::{mount_path, mount_path_as_user, try_mount_if_needed, try_mount_if_needed_as_user, unmount_path};
+        #[cfg(feature = "mount")]
+        pub(crate) use windows::mount_share;
+        pub(crate) use windows::inspect_with;
+        #[cfg(feature = "test-util")]
+        pub use windows::record_win32_calls;
+        pub(crate) use windows::{
+            MountWatchStop, SessionCache, capabilities, identity_parts, inspect_cached, inspect_recorded, is_boot_partition, is_case_sensitive, is_dev_drive, is_snapshot, is_system_volume, link_support, max_filename_length, sector_sizes, snapshot_origin, space_info, volume_label, watch_mounts,
+        };
+        pub fn check_status(path: impl AsRef<Path>) -> PathStatus {
+            crate::SystemInspector.check_status(path.as_ref())
         }
     } else if #[cfg(target_family = "unix")] {
         mod unix;
-        pub use unix::inspect_path;
+        pub use unix::{MountTable, crosses_mount, inspect_path, inspect_paths, list_volumes, probe_status, same_filesystem};
+        pub use unix::{DeviceNumber, MountInfo, inspect_path_lexical, inspect_path_verbose, mounts};
+        pub(crate) use unix::{
+            SessionCache, capabilities, filesystem_uuid, identity_parts, inspect_cached, inspect_recorded, is_boot_partition, is_case_sensitive,
+            is_disk_image, is_iscsi, link_support, max_filename_length, parent_mount, sector_sizes, space_info,
+            volume_label,
+        };
+        pub(crate) use unix::inspect_with;
+        #[cfg(feature = "mount")]
+        pub use unix::{MountMethod, MountSpec, try_mount_if_needed, try_mount_if_needed_with};
+        #[cfg(all(feature = "mount", target_os = "linux"))]
+        pub use unix::mount_path;
+        #[cfg(all(feature = "mount", target_os = "linux"))]
+        pub(crate) use unix::{mount_share, unmount_syscall};
+        #[cfg(target_os = "linux")]
+        pub use unix::{check_status_fast, inspect_in_namespace};
+        #[cfg(target_os = "linux")]
+        pub(crate) use unix::{MountWatchStop, quota, watch_mounts};
 
         /// Probes a path to determine its current mount/connection status.
         ///
@@ -29,7 +65,12 @@ cfg_if::cfg_if! {
         ///
         /// - [`PathStatus::Mounted`] — The path responded to metadata access
         /// - [`PathStatus::Disconnected`] — The path appears unavailable (typically
-        ///   network or device not connected) *(Windows only — see below)*
+        ///   network or device not connected)
+        /// - [`PathStatus::Stale`] — A stale network file handle (NFS `ESTALE`)
+        /// - [`PathStatus::PermissionLimited`] — The path exists but access is denied
+        /// - [`PathStatus::NoMedia`] — A removable or optical drive has no media
+        /// - [`PathStatus::NotMounted`] — Nothing is attached where a device or
+        ///   network connection is expected
         /// - [`PathStatus::Unknown`] — Status could not be determined reliably
         ///
         /// # Behavior
@@ -40,22 +81,12 @@ cfg_if::cfg_if! {
         ///
         /// # Platform differences
         ///
-        /// ## Windows
+        /// Raw OS error codes are mapped through a per-platform table (Win32 error
+        /// codes on Windows, `errno` values on Unix); errors without a raw code fall
+        /// back to their [`std::io::ErrorKind`]. Anything unmapped is Unknown.
         ///
-        /// Error kinds are mapped to status:
-        ///
-        /// - `NotFound`, `TimedOut`, `NetworkDown`, `NotConnected` → Disconnected
-        /// - `PermissionDenied` → Mounted (exists but access restricted)
-        /// - Other errors → Unknown
-        ///
-        /// ## Unix
-        ///
-        /// Currently uses a simpler probe:
-        ///
-        /// - Success → Mounted
-        /// - Any error → Unknown
-        ///
-        /// (Future versions may distinguish disconnected network mounts more precisely.)
+        /// This never fails; use [`probe_status`] to get an
+        /// error for malformed paths instead of [`PathStatus::Unknown`].
         ///
         /// # Examples
         ///
@@ -64,11 +95,14 @@ cfg_if::cfg_if! {
         /// use inspect_path::inspect_path;
         ///
         /// let mut info = inspect_path(Path::new("/")).unwrap();
-        /// info.check_status();
+        /// let previous = info.check_status();
         ///
         /// if info.is_status_mounted() {
         ///     println!("Path is reachable");
         /// }
+        /// if previous != *info.status() {
+        ///     println!("{previous} -> {}", info.status());
+        /// }
         /// ```
         ///
         /// # Notes
@@ -76,10 +110,99 @@ cfg_if::cfg_if! {
         /// This is a heuristic check. Some filesystems may report as available even
         /// if later operations fail, and some virtual filesystems may always appear
         /// mounted.
-        pub fn check_status(path: &Path) -> PathStatus {
-            unix::check_status(path)
+        pub fn check_status(path: impl AsRef<Path>) -> PathStatus {
+            crate::SystemInspector.check_status(path.as_ref())
         }
     } else {
-        compile_error!("unsupported platform");
+        mod unsupported;
+        pub use unsupported::{MountTable, crosses_mount, inspect_path, inspect_paths, list_volumes, probe_status, same_filesystem};
+        pub(crate) use unsupported::{
+            SessionCache, capabilities, identity_parts, inspect_cached, inspect_recorded, is_boot_partition, is_case_sensitive, is_dev_drive, is_snapshot, is_system_volume, link_support, max_filename_length, sector_sizes, snapshot_origin, space_info, volume_label,
+        };
+        pub(crate) use unsupported::inspect_with;
+
+        /// Always [`PathStatus::Unknown`]: this target has no filesystem to
+        /// probe.
+        pub fn check_status(path: impl AsRef<Path>) -> PathStatus {
+            crate::SystemInspector.check_status(path.as_ref())
+        }
+    }
+}
+
+/// Rejects paths no probe can succeed on: empty, or containing a NUL.
+pub(crate) fn validate_path(path: &Path) -> Result<(), InspectPathError> {
+    let os = path.as_os_str();
+    if os.is_empty() || os.as_encoded_bytes().contains(&0) {
+        return Err(InspectPathError::InvalidPath(path.display().to_string()));
     }
+    Ok(())
+}
+
+#[cfg(any(unix, windows))]
+/// Runs a metadata probe, mapping failures through `table` (raw OS error code
+/// to status) and then through the error kind.
+pub(crate) fn probe_with(
+    path: &Path,
+    table: &[(i32, PathStatus)],
+) -> Result<PathStatus, InspectPathError> {
+    validate_path(path)?;
+
+    match crate::probe::timed(path, || std::fs::metadata(path)) {
+        Ok(_) => Ok(PathStatus::Mounted),
+        Err(e) => Ok(status_from_error(&e, table)),
+    }
+}
+
+#[cfg(any(unix, windows))]
+/// Maps a failed space query to [`InspectPathError::Unavailable`] when the
+/// error means the volume is gone, and to [`InspectPathError::Io`] otherwise.
+pub(crate) fn space_error(
+    path: &Path,
+    source: io::Error,
+    table: &[(i32, PathStatus)],
+) -> InspectPathError {
+    match status_from_error(&source, table) {
+        status @ (PathStatus::Disconnected
+        | PathStatus::Stale
+        | PathStatus::NoMedia
+        | PathStatus::NotMounted) => InspectPathError::Unavailable {
+            path: path.to_path_buf(),
+            status,
+        },
+        _ => InspectPathError::Io {
+            path: path.to_path_buf(),
+            source,
+        },
+    }
+}
+
+#[cfg(any(unix, windows))]
+pub(crate) fn status_from_error(e: &io::Error, table: &[(i32, PathStatus)]) -> PathStatus {
+    if let Some(code) = e.raw_os_error()
+        && let Some((_, status)) = table.iter().find(|(c, _)| *c == code)
+    {
+        return status.clone();
+    }
+
+    match e.kind() {
+        ErrorKind::NotFound
+        | ErrorKind::TimedOut
+        | ErrorKind::NetworkDown
+        | ErrorKind::NetworkUnreachable
+        | ErrorKind::HostUnreachable
+        | ErrorKind::NotConnected => PathStatus::Disconnected,
+
+        ErrorKind::StaleNetworkFileHandle => PathStatus::Stale,
+        ErrorKind::PermissionDenied => PathStatus::PermissionLimited,
+
+        _ => PathStatus::Unknown,
+    }
+}
+
+/// 64-bit FNV-1a over `data`, one unit at a time. Unlike `DefaultHasher` it is
+/// fixed, so hashes stay comparable across runs and Rust versions.
+pub(crate) fn fnv1a<T: Into<u64>>(data: impl IntoIterator<Item = T>) -> u64 {
+    data.into_iter().fold(0xcbf2_9ce4_8422_2325, |hash, unit| {
+        (hash ^ unit.into()).wrapping_mul(0x0100_0000_01b3)
+    })
 }
@@ -1,56 +1,124 @@
-use crate::{InspectPathError, PathInfo, PathStatus, PathType, RemoteType};
+use crate::{
+    AtimePolicy, Capabilities, Confidence, DeviceId, InspectOptions, InspectPathError, PathInfo,
+    PathInspector, PathStatus, PathType, RemoteType, SpaceInfo, VolumeFilter,
+    normalize::expand_tilde,
+    remote::{self, NFS},
+    report::Recorder,
+};
 use std::{
-    fs::{self, read_to_string},
+    collections::HashMap,
+    fs,
+    io::{BufRead, BufReader},
     path::{Path, PathBuf},
+    sync::Arc,
 };
 
+#[cfg(feature = "mount")]
+mod mount;
+#[cfg(all(feature = "mount", target_os = "linux"))]
+pub use mount::mount_path;
+#[cfg(feature = "mount")]
+pub use mount::{MountMethod, MountSpec, try_mount_if_needed, try_mount_if_needed_with};
+#[cfg(all(feature = "mount", target_os = "linux"))]
+pub(crate) use mount::{mount_share, unmount_syscall};
+
 // path to mountinfo
 const MOUNTINFO_PATH: &str = "/proc/self/mountinfo";
-// remote fs types
-const REMOTE_FS_TYPES: &[&[&str]] = &[NFS, SMB, SSH, CLUSTER, PROTOCOL, OTHER];
-const NFS: &[&str] = &[
-    // NFS
-    "nfs", "nfs4",
-];
-const SMB: &[&str] = &[
-    // SMB / CIFS
-    "cifs", "smbfs", "smb3",
-];
-const SSH: &[&str] = &[
-    // SSH
-    "sshfs",
-    "fuse.sshfs",
+// overrides MOUNTINFO_PATH when set
+const MOUNTINFO_ENV: &str = "INSPECT_PATH_MOUNTINFO";
+// root of sysfs
+const SYS_PATH: &str = "/sys";
+// formats that can't be written to, whatever the mount options say
+const READ_ONLY_FS_TYPES: &[&str] = &["squashfs", "iso9660", "erofs", "cramfs"];
+// longest mountinfo line accepted: four escaped PATH_MAX names and a long
+// overlayfs `lowerdir=` list fit with room to spare
+const MAX_MOUNTINFO_LINE: usize = 1 << 20;
+// most fields accepted before the ` - ` separator: six fixed ones and the
+// optional propagation fields
+const MAX_MOUNTINFO_FIELDS: usize = 64;
+// writable formats the kernel may remount read-only after errors
+const ERRORS_REMOUNT_RO_FS_TYPES: &[&str] = &["ext2", "ext3", "ext4", "xfs", "btrfs", "f2fs"];
+// formats that fold case on every lookup
+const CASE_INSENSITIVE_FS_TYPES: &[&str] = &[
+    "vfat", "msdos", "exfat", "ntfs", "ntfs3", "apfs", "hfs", "hfsplus",
 ];
-const CLUSTER: &[&str] = &[
-    // Cluster / distributed
-    "ceph",
-    "fuse.ceph",
-    "glusterfs",
-    "fuse.glusterfs",
+// case-sensitive by default, but directories may opt into case folding (`chattr +F`)
+const CASEFOLD_FS_TYPES: &[&str] = &["ext4", "f2fs", "tmpfs"];
+// formats without hard or symbolic links
+const NO_LINK_FS_TYPES: &[&str] = &["vfat", "msdos", "exfat"];
+// udev's label → device symlinks
+const BY_LABEL_PATH: &str = "/dev/disk/by-label";
+const BY_UUID_PATH: &str = "/dev/disk/by-uuid";
+// errno of a failed metadata probe → status
+const ERRNO_STATUS: &[(i32, PathStatus)] = &[
+    (libc::ENOENT, PathStatus::Disconnected),
+    (libc::ENOTCONN, PathStatus::Disconnected),
+    (libc::ETIMEDOUT, PathStatus::Disconnected),
+    (libc::EHOSTDOWN, PathStatus::Disconnected),
+    (libc::EHOSTUNREACH, PathStatus::Disconnected),
+    (libc::ENETDOWN, PathStatus::Disconnected),
+    (libc::ENETUNREACH, PathStatus::Disconnected),
+    (libc::ECONNREFUSED, PathStatus::Disconnected),
+    (libc::ESTALE, PathStatus::Stale),
+    (libc::EACCES, PathStatus::PermissionLimited),
+    (libc::EPERM, PathStatus::PermissionLimited),
+    #[cfg(target_os = "linux")]
+    (libc::ENOMEDIUM, PathStatus::NoMedia),
+    (libc::ENODEV, PathStatus::NotMounted),
+    (libc::ENXIO, PathStatus::NotMounted),
 ];
-const PROTOCOL: &[&str] = &[
-    // Network / protocol FS
-    "9p",
-    "afp",
-    "davfs",
-    "fuse.davfs",
-];
-const OTHER: &[&str] = &[
-    // Older / less common but still seen
-    "ncpfs", "coda", "ocfs2", "gfs", "gfs2",
-];
-// local fs types
+// local fs types; kept unformatted so each label stays above its group
+#[rustfmt::skip]
 const LOCAL_BLOCK_FS_TYPES: &[&str] = &[
     // Linux native
     "ext2", "ext3", "ext4", "xfs", "btrfs", "f2fs", "jfs", "reiserfs", "reiser4", "bcachefs",
     // FAT family
-    "vfat", "msdos", "exfat", // NTFS
-    "ntfs", "ntfs3", // ZFS (out of tree but common)
+    "vfat", "msdos", "exfat",
+    // NTFS
+    "ntfs", "ntfs3",
+    // ZFS (out of tree but common)
     "zfs",
+    // BSD
+    "ufs", "ffs",
+    // macOS
+    "apfs", "hfs",
 ];
+const VIRTUAL_FS_TYPES: &[&str] = &[
+    // Kernel pseudo filesystems
+    "proc",
+    "sysfs",
+    "devtmpfs",
+    "devpts",
+    "cgroup",
+    "cgroup2",
+    "mqueue",
+    "hugetlbfs",
+    "securityfs",
+    "debugfs",
+    "tracefs",
+    "configfs",
+    "pstore",
+    "bpf",
+    "efivarfs",
+    "fusectl",
+    "binfmt_misc",
+    "autofs",
+    "nsfs",
+    "rpc_pipefs",
+    "selinuxfs",
+    // BSD
+    "devfs",
+    "fdescfs",
+    "procfs",
+    "linprocfs",
+    "linsysfs",
+];
+// tmpfs mounted here holds runtime state (`/run`, `/dev/shm`, ...), not files
+// a user put on a RAM disk
+const SYSTEM_TMPFS_PATHS: &[&str] = &["/dev", "/proc", "/run", "/sys"];
 const CDROM_FS_TYPES: &[&str] = &[
     // Optical / legacy media
-    "iso9660", "udf",
+    "iso9660", "udf", "cd9660",
 ];
 
 /// Inspects a filesystem path and returns detailed information about it.
@@ -64,268 +132,3537 @@ const CDROM_FS_TYPES: &[&str] = &[
 /// # Errors
 ///
 /// Returns an error if the path is invalid or its type cannot be determined.
-pub fn inspect_path(path: &Path) -> Result<PathInfo, InspectPathError> {
-    let (resolved_path, is_symlink) = get_resolved_path(path);
+///
+/// # Mount table
+///
+/// Mounts are read from `/proc/self/mountinfo`, or from the file named by the
+/// `INSPECT_PATH_MOUNTINFO` environment variable when it is set. The override
+/// is useful for fixtures and for sandboxes where `/proc` is masked.
+///
+/// # Nonexistent paths
+///
+/// A path that doesn't exist yet is classified by its nearest existing
+/// ancestor, so inspecting `/mnt/share/new.txt` before writing it reports the
+/// filesystem of `/mnt/share`. Use [`InspectOptions::require_existing`] with
+/// [`inspect_path_with`] for strict "must exist" semantics.
+///
+/// # Examples
+///
+/// Anything that converts to a `&Path` is accepted:
+///
+/// ```rust
+/// use std::path::PathBuf;
+/// use inspect_path::inspect_path;
+///
+/// let dir: PathBuf = std::env::temp_dir();
+/// let name: String = dir.to_string_lossy().into_owned();
+///
+/// let from_ref = inspect_path(&dir).unwrap();
+/// assert_eq!(inspect_path(dir.clone()).unwrap().kind(), from_ref.kind());
+/// assert_eq!(inspect_path(name.as_str()).unwrap().kind(), from_ref.kind());
+/// assert_eq!(inspect_path(name).unwrap().kind(), from_ref.kind());
+/// ```
+///
+/// # See also
+///
+/// - [`inspect_path_and_status`] — inspect and verify availability in one call
+///
+/// [`InspectOptions::require_existing`]: crate::InspectOptions::require_existing
+/// [`inspect_path_with`]: crate::inspect_path_with
+/// [`inspect_path_and_status`]: crate::inspect_path_and_status
+pub fn inspect_path(path: impl AsRef<Path>) -> Result<PathInfo, InspectPathError> {
+    crate::SystemInspector.inspect(path.as_ref())
+}
 
-    let miv = mountinfo_into_vec(&mountinfo_to_string()?)?;
-    let candidates: Vec<&MountInfo> = miv
-        .iter()
-        .filter(|m| {
-            if let Some(rp) = &resolved_path {
-                rp.starts_with(&m.mount_point)
-            } else {
-                path.starts_with(&m.mount_point)
-            }
-        })
-        .collect();
+/// Where the Linux backend reads mount and block device data from.
+///
+/// Defaults to the live system, with the mountinfo file overridable through
+/// the `INSPECT_PATH_MOUNTINFO` environment variable; tests point it at
+/// fixture files instead.
+#[derive(Debug, Clone)]
+pub(crate) struct Sources {
+    mountinfo: PathBuf,
+    sys: PathBuf,
+}
 
-    let best = candidates
-        .into_iter()
-        .max_by_key(|m| m.mount_point.components().count())
-        .ok_or(InspectPathError::ParseGen)?;
+impl Default for Sources {
+    fn default() -> Self {
+        Sources {
+            mountinfo: mountinfo_path(std::env::var_os(MOUNTINFO_ENV)),
+            sys: PathBuf::from(SYS_PATH),
+        }
+    }
+}
 
-    let kind = get_kind(best)?;
-    let remote_kind = if kind != PathType::Remote {
-        None
-    } else {
-        get_remote_kind(best)?
-    };
+fn mountinfo_path(env: Option<std::ffi::OsString>) -> PathBuf {
+    env.filter(|p| !p.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(MOUNTINFO_PATH))
+}
 
-    Ok(PathInfo {
-        path: path.to_path_buf(),
-        resolved_path,
-        is_symlink,
-        kind,
-        remote_kind,
-        status: PathStatus::Unknown,
+pub(crate) fn inspect_path_from(
+    path: &Path,
+    sources: &Sources,
+) -> Result<PathInfo, InspectPathError> {
+    inspect_path_recorded(path, sources, &mut Recorder::off())
+}
+
+/// [`inspect_path`] for [`inspect_report`](crate::inspect_report).
+pub(crate) fn inspect_recorded(
+    path: &Path,
+    rec: &mut Recorder,
+) -> Result<PathInfo, InspectPathError> {
+    inspect_path_recorded(path, &Sources::default(), rec)
+}
+
+fn inspect_path_recorded(
+    path: &Path,
+    sources: &Sources,
+    rec: &mut Recorder,
+) -> Result<PathInfo, InspectPathError> {
+    let (resolved_path, is_symlink) = get_resolved_path(path, rec);
+    inspect_resolved(path, resolved_path, is_symlink, sources, rec)
+}
+
+/// [`inspect_path`] with the symlink and mount table choices of `opts`.
+pub(crate) fn inspect_with(
+    path: &Path,
+    opts: &InspectOptions,
+) -> Result<PathInfo, InspectPathError> {
+    let sources = match &opts.mount_source {
+        Some(file) => Sources {
+            mountinfo: file.clone(),
+            ..Sources::default()
+        },
+        None => Sources::default(),
+    };
+    crate::probe::timed(path, || {
+        if opts.follow_symlinks {
+            inspect_path_from(path, &sources)
+        } else {
+            inspect_path_lexical_from(path, &sources)
+        }
     })
 }
 
-fn get_kind(best: &MountInfo) -> Result<PathType, InspectPathError> {
-    let removable_path = format!("/sys/dev/block/{}:0/removable", best.device_number.major);
-    let removable: u8 = fs::read_to_string(Path::new(&removable_path))
-        .unwrap_or_else(|_| "0".to_string())
-        .trim()
-        .parse()
-        .map_err(|e| InspectPathError::ParseInt(e))?;
-    let fs_type = best.fs_type.as_str();
+/// Inspects a path with its parent directories resolved but its final
+/// component left as it is, like `realpath -s` of the parent joined with the
+/// name.
+///
+/// Where [`inspect_path`] classifies the filesystem a symlink points into,
+/// this classifies the one the symlink itself lives on, while symlinks
+/// earlier in the path are still followed.
+/// [`resolved_path`](PathInfo::resolved_path) is the parent-resolved path and
+/// [`is_symlink`](PathInfo::is_symlink) still reports the final component.
+///
+/// On Windows, where the volume lookup resolves every link on the path, a
+/// final component that is a symlink or junction is classified by the
+/// directory holding it; a folder a volume is mounted on is not a link.
+///
+/// # Errors
+///
+/// Same as [`inspect_path`].
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use inspect_path::{inspect_path, inspect_path_lexical};
+///
+/// // ~/data -> /mnt/nfs/data
+/// let link = inspect_path_lexical("~/data").unwrap();
+/// let target = inspect_path("~/data").unwrap();
+/// assert!(link.is_symlink() && !link.is_remote());
+/// assert!(target.is_remote());
+/// ```
+pub fn inspect_path_lexical(path: impl AsRef<Path>) -> Result<PathInfo, InspectPathError> {
+    crate::inspect_path_with(path, &InspectOptions::new().follow_symlinks(false))
+}
 
-    if best.device_number.major == 0 {
-        Ok(PathType::Virtual(fs_type.into()))
-    } else if removable == 1 {
-        Ok(PathType::Removable)
-    } else if CDROM_FS_TYPES.contains(&fs_type) {
-        Ok(PathType::CDRom)
-    } else if REMOTE_FS_TYPES.iter().any(|fst| fst.contains(&fs_type)) {
-        Ok(PathType::Remote)
-    } else if fs_type.starts_with("fuse") {
-        Ok(PathType::Unknown)
-    } else if LOCAL_BLOCK_FS_TYPES.contains(&fs_type) {
-        Ok(PathType::Fixed)
-    } else {
-        Ok(PathType::Unknown)
-    }
+fn inspect_path_lexical_from(path: &Path, sources: &Sources) -> Result<PathInfo, InspectPathError> {
+    let mut rec = Recorder::off();
+    let (resolved_path, is_symlink) = get_parent_resolved_path(path, &mut rec);
+    inspect_resolved(path, resolved_path, is_symlink, sources, &mut rec)
 }
 
-fn get_remote_kind(best: &MountInfo) -> Result<Option<RemoteType>, InspectPathError> {
-    let fs_type = best.fs_type.as_str();
+/// Inspects a path and also returns every mount that covers it.
+///
+/// The first element is what [`inspect_path`] returns. The second lists a
+/// [`PathInfo`] for each mount whose mount point is a prefix of the path,
+/// ordered from shallowest (`/`) to deepest; the last one is the mount that
+/// was chosen. Mounts stacked on the same mount point appear in mount order.
+/// This is meant for debugging overlapping mounts.
+///
+/// # Errors
+///
+/// Returns an error if the mount table can't be read or no mount covers
+/// `path`.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::path::Path;
+/// use inspect_path::inspect_path_verbose;
+///
+/// let (info, candidates) = inspect_path_verbose(Path::new("/")).unwrap();
+/// assert_eq!(candidates.last(), Some(&info));
+/// ```
+pub fn inspect_path_verbose(
+    path: impl AsRef<Path>,
+) -> Result<(PathInfo, Vec<PathInfo>), InspectPathError> {
+    inspect_path_verbose_from(path.as_ref(), &Sources::default())
+}
 
-    if NFS.contains(&fs_type) {
-        Ok(Some(RemoteType::NFS))
-    } else if SMB.contains(&fs_type) {
-        Ok(Some(RemoteType::SMB))
-    } else if SSH.contains(&fs_type) {
-        Ok(Some(RemoteType::Other("SSH".into())))
-    } else if CLUSTER.contains(&fs_type) {
-        Ok(Some(RemoteType::Other("Cluster / distributed".into())))
-    } else if PROTOCOL.contains(&fs_type) {
-        Ok(Some(RemoteType::Other("Network / protocol FS".into())))
-    } else if OTHER.contains(&fs_type) {
-        Ok(Some(RemoteType::Other("Other".into())))
-    } else {
-        Ok(Some(RemoteType::Unknown))
-    }
+pub(crate) fn inspect_path_verbose_from(
+    path: &Path,
+    sources: &Sources,
+) -> Result<(PathInfo, Vec<PathInfo>), InspectPathError> {
+    let (resolved_path, is_symlink) = get_resolved_path(path, &mut Recorder::off());
+    let miv = load_mounts(sources)?;
+    let target = resolved_path.as_deref().unwrap_or(path);
+
+    let mut candidates: Vec<&MountInfo> = miv
+        .iter()
+        .filter(|m| target.starts_with(&m.mount_point))
+        .collect();
+    candidates.sort_by_key(|m| m.mount_point.components().count());
+
+    let infos = candidates
+        .into_iter()
+        .map(|m| {
+            let classified = m.classify_in(&sources.sys)?;
+            Ok(path_info(
+                path,
+                resolved_path.clone(),
+                is_symlink,
+                m,
+                classified,
+            ))
+        })
+        .collect::<Result<Vec<_>, InspectPathError>>()?;
+
+    let chosen = infos.last().cloned().ok_or_else(|| uncovered(path))?;
+    Ok((chosen, infos))
 }
 
-fn get_resolved_path(path: &Path) -> (Option<PathBuf>, bool) {
-    let s = path.to_string_lossy();
-    let mut expanded = path.to_path_buf();
+/// Inspects many paths against a single read of the mount table.
+///
+/// Equivalent to calling [`inspect_path`] on each path, but mountinfo is read
+/// and parsed once and each mount is classified once, which matters when
+/// classifying thousands of files. Results are in input order, and a failure
+/// for one path does not affect the others.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::path::Path;
+/// use inspect_path::inspect_paths;
+///
+/// let paths = [Path::new("/"), Path::new("/tmp")];
+/// for (path, info) in paths.iter().zip(inspect_paths(paths)) {
+///     println!("{}: {:?}", path.display(), info.map(|i| i.kind().clone()));
+/// }
+/// ```
+pub fn inspect_paths<P: AsRef<Path>>(
+    paths: impl IntoIterator<Item = P>,
+) -> Vec<Result<PathInfo, InspectPathError>> {
+    let paths: Vec<P> = paths.into_iter().collect();
+    let paths: Vec<&Path> = paths.iter().map(AsRef::as_ref).collect();
+    inspect_paths_from(&paths, &Sources::default())
+}
 
-    if s == "~" || s.starts_with("~/") {
-        if let Some(home) = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE")) {
-            expanded = PathBuf::from(home).join(s.trim_start_matches("~/"));
+pub(crate) fn inspect_paths_from(
+    paths: &[&Path],
+    sources: &Sources,
+) -> Vec<Result<PathInfo, InspectPathError>> {
+    let mut cache = SessionCache::default();
+    match load_mounts(sources) {
+        Ok(miv) => cache.mounts = Some(miv),
+        // let every path report the error itself
+        Err(_) => {
+            return paths
+                .iter()
+                .map(|path| inspect_path_from(path, sources))
+                .collect();
         }
     }
+    paths
+        .iter()
+        .map(|path| inspect_cached_from(path, &mut cache, sources))
+        .collect()
+}
 
-    let resolved = fs::canonicalize(&expanded).ok();
+/// What an [`InspectSession`](crate::InspectSession) remembers: one read of
+/// the mount table, and each mount's classification by mount id.
+#[derive(Debug, Default)]
+pub(crate) struct SessionCache {
+    mounts: Option<Vec<MountInfo>>,
+    classified: HashMap<u32, (PathType, Confidence, Option<RemoteType>)>,
+}
 
-    let is_symlink = fs::symlink_metadata(&expanded)
-        .map(|m| m.file_type().is_symlink())
-        .unwrap_or(false);
+/// [`inspect_path`] through `cache`, reading the mount table only if the
+/// cache has none yet.
+pub(crate) fn inspect_cached(
+    path: &Path,
+    cache: &mut SessionCache,
+) -> Result<PathInfo, InspectPathError> {
+    inspect_cached_from(path, cache, &Sources::default())
+}
 
-    (resolved, is_symlink)
+fn inspect_cached_from(
+    path: &Path,
+    cache: &mut SessionCache,
+    sources: &Sources,
+) -> Result<PathInfo, InspectPathError> {
+    let SessionCache { mounts, classified } = cache;
+    let miv = match mounts {
+        Some(miv) => miv,
+        None => mounts.insert(load_mounts(sources)?),
+    };
+
+    let (resolved_path, is_symlink) = get_resolved_path(path, &mut Recorder::off());
+    let best =
+        find_mount(miv, resolved_path.as_deref().unwrap_or(path)).ok_or_else(|| uncovered(path))?;
+    let class = match classified.get(&best.mount_id) {
+        Some(class) => class.clone(),
+        None => {
+            let class = best.classify_in(&sources.sys)?;
+            classified.insert(best.mount_id, class.clone());
+            class
+        }
+    };
+    Ok(path_info(path, resolved_path, is_symlink, best, class))
 }
 
-/// Probes a path to determine its current mount/connection status.
+/// Lists mounted volumes, each as a [`PathInfo`] whose path is its mount
+/// point.
 ///
-/// This function attempts to access filesystem metadata for the given path
-/// and classifies its availability based on the result.
+/// Mounts are returned in mount table order. Virtual filesystems are skipped
+/// unless [`VolumeFilter::include_virtual`] is set, and a mount of the same
+/// device and root as one already listed (a bind mount of the same tree) is
+/// skipped unless [`VolumeFilter::dedup_bind_mounts`] is turned off.
 ///
-/// It is primarily used to detect whether a remote or removable filesystem
-/// is currently reachable.
-///
-/// # Returns
+/// # Errors
 ///
-/// - [`PathStatus::Mounted`] — The path responded to metadata access
-/// - [`PathStatus::Disconnected`] — The path appears unavailable (typically
-///   network or device not connected) *(Windows only — see below)*
-/// - [`PathStatus::Unknown`] — Status could not be determined reliably
+/// Returns an error if the mount table can't be read or parsed.
 ///
-/// # Behavior
+/// # Examples
 ///
-/// This function performs a real filesystem probe using `std::fs::metadata`.
-/// On remote filesystems this may involve network I/O and can block for a
-/// noticeable amount of time if the target is unreachable.
+/// ```rust
+/// use inspect_path::{VolumeFilter, list_volumes};
 ///
-/// # Platform differences
+/// for volume in list_volumes(&VolumeFilter::new()).unwrap() {
+///     println!("{volume}");
+/// }
+/// ```
 ///
-/// ## Windows
+/// [`VolumeFilter::include_virtual`]: crate::VolumeFilter::include_virtual
+/// [`VolumeFilter::dedup_bind_mounts`]: crate::VolumeFilter::dedup_bind_mounts
+pub fn list_volumes(filter: &VolumeFilter) -> Result<Vec<PathInfo>, InspectPathError> {
+    list_volumes_from(filter, &Sources::default())
+}
+
+pub(crate) fn list_volumes_from(
+    filter: &VolumeFilter,
+    sources: &Sources,
+) -> Result<Vec<PathInfo>, InspectPathError> {
+    let miv = load_mounts(sources)?;
+    let mut seen = Vec::new();
+    let mut volumes = Vec::new();
+
+    for mount in &miv {
+        let classified = mount.classify_in(&sources.sys)?;
+        if matches!(classified.0, PathType::Virtual(_)) && !filter.include_virtual {
+            continue;
+        }
+        // the same device under another root is different data, e.g. a
+        // btrfs subvolume or a bind mount of a subdirectory
+        let key = (&mount.device_number, &mount.fs_root);
+        if filter.dedup_bind_mounts && seen.contains(&key) {
+            continue;
+        }
+
+        let point = mount.mount_point.as_path();
+        let info = path_info(point, Some(point.to_path_buf()), false, mount, classified);
+        if filter.matches(&info) {
+            seen.push(key);
+            volumes.push(info);
+        }
+    }
+    Ok(volumes)
+}
+
+/// A snapshot of the mount table, for answering many lexical questions
+/// without re-reading it.
 ///
-/// Error kinds are mapped to status:
+/// See [`is_network_path_with`](crate::is_network_path_with).
+#[derive(Debug, Clone)]
+pub struct MountTable {
+    mounts: Vec<MountInfo>,
+}
+
+impl MountTable {
+    /// Reads `/proc/self/mountinfo`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the mount table can't be read or parsed.
+    pub fn load() -> Result<Self, InspectPathError> {
+        Self::load_from(&Sources::default())
+    }
+
+    pub(crate) fn load_from(sources: &Sources) -> Result<Self, InspectPathError> {
+        let mounts = load_mounts(sources)?;
+        Ok(Self { mounts })
+    }
+
+    /// Whether the deepest mount covering `path` is a network filesystem.
+    /// Matches lexically: `path` is not resolved or touched.
+    pub(crate) fn is_network(&self, path: &Path) -> bool {
+        find_mount(&self.mounts, path).is_some_and(|m| remote::is_network_fs_type(&m.fs_type))
+    }
+
+    /// The mount point of the deepest mount covering `path`, matched
+    /// lexically.
+    pub(crate) fn mount_root(&self, path: &Path) -> Option<PathBuf> {
+        find_mount(&self.mounts, path).map(|m| m.mount_point.clone())
+    }
+}
+
+/// The mount whose id is `mount`'s parent id, as a [`PathInfo`] for its
+/// mount point. The parent is looked up in the table `mount` was read from.
+pub(crate) fn parent_mount(mount: &MountInfo) -> Result<Option<PathInfo>, InspectPathError> {
+    parent_mount_from(mount, &Sources::default())
+}
+
+/// [`parent_mount`], with `sources` for what `mount` doesn't record: its
+/// table if it was parsed on its own, and sysfs.
+fn parent_mount_from(
+    mount: &MountInfo,
+    sources: &Sources,
+) -> Result<Option<PathInfo>, InspectPathError> {
+    // the namespace root names a parent outside the table, or itself
+    if mount.parent_id == mount.mount_id {
+        return Ok(None);
+    }
+    let sources = match &mount.table.0 {
+        Some(table) => &Sources {
+            mountinfo: table.to_path_buf(),
+            sys: sources.sys.clone(),
+        },
+        None => sources,
+    };
+    let miv = load_mounts(sources)?;
+    let Some(parent) = miv.iter().find(|m| m.mount_id == mount.parent_id) else {
+        return Ok(None);
+    };
+    let classified = parent.classify_in(&sources.sys)?;
+    let point = parent.mount_point.as_path();
+    Ok(Some(path_info(
+        point,
+        Some(point.to_path_buf()),
+        false,
+        parent,
+        classified,
+    )))
+}
+
+/// Inspects a path as seen from the mount namespace of process `pid`.
 ///
-/// - `NotFound`, `TimedOut`, `NetworkDown`, `NotConnected` → Disconnected
-/// - `PermissionDenied` → Mounted (exists but access restricted)
-/// - Other errors → Unknown
+/// This reads `/proc/{pid}/mountinfo` instead of `/proc/self/mountinfo`, so a
+/// monitoring agent on the host can classify a path the way a container sees
+/// it, without entering the container's namespace.
 ///
-/// ## Unix
+/// `path` is interpreted inside that namespace and matched lexically against
+/// its mount table: symlinks are not followed, since they would resolve in
+/// the caller's namespace rather than the target's.
 ///
-/// Currently uses a simpler probe:
+/// Reading another process's mountinfo typically requires the same user or
+/// `CAP_SYS_PTRACE`.
 ///
-/// - Success → Mounted
-/// - Any error → Unknown
+/// # Errors
 ///
-/// (Future versions may distinguish disconnected network mounts more precisely.)
+/// Returns an error if the process doesn't exist, its mountinfo can't be
+/// read, or no mount covers `path`.
 ///
 /// # Examples
 ///
 /// ```rust,no_run
 /// use std::path::Path;
-/// use inspect_path::inspect_path;
-///
-/// let mut info = inspect_path(Path::new("/")).unwrap();
-/// info.check_status();
+/// use inspect_path::inspect_in_namespace;
 ///
-/// if info.is_status_mounted() {
-///     println!("Path is reachable");
-/// }
+/// let info = inspect_in_namespace(4242, Path::new("/data")).unwrap();
+/// println!("container sees /data as {info}");
 /// ```
-///
-/// # Notes
-///
-/// This is a heuristic check. Some filesystems may report as available even
-/// if later operations fail, and some virtual filesystems may always appear
-/// mounted.
-pub fn check_status(path: &Path) -> PathStatus {
-    match std::fs::metadata(path) {
-        Ok(_) => PathStatus::Mounted,
-        Err(_) => PathStatus::Unknown,
+#[cfg(target_os = "linux")]
+pub fn inspect_in_namespace(
+    pid: u32,
+    path: impl AsRef<Path>,
+) -> Result<PathInfo, InspectPathError> {
+    let sources = Sources {
+        mountinfo: PathBuf::from(format!("/proc/{pid}/mountinfo")),
+        ..Sources::default()
+    };
+    inspect_resolved(path.as_ref(), None, false, &sources, &mut Recorder::off())
+}
+
+fn inspect_resolved(
+    path: &Path,
+    resolved_path: Option<PathBuf>,
+    is_symlink: bool,
+    sources: &Sources,
+    rec: &mut Recorder,
+) -> Result<PathInfo, InspectPathError> {
+    rec.backend(backend_name(sources));
+    let miv = rec.time("read mount table", || load_mounts(sources))?;
+    let target = resolved_path.as_deref().unwrap_or(path);
+    let best = find_mount(&miv, target).ok_or_else(|| uncovered(path))?;
+
+    rec.raw("mountinfo line", &best.line);
+    rec.secret(best.block_device.to_string_lossy(), "<source>");
+    rec.secret(best.mount_point.to_string_lossy(), "<mount>");
+    rec.decide(format_args!(
+        "deepest mount covering {}: {} ({} candidates)",
+        target.display(),
+        best.mount_point.display(),
+        miv.iter()
+            .filter(|m| target.starts_with(&m.mount_point))
+            .count()
+    ));
+    #[cfg(target_os = "linux")]
+    if rec.is_enabled()
+        && let Some(magic) = rec.time("statfs", || statfs_magic(&best.mount_point))
+    {
+        rec.raw(
+            "statfs magic",
+            format_args!("{magic:#x} ({})", fs_type_from_magic(magic)),
+        );
+    }
+
+    let classified = best.classify_recorded(&sources.sys, rec)?;
+    Ok(path_info(path, resolved_path, is_symlink, best, classified))
+}
+
+/// Names the mount table [`load_mounts`] reads for `sources`.
+fn backend_name(sources: &Sources) -> &'static str {
+    if cfg!(any(
+        target_os = "freebsd",
+        target_os = "openbsd",
+        target_os = "macos"
+    )) && sources.mountinfo == Path::new(MOUNTINFO_PATH)
+    {
+        "getfsstat"
+    } else {
+        "mountinfo"
     }
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
-struct DeviceNumber {
-    major: u32,
-    minor: u32,
+fn path_info(
+    path: &Path,
+    resolved_path: Option<PathBuf>,
+    is_symlink: bool,
+    best: &MountInfo,
+    (kind, confidence, remote_kind): (PathType, Confidence, Option<RemoteType>),
+) -> PathInfo {
+    PathInfo {
+        path: path.to_path_buf(),
+        resolved_path,
+        is_symlink,
+        kind,
+        remote_kind,
+        status: PathStatus::Unknown,
+        confidence,
+        mount: Some(best.clone()),
+    }
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
-struct MountInfo {
-    mount_id: u32,
-    parent_id: u32,
-    device_number: DeviceNumber,
-    fs_root: PathBuf,
-    mount_point: PathBuf,
-    fs_type: String,
-    block_device: PathBuf,
-    mount_options: String,
+/// Returns the deepest mount whose mount point is a prefix of `path`.
+fn find_mount<'a>(miv: &'a [MountInfo], path: &Path) -> Option<&'a MountInfo> {
+    miv.iter()
+        .filter(|m| path.starts_with(&m.mount_point))
+        .max_by_key(|m| m.mount_point.components().count())
+}
+
+/// The error for a path outside every mount, named as the caller gave it.
+fn uncovered(path: &Path) -> InspectPathError {
+    InspectPathError::ParseGen {
+        path: path.to_path_buf(),
+        msg: "no mount point covers path",
+    }
 }
-fn mountinfo_to_string() -> Result<String, InspectPathError> {
-    let mountinfo_file = read_to_string(Path::new(MOUNTINFO_PATH))?;
-    Ok(mountinfo_file)
+
+/// Returns `true` if `descendant` lives on a different mount than `ancestor`.
+///
+/// This is the primitive behind `--one-file-system` style traversal: a
+/// recursive walker rooted at `ancestor` can stop descending once this
+/// returns `true`.
+///
+/// Both paths are resolved (following symlinks) and matched against the same
+/// snapshot of `/proc/self/mountinfo`. Paths that do not exist are resolved
+/// through their nearest existing ancestor. Bind mounts count as separate
+/// mounts even when they expose the same device.
+///
+/// # Errors
+///
+/// Returns an error if the mount table cannot be read or parsed.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::path::Path;
+/// use inspect_path::crosses_mount;
+///
+/// if crosses_mount(Path::new("/"), Path::new("/proc/self")).unwrap() {
+///     println!("/proc is a separate mount");
+/// }
+/// ```
+pub fn crosses_mount(
+    ancestor: impl AsRef<Path>,
+    descendant: impl AsRef<Path>,
+) -> Result<bool, InspectPathError> {
+    crosses_mount_from(ancestor.as_ref(), descendant.as_ref(), &Sources::default())
 }
 
-fn mountinfo_into_vec(s: &str) -> Result<Vec<MountInfo>, InspectPathError> {
-    let mut out: Vec<MountInfo> = Vec::new();
+pub(crate) fn crosses_mount_from(
+    ancestor: &Path,
+    descendant: &Path,
+    sources: &Sources,
+) -> Result<bool, InspectPathError> {
+    let miv = load_mounts(sources)?;
+    let (a, _) = get_resolved_path(ancestor, &mut Recorder::off());
+    let (d, _) = get_resolved_path(descendant, &mut Recorder::off());
 
-    for line in s.lines() {
-        let (pre, post) = line.split_once(" - ").ok_or(InspectPathError::ParseGen)?;
+    let a =
+        find_mount(&miv, a.as_deref().unwrap_or(ancestor)).ok_or_else(|| uncovered(ancestor))?;
+    let d = find_mount(&miv, d.as_deref().unwrap_or(descendant))
+        .ok_or_else(|| uncovered(descendant))?;
 
-        let mut vfs = pre.split_whitespace();
+    Ok(a.mount_id != d.mount_id)
+}
 
-        let mount_id: u32 = vfs.next().ok_or(InspectPathError::ParseGen)?.parse()?;
-        let parent_id: u32 = vfs.next().ok_or(InspectPathError::ParseGen)?.parse()?;
+/// Returns `true` if `a` and `b` are on the same filesystem, i.e. a rename
+/// between them can succeed without copying.
+///
+/// Compares the `st_dev` device number of both paths. A path that doesn't
+/// exist yet uses its nearest existing ancestor, so a file about to be created
+/// compares like the directory it will be created in.
+///
+/// Unlike [`crosses_mount`], this looks at devices rather than mounts: two
+/// bind mounts of the same filesystem count as the same filesystem. Btrfs
+/// subvolumes report distinct devices and count as different filesystems.
+///
+/// # Errors
+///
+/// Returns [`InspectPathError::Io`] if a path (or its ancestor) can't be
+/// stat'ed for a reason other than not existing.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::path::Path;
+/// use inspect_path::same_filesystem;
+///
+/// let tmp = std::env::temp_dir();
+/// assert!(same_filesystem(&tmp, &tmp.join("not-yet-created")).unwrap());
+/// ```
+pub fn same_filesystem(a: impl AsRef<Path>, b: impl AsRef<Path>) -> Result<bool, InspectPathError> {
+    Ok(device_of(a.as_ref())? == device_of(b.as_ref())?)
+}
 
-        let (major, minor) = vfs
-            .next()
-            .ok_or(InspectPathError::ParseGen)?
-            .split_once(":")
-            .ok_or(InspectPathError::ParseGen)?;
+fn device_of(path: &Path) -> Result<u64, InspectPathError> {
+    use std::{io::ErrorKind, os::unix::fs::MetadataExt};
 
-        let device_number: DeviceNumber = DeviceNumber {
-            major: major.parse()?,
-            minor: minor.parse()?,
+    for ancestor in path.ancestors() {
+        let current = if ancestor.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            ancestor
         };
+        match fs::metadata(current) {
+            Ok(meta) => return Ok(meta.dev()),
+            Err(e) if e.kind() == ErrorKind::NotFound => continue,
+            Err(source) => {
+                return Err(InspectPathError::Io {
+                    path: current.to_path_buf(),
+                    source,
+                });
+            }
+        }
+    }
+    Err(InspectPathError::InvalidPath(path.display().to_string()))
+}
 
-        let fs_root: PathBuf = vfs.next().ok_or(InspectPathError::ParseGen)?.into();
-        let mount_point: PathBuf = vfs.next().ok_or(InspectPathError::ParseGen)?.into();
-        // rest of vfs not parsed
+fn get_kind(
+    best: &MountInfo,
+    sys: &Path,
+    rec: &mut Recorder,
+) -> Result<(PathType, Confidence), InspectPathError> {
+    let fs_type = best.fs_type.as_str();
 
-        let mut fs = post.split_whitespace();
+    // network filesystems also use anonymous (major 0) device numbers,
+    // so they have to be matched before the virtual fallback
+    let (kind, confidence, reason) = if remote::is_network_fs_type(fs_type) {
+        (
+            PathType::Remote,
+            Confidence::High,
+            "network filesystem type",
+        )
+    } else if fs_type == "tmpfs"
+        && SYSTEM_TMPFS_PATHS
+            .iter()
+            .any(|p| best.mount_point.starts_with(p))
+    {
+        let kind = PathType::Virtual(fs_type.into());
+        (kind, Confidence::High, "tmpfs under a system path")
+    } else if fs_type == "tmpfs" {
+        // like a Windows RAM disk: writable storage that is gone on reboot,
+        // which is what callers deciding where to keep files need to know
+        (PathType::RamDisk, Confidence::High, "tmpfs")
+    } else if VIRTUAL_FS_TYPES.contains(&fs_type) {
+        let kind = PathType::Virtual(fs_type.into());
+        (kind, Confidence::High, "virtual filesystem type")
+    } else if best.device_number.major == 0
+        && !cfg!(any(
+            target_os = "freebsd",
+            target_os = "openbsd",
+            target_os = "macos"
+        ))
+    {
+        let kind = PathType::Virtual(fs_type.into());
+        (kind, Confidence::Medium, "anonymous device (major 0)")
+    } else if removable_flag(best, sys, rec)? == 1 {
+        (
+            PathType::Removable,
+            Confidence::High,
+            "sysfs removable flag",
+        )
+    } else if CDROM_FS_TYPES.contains(&fs_type) {
+        (PathType::CDRom, Confidence::High, "optical filesystem type")
+    } else if fs_type.starts_with("fuse") {
+        (PathType::Unknown, Confidence::Low, "unrecognized FUSE type")
+    } else if fs_type == "ntfs" || fs_type == "ntfs3" {
+        // usually an external or dual-boot disk whose backing device is ambiguous
+        (
+            PathType::Fixed,
+            Confidence::Medium,
+            "NTFS on a block device",
+        )
+    } else if LOCAL_BLOCK_FS_TYPES.contains(&fs_type) {
+        (
+            PathType::Fixed,
+            Confidence::High,
+            "local block filesystem type",
+        )
+    } else {
+        (PathType::Unknown, Confidence::Low, "no rule matched")
+    };
 
-        let fs_type: String = fs.next().ok_or(InspectPathError::ParseGen)?.into();
-        let block_device: PathBuf = fs.next().ok_or(InspectPathError::ParseGen)?.into();
-        let mount_options: String = fs.next().ok_or(InspectPathError::ParseGen)?.into();
+    rec.decide(format_args!(
+        "{fs_type} on {}: {reason} -> {kind:?} ({confidence:?})",
+        best.mount_point.display()
+    ));
+    Ok((kind, confidence))
+}
 
-        let value = MountInfo {
-            mount_id,
-            parent_id,
-            device_number,
-            fs_root,
-            mount_point,
-            fs_type,
-            block_device,
-            mount_options,
-        };
-        out.push(value);
+/// Reads the sysfs `removable` flag of the disk behind `best`.
+///
+/// Only block devices have one, so [`get_kind`] asks after the rules that
+/// settle virtual and network mounts from the mount table entry alone. With
+/// the table cached by [`inspect_paths`] or an
+/// [`InspectSession`](crate::InspectSession), scanning `/proc` or `/sys`
+/// then costs no reads beyond resolving each path.
+fn removable_flag(
+    best: &MountInfo,
+    sys: &Path,
+    rec: &mut Recorder,
+) -> Result<u8, InspectPathError> {
+    let DeviceNumber { major, minor } = best.device_number;
+    let device = sys.join(format!("dev/block/{major}:{minor}"));
+    // a partition has no flag of its own; it lives on its parent disk
+    let removable_path = [device.join("removable"), device.join("../removable")]
+        .into_iter()
+        .find(|p| p.is_file())
+        .unwrap_or_else(|| device.join("removable"));
+    let removable = rec.time("read removable", || fs::read_to_string(&removable_path));
+    if let Ok(value) = &removable {
+        rec.raw("removable", value.trim());
     }
-    Ok(out)
+    removable
+        .unwrap_or_else(|_| "0".to_string())
+        .trim()
+        .parse()
+        .map_err(|source| InspectPathError::ParseInt {
+            path: removable_path,
+            source,
+        })
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+fn get_remote_kind(best: &MountInfo) -> Result<Option<RemoteType>, InspectPathError> {
+    Ok(Some(
+        remote::from_fs_type(&best.fs_type).unwrap_or(RemoteType::Unknown),
+    ))
+}
 
-    #[test]
-    fn mountinfo_to_vec_virtual() {
-        let line =
-            "40 28 0:20 / /dev/mqueue rw,nosuid,nodev,noexec,relatime shared:15 - mqueue mqueue rw";
-        let right = mountinfo_into_vec(line).unwrap();
+fn get_resolved_path(path: &Path, rec: &mut Recorder) -> (Option<PathBuf>, bool) {
+    let expanded = expand_tilde(path);
+    if expanded != path {
+        rec.decide(format_args!("expanded ~ to {}", expanded.display()));
+    }
 
-        let device_number = DeviceNumber {
-            major: 0,
-            minor: 20,
-        };
-        let left = vec![MountInfo {
-            mount_id: 40,
-            parent_id: 28,
-            device_number,
-            fs_root: PathBuf::from("/"),
-            mount_point: PathBuf::from("/dev/mqueue"),
-            fs_type: String::from("mqueue"),
-            block_device: PathBuf::from("mqueue"),
-            mount_options: String::from("rw"),
+    let resolved = match rec.time("canonicalize", || fs::canonicalize(&expanded)) {
+        Ok(resolved) => Some(resolved),
+        Err(e) => {
+            rec.decide(format_args!(
+                "canonicalize failed ({e}), resolving the nearest existing ancestor"
+            ));
+            resolve_existing_ancestor(&expanded)
+        }
+    };
+    rec.resolved(resolved.as_deref());
+
+    let is_symlink = rec
+        .time("symlink_metadata", || fs::symlink_metadata(&expanded))
+        .map(|m| m.file_type().is_symlink())
+        .unwrap_or(false);
+
+    (resolved, is_symlink)
+}
+
+/// Like [`get_resolved_path`], but leaves the final component unresolved.
+fn get_parent_resolved_path(path: &Path, rec: &mut Recorder) -> (Option<PathBuf>, bool) {
+    let expanded = expand_tilde(path);
+    let resolved = match expanded.file_name() {
+        // `/` or a trailing `..` has no name to leave alone
+        None => fs::canonicalize(&expanded).ok(),
+        // the parent is the nearest ancestor, or the one that exists
+        Some(_) => resolve_existing_ancestor(&expanded),
+    };
+    rec.resolved(resolved.as_deref());
+
+    let is_symlink = fs::symlink_metadata(&expanded).is_ok_and(|m| m.file_type().is_symlink());
+    (resolved, is_symlink)
+}
+
+/// Canonicalizes the nearest existing ancestor of a path that doesn't exist
+/// yet and re-attaches the missing tail, so a to-be-created file is matched
+/// against the filesystem it will land on.
+fn resolve_existing_ancestor(path: &Path) -> Option<PathBuf> {
+    path.ancestors().skip(1).find_map(|ancestor| {
+        let existing = if ancestor.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            ancestor
+        };
+        let base = fs::canonicalize(existing).ok()?;
+        Some(base.join(path.strip_prefix(ancestor).ok()?))
+    })
+}
+
+/// Probes a path like [`check_status`](crate::check_status), but reports
+/// malformed input as an error.
+///
+/// Every I/O outcome maps to a [`PathStatus`]; an error is returned only for
+/// programmer-level problems such as an empty path or one containing a NUL
+/// byte.
+///
+/// # Errors
+///
+/// Returns [`InspectPathError::InvalidPath`] if the path cannot be passed to
+/// the OS.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::path::Path;
+/// use inspect_path::{PathStatus, probe_status};
+///
+/// match probe_status(Path::new("/mnt/nfs"))? {
+///     PathStatus::Stale => println!("remount needed"),
+///     status => println!("{status}"),
+/// }
+/// # Ok::<(), inspect_path::InspectPathError>(())
+/// ```
+pub fn probe_status(path: impl AsRef<Path>) -> Result<PathStatus, InspectPathError> {
+    super::probe_with(path.as_ref(), ERRNO_STATUS)
+}
+
+/// Probes a path using `statx` with `AT_STATX_DONT_SYNC`.
+///
+/// This is a cheaper variant of [`check_status`](crate::check_status) for network filesystems:
+/// the kernel is allowed to answer from cached attributes instead of forcing
+/// a round-trip to the server. The trade-off is freshness — a share that went
+/// away moments ago may still report as [`PathStatus::Mounted`] until its
+/// attribute cache expires.
+///
+/// Failures map to a status the same way as in
+/// [`check_status`](crate::check_status), which it falls back to on kernels
+/// without `statx` (before 4.11).
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::path::Path;
+/// use inspect_path::check_status_fast;
+///
+/// if check_status_fast(Path::new("/mnt/nfs")) == inspect_path::PathStatus::Mounted {
+///     println!("NFS mount looks alive");
+/// }
+/// ```
+#[cfg(target_os = "linux")]
+pub fn check_status_fast(path: impl AsRef<Path>) -> PathStatus {
+    check_status_fast_inner(path.as_ref())
+}
+
+#[cfg(target_os = "linux")]
+fn check_status_fast_inner(path: &Path) -> PathStatus {
+    use std::{ffi::CString, mem::MaybeUninit, os::unix::ffi::OsStrExt};
+
+    let Ok(c_path) = CString::new(path.as_os_str().as_bytes()) else {
+        return PathStatus::Unknown;
+    };
+    let mut buf = MaybeUninit::<libc::statx>::zeroed();
+
+    let result = crate::probe::timed(path, || {
+        let rc = unsafe {
+            libc::statx(
+                libc::AT_FDCWD,
+                c_path.as_ptr(),
+                libc::AT_STATX_DONT_SYNC,
+                libc::STATX_TYPE,
+                buf.as_mut_ptr(),
+            )
+        };
+        // read errno before a slow probe handler gets to run
+        if rc == 0 {
+            Ok(())
+        } else {
+            Err(std::io::Error::last_os_error())
+        }
+    });
+
+    match result {
+        Ok(()) => PathStatus::Mounted,
+        Err(e) if e.raw_os_error() == Some(libc::ENOSYS) => {
+            probe_status(path).unwrap_or(PathStatus::Unknown)
+        }
+        Err(e) => super::status_from_error(&e, ERRNO_STATUS),
+    }
+}
+
+#[cfg(target_os = "linux")]
+// f_type is i64 or i32 depending on the target
+#[allow(clippy::unnecessary_cast)]
+fn statfs_magic(path: &Path) -> Option<u32> {
+    use std::{ffi::CString, mem::MaybeUninit, os::unix::ffi::OsStrExt};
+
+    let c_path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut buf = MaybeUninit::<libc::statfs>::zeroed();
+    if unsafe { libc::statfs(c_path.as_ptr(), buf.as_mut_ptr()) } != 0 {
+        return None;
+    }
+    let magic = unsafe { buf.assume_init() }.f_type as i64;
+    Some(magic as u32)
+}
+
+// from linux/magic.h; ext2, ext3 and ext4 share a magic
+#[cfg(target_os = "linux")]
+const FS_MAGIC: &[(u32, &str)] = &[
+    (0xef53, "ext4"),
+    (0x9123_683e, "btrfs"),
+    (0x5846_5342, "xfs"),
+    (0x2fc1_2fc1, "zfs"),
+    (0xf2f5_2010, "f2fs"),
+    (0x4d44, "vfat"),
+    (0x2011_bab0, "exfat"),
+    (0x5346_544e, "ntfs"),
+    (0x9660, "iso9660"),
+    (0x1501_3346, "udf"),
+    (0x0102_1994, "tmpfs"),
+    (0x8584_58f6, "ramfs"),
+    (0x794c_7630, "overlay"),
+    (0x7371_7368, "squashfs"),
+    (0x6573_5546, "fuse"),
+    (0x6969, "nfs"),
+    (0xff53_4d42, "cifs"),
+    (0xfe53_4d42, "smb2"),
+    (0x00c3_6400, "ceph"),
+    (0x0102_1997, "9p"),
+    (0x9fa0, "proc"),
+    (0x6265_6572, "sysfs"),
+    (0x1373, "devfs"),
+    (0x1cd1, "devpts"),
+    (0x0027_e0eb, "cgroup"),
+    (0x6367_7270, "cgroup2"),
+    (0x7472_6163, "tracefs"),
+    (0x6462_6720, "debugfs"),
+    (0xcafe_4a11, "bpf"),
+    (0x7363_6673, "securityfs"),
+];
+
+/// Maps a `statfs` magic number to a filesystem name, or `unknown(0x...)`.
+#[cfg(target_os = "linux")]
+fn fs_type_from_magic(magic: u32) -> String {
+    FS_MAGIC
+        .iter()
+        .find(|(m, _)| *m == magic)
+        .map(|(_, name)| (*name).to_string())
+        .unwrap_or_else(|| format!("unknown({magic:#x})"))
+}
+
+/// Wakes the mount watcher thread so it exits.
+#[cfg(target_os = "linux")]
+#[derive(Debug)]
+pub(crate) struct MountWatchStop(std::os::fd::OwnedFd);
+
+#[cfg(target_os = "linux")]
+impl MountWatchStop {
+    pub(crate) fn stop(&self) {
+        use std::os::fd::AsRawFd;
+
+        let byte = 1u8;
+        unsafe { libc::write(self.0.as_raw_fd(), (&raw const byte).cast(), 1) };
+    }
+}
+
+/// Spawns a thread that diffs the mount table each time the kernel flags
+/// mountinfo with `POLLPRI`.
+#[cfg(target_os = "linux")]
+pub(crate) fn watch_mounts(
+    tx: std::sync::mpsc::Sender<crate::MountEvent>,
+) -> Result<(MountWatchStop, std::thread::JoinHandle<()>), InspectPathError> {
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+
+    let sources = Sources::default();
+    let mountinfo = sources.mountinfo;
+    let io_error = |source| InspectPathError::Io {
+        path: mountinfo.clone(),
+        source,
+    };
+
+    // the kernel tracks which table version each open file has seen, so the
+    // same file must be re-read to clear the event
+    let mut file = fs::File::open(&mountinfo).map_err(io_error)?;
+    let mut current = read_mounts(&mut file, &mountinfo)?;
+
+    let mut fds = [0; 2];
+    if unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC) } != 0 {
+        return Err(InspectPathError::Sys {
+            path: mountinfo,
+            errno: std::io::Error::last_os_error().raw_os_error().unwrap_or(0),
+            context: "pipe2",
+        });
+    }
+    let (wake, stop) = unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) };
+
+    let thread_mountinfo = mountinfo.clone();
+    let handle = std::thread::Builder::new()
+        .name("inspect_path-mounts".into())
+        .spawn(move || {
+            let mut pfds = [
+                libc::pollfd {
+                    fd: file.as_raw_fd(),
+                    events: libc::POLLPRI,
+                    revents: 0,
+                },
+                libc::pollfd {
+                    fd: wake.as_raw_fd(),
+                    events: libc::POLLIN,
+                    revents: 0,
+                },
+            ];
+            loop {
+                if unsafe { libc::poll(pfds.as_mut_ptr(), 2, -1) } < 0 {
+                    if std::io::Error::last_os_error().kind() == std::io::ErrorKind::Interrupted {
+                        continue;
+                    }
+                    return;
+                }
+                if pfds[1].revents != 0 {
+                    return;
+                }
+                if pfds[0].revents & (libc::POLLPRI | libc::POLLERR) == 0 {
+                    continue;
+                }
+
+                let Ok(next) = read_mounts(&mut file, &thread_mountinfo) else {
+                    return;
+                };
+                for event in diff_mounts(&current, &next) {
+                    if tx.send(event).is_err() {
+                        return;
+                    }
+                }
+                current = next;
+            }
+        })
+        .map_err(io_error)?;
+
+    Ok((MountWatchStop(stop), handle))
+}
+
+#[cfg(target_os = "linux")]
+fn read_mounts(file: &mut fs::File, path: &Path) -> Result<Vec<MountInfo>, InspectPathError> {
+    use std::io::{Seek, SeekFrom};
+
+    file.seek(SeekFrom::Start(0))
+        .map_err(|source| InspectPathError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+    mounts_from(BufReader::new(file), path.to_path_buf()).collect()
+}
+
+/// Compares two mount tables by mount id: removals first, then additions and
+/// changes in the order of `new`.
+#[cfg(target_os = "linux")]
+fn diff_mounts(old: &[MountInfo], new: &[MountInfo]) -> Vec<crate::MountEvent> {
+    use crate::{MountEvent, MountEventKind};
+
+    let event = |kind, m: &MountInfo| MountEvent {
+        kind,
+        mount_point: m.mount_point.clone(),
+        fs_type: Some(m.fs_type.clone()),
+    };
+
+    let removed = old
+        .iter()
+        .filter(|m| !new.iter().any(|n| n.mount_id == m.mount_id))
+        .map(|m| event(MountEventKind::Removed, m));
+    let added_or_changed =
+        new.iter()
+            .filter_map(|m| match old.iter().find(|o| o.mount_id == m.mount_id) {
+                None => Some(event(MountEventKind::Added, m)),
+                Some(prev) if prev != m => Some(event(MountEventKind::Changed, m)),
+                Some(_) => None,
+            });
+    removed.chain(added_or_changed).collect()
+}
+
+/// Queries the size and free space of the filesystem at `path` with `statvfs`.
+// the statvfs field widths vary by platform
+#[allow(clippy::unnecessary_cast)]
+pub(crate) fn space_info(path: &Path) -> Result<SpaceInfo, InspectPathError> {
+    let stat = statvfs(path)?;
+    let block = stat.f_frsize as u64;
+    Ok(SpaceInfo {
+        total: stat.f_blocks as u64 * block,
+        free: stat.f_bfree as u64 * block,
+        available: stat.f_bavail as u64 * block,
+    })
+}
+
+/// Returns the calling user's block quota on the filesystem behind `mount`.
+#[cfg(target_os = "linux")]
+pub(crate) fn quota(mount: &MountInfo) -> Option<crate::Quota> {
+    use std::{ffi::CString, os::unix::ffi::OsStrExt};
+    // from <sys/quota.h>
+    const USRQUOTA: libc::c_int = 0;
+
+    let special = CString::new(mount.block_device.as_os_str().as_bytes()).ok()?;
+    let mut dq = unsafe { std::mem::zeroed::<libc::dqblk>() };
+    let result = unsafe {
+        libc::quotactl(
+            libc::QCMD(libc::Q_GETQUOTA, USRQUOTA),
+            special.as_ptr(),
+            libc::getuid() as libc::c_int,
+            &mut dq as *mut libc::dqblk as *mut libc::c_char,
+        )
+    };
+    // ESRCH when quotas are off, ENOTBLK for a source that isn't a device
+    if result != 0 {
+        return None;
+    }
+    quota_from(&dq)
+}
+
+#[cfg(target_os = "linux")]
+fn quota_from(dq: &libc::dqblk) -> Option<crate::Quota> {
+    // block limits count QIF_DQBLKSIZE (1 KiB) units; curspace is in bytes
+    const QIF_DQBLKSIZE: u64 = 1024;
+
+    let wanted = libc::QIF_BLIMITS | libc::QIF_SPACE;
+    if dq.dqb_valid & wanted != wanted {
+        return None;
+    }
+    let blocks = match dq.dqb_bhardlimit {
+        0 => dq.dqb_bsoftlimit,
+        hard => hard,
+    };
+    (blocks > 0).then(|| crate::Quota {
+        used: dq.dqb_curspace,
+        limit: blocks * QIF_DQBLKSIZE,
+    })
+}
+
+/// Returns the longest file name, in bytes, the filesystem at `path` accepts.
+#[allow(clippy::unnecessary_cast)]
+pub(crate) fn max_filename_length(path: &Path) -> Option<u64> {
+    Some(statvfs(path).ok()?.f_namemax as u64).filter(|&n| n > 0)
+}
+
+fn statvfs(path: &Path) -> Result<libc::statvfs, InspectPathError> {
+    use std::{ffi::CString, mem::MaybeUninit, os::unix::ffi::OsStrExt};
+
+    let c_path = CString::new(path.as_os_str().as_bytes())
+        .map_err(|_| InspectPathError::InvalidPath(path.display().to_string()))?;
+    let mut buf = MaybeUninit::<libc::statvfs>::zeroed();
+
+    crate::probe::timed(path, || {
+        let rc = unsafe { libc::statvfs(c_path.as_ptr(), buf.as_mut_ptr()) };
+        // read errno before a slow probe handler gets to run
+        if rc == 0 {
+            Ok(())
+        } else {
+            Err(std::io::Error::last_os_error())
+        }
+    })
+    .map_err(|e| super::space_error(path, e, ERRNO_STATUS))?;
+    Ok(unsafe { buf.assume_init() })
+}
+
+pub(crate) fn capabilities() -> Capabilities {
+    let linux = cfg!(target_os = "linux");
+    Capabilities {
+        can_mount: cfg!(feature = "mount"),
+        // BSDs have no mountinfo unless a fixture is configured
+        has_mountinfo: Sources::default().mountinfo.is_file(),
+        has_device_numbers: true,
+        has_volume_labels: Path::new(BY_LABEL_PATH).is_dir(),
+        can_watch_mounts: linux,
+        has_quotas: linux,
+    }
+}
+
+/// Returns the filesystem label of the device behind `mount`, as published
+/// by udev under `/dev/disk/by-label`.
+pub(crate) fn volume_label(mount: &MountInfo) -> Option<String> {
+    device_link_in(mount, Path::new(BY_LABEL_PATH))
+}
+
+/// Returns the filesystem UUID of the device behind `mount`, as published by
+/// udev under `/dev/disk/by-uuid`.
+pub(crate) fn filesystem_uuid(mount: &MountInfo) -> Option<String> {
+    device_link_in(mount, Path::new(BY_UUID_PATH))
+}
+
+/// Returns the name of the udev symlink in `dir` (`by-label`, `by-uuid`)
+/// that points at the block device behind `mount`.
+fn device_link_in(mount: &MountInfo, dir: &Path) -> Option<String> {
+    // virtual and network sources (`proc`, `srv:/export`) aren't devices
+    if !mount.block_device.is_absolute() {
+        return None;
+    }
+    let device = fs::canonicalize(&mount.block_device).ok()?;
+
+    fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .find(|entry| fs::canonicalize(entry.path()).is_ok_and(|target| target == device))
+        .map(|entry| unescape_udev(&entry.file_name().to_string_lossy()))
+}
+
+/// Returns the (logical, physical) sector sizes of the block device behind
+/// `mount` from sysfs.
+pub(crate) fn sector_sizes(mount: &MountInfo) -> Option<(u32, u32)> {
+    sector_sizes_in(mount, Path::new(SYS_PATH))
+}
+
+fn sector_sizes_in(mount: &MountInfo, sys: &Path) -> Option<(u32, u32)> {
+    let DeviceNumber { major, minor } = mount.device_number;
+    let device = sys.join(format!("dev/block/{major}:{minor}"));
+    // a partition's queue settings live on its parent disk
+    let queue = [device.join("queue"), device.join("../queue")]
+        .into_iter()
+        .find(|q| q.is_dir())?;
+    let read = |name: &str| -> Option<u32> {
+        fs::read_to_string(queue.join(name))
+            .ok()?
+            .trim()
+            .parse()
+            .ok()
+    };
+    Some((read("logical_block_size")?, read("physical_block_size")?))
+}
+
+/// Whether `mount` is backed by an image file rather than a disk.
+///
+/// On Linux, loop devices and their partitions have a `loop` directory in
+/// sysfs. On FreeBSD and OpenBSD, memory disks (`md`) and vnode disks (`vnd`)
+/// are recognized by their device name. On macOS, `diskutil info` reports
+/// `Disk Image` as the protocol of disks attached with `hdiutil`.
+pub(crate) fn is_disk_image(mount: &MountInfo) -> Option<bool> {
+    is_disk_image_in(mount, Path::new(SYS_PATH))
+}
+
+fn is_disk_image_in(mount: &MountInfo, sys: &Path) -> Option<bool> {
+    if cfg!(target_os = "macos") {
+        return diskutil_is_image(&mount.block_device);
+    }
+    if cfg!(any(target_os = "freebsd", target_os = "openbsd")) {
+        let name = mount.block_device.strip_prefix("/dev").ok()?.to_str()?;
+        return Some(name.starts_with("md") || name.starts_with("vnd"));
+    }
+
+    let DeviceNumber { major, minor } = mount.device_number;
+    let device = sys.join(format!("dev/block/{major}:{minor}"));
+    if device.is_dir() {
+        // a partition's loop attributes live on its parent device
+        Some(device.join("loop").is_dir() || device.join("../loop").is_dir())
+    } else if major == 0 {
+        // no block device: virtual or network
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// Runs `diskutil info` on `device`; anything not under `/dev` (a network
+/// share, `devfs`) has no disk to ask about.
+fn diskutil_is_image(device: &Path) -> Option<bool> {
+    if !device.starts_with("/dev") {
+        return Some(false);
+    }
+    let output = std::process::Command::new("diskutil")
+        .arg("info")
+        .arg(device)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    diskutil_protocol(&String::from_utf8_lossy(&output.stdout)).map(|p| p == "Disk Image")
+}
+
+/// The `Protocol:` field of `diskutil info` output.
+fn diskutil_protocol(info: &str) -> Option<&str> {
+    info.lines()
+        .find_map(|line| line.trim().strip_prefix("Protocol:"))
+        .map(str::trim)
+}
+
+/// Whether the disk behind `mount` is an iSCSI LUN, i.e. network-backed.
+///
+/// On Linux, the SCSI host the disk hangs off is looked up in its sysfs device
+/// path: the disk is iSCSI if the host has a session under
+/// `class/iscsi_session`, or its driver's `proc_name` starts with `iscsi`
+/// (`iscsi_tcp`, `iscsi_iser`). A device-mapper or md device (multipath,
+/// LVM, RAID) is iSCSI if any device under its `slaves/` is, followed down
+/// through stacked layers. Always `None` elsewhere.
+pub(crate) fn is_iscsi(mount: &MountInfo) -> Option<bool> {
+    is_iscsi_in(mount, Path::new(SYS_PATH))
+}
+
+fn is_iscsi_in(mount: &MountInfo, sys: &Path) -> Option<bool> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+
+    let DeviceNumber { major, minor } = mount.device_number;
+    let Ok(device) = fs::canonicalize(sys.join(format!("dev/block/{major}:{minor}"))) else {
+        // no block device: virtual or network
+        return (major == 0).then_some(false);
+    };
+    Some(device_is_iscsi(&device, sys, 0))
+}
+
+// deeper stacks than dm on md on dm... are a loop in a broken sysfs
+const MAX_SLAVE_DEPTH: usize = 8;
+
+/// [`is_iscsi_in`] for the sysfs directory of a block device, descending
+/// into the devices under `slaves/`.
+fn device_is_iscsi(device: &Path, sys: &Path, depth: usize) -> bool {
+    // e.g. devices/platform/host3/session1/target3:0:0/3:0:0:0/block/sdb/sdb1
+    let names: Vec<_> = device.iter().filter_map(|c| c.to_str()).collect();
+    let session = names.iter().any(|name| {
+        name.strip_prefix("session")
+            .is_some_and(|n| n.parse::<u32>().is_ok())
+            && sys.join("class/iscsi_session").join(name).exists()
+    });
+    let driver = names
+        .iter()
+        .filter(|name| {
+            name.strip_prefix("host")
+                .is_some_and(|n| n.parse::<u32>().is_ok())
+        })
+        .filter_map(|host| {
+            fs::read_to_string(sys.join("class/scsi_host").join(host).join("proc_name")).ok()
+        })
+        .any(|proc_name| proc_name.trim().starts_with("iscsi"));
+    if session || driver {
+        return true;
+    }
+    if depth == MAX_SLAVE_DEPTH {
+        return false;
+    }
+    // a partition's slaves are listed on its disk, e.g. md0 for md0p1
+    let disk = match device.parent() {
+        Some(disk) if device.join("partition").exists() => disk,
+        _ => device,
+    };
+    let Ok(slaves) = fs::read_dir(disk.join("slaves")) else {
+        return false;
+    };
+    slaves.flatten().any(|slave| {
+        fs::canonicalize(slave.path()).is_ok_and(|slave| device_is_iscsi(&slave, sys, depth + 1))
+    })
+}
+
+/// Where distributions mount the boot and EFI System partitions.
+const BOOT_MOUNT_POINTS: &[&str] = &["/boot", "/boot/efi", "/efi"];
+
+/// Whether `mount` is the boot partition or an EFI System Partition: mounted
+/// at `/boot`, `/boot/efi` or `/efi`, or a FAT filesystem somewhere below
+/// `/boot` or `/efi` (e.g. `/boot/efi/EFI` on some layouts).
+pub(crate) fn is_boot_partition(mount: &MountInfo) -> bool {
+    let point = mount.mount_point.as_path();
+    BOOT_MOUNT_POINTS.iter().any(|p| point == Path::new(p))
+        || (mount.fs_type == "vfat" && (point.starts_with("/boot") || point.starts_with("/efi")))
+}
+
+/// Whether names on `mount` are case-sensitive at `path`.
+///
+/// Local formats report their default; on filesystems supporting per-directory
+/// case folding the nearest existing directory of `path` is checked too.
+/// Network and virtual filesystems give `None`: it depends on the server.
+pub(crate) fn is_case_sensitive(mount: &MountInfo, path: &Path) -> Option<bool> {
+    let fs_type = mount.fs_type.as_str();
+    if CASE_INSENSITIVE_FS_TYPES.contains(&fs_type) {
+        return Some(false);
+    }
+    if !LOCAL_BLOCK_FS_TYPES.contains(&fs_type) && !CASEFOLD_FS_TYPES.contains(&fs_type) {
+        return None;
+    }
+    #[cfg(target_os = "linux")]
+    if CASEFOLD_FS_TYPES.contains(&fs_type) && is_casefolded(path) {
+        return Some(false);
+    }
+    #[cfg(not(target_os = "linux"))]
+    let _ = path;
+    Some(true)
+}
+
+/// Whether `mount` supports `(hard links, symbolic links)`, from its type.
+///
+/// Native Unix and NTFS formats support both, FAT-family formats neither.
+/// Network filesystems other than NFS depend on the server and give `None`.
+pub(crate) fn link_support(mount: &MountInfo) -> Option<(bool, bool)> {
+    let fs_type = mount.fs_type.as_str();
+    if NO_LINK_FS_TYPES.contains(&fs_type) {
+        Some((false, false))
+    } else if LOCAL_BLOCK_FS_TYPES.contains(&fs_type)
+        || NFS.contains(&fs_type)
+        || matches!(fs_type, "tmpfs" | "hfsplus")
+    {
+        Some((true, true))
+    } else {
+        None
+    }
+}
+
+/// Whether the nearest existing directory of `path` has the casefold flag.
+#[cfg(target_os = "linux")]
+fn is_casefolded(path: &Path) -> bool {
+    use std::os::fd::AsRawFd;
+
+    // FS_CASEFOLD_FL from linux/fs.h
+    const FS_CASEFOLD_FL: libc::c_int = 0x4000_0000;
+
+    let Some(dir) = path.ancestors().find(|p| p.is_dir()) else {
+        return false;
+    };
+    let Ok(file) = fs::File::open(dir) else {
+        return false;
+    };
+    // the kernel writes an int despite the ioctl's declared long argument
+    let mut flags: libc::c_int = 0;
+    let result = unsafe { libc::ioctl(file.as_raw_fd(), libc::FS_IOC_GETFLAGS, &mut flags) };
+    result == 0 && flags & FS_CASEFOLD_FL != 0
+}
+
+/// The inputs to [`PathInfo::stable_id`](crate::PathInfo::stable_id) for
+/// `mount`, most stable first.
+pub(crate) fn identity_parts(mount: &MountInfo) -> Vec<String> {
+    identity_parts_in(mount, Path::new(BY_UUID_PATH))
+}
+
+fn identity_parts_in(mount: &MountInfo, by_uuid: &Path) -> Vec<String> {
+    let fs_type = mount.fs_type.clone();
+    let root = mount.fs_root.to_string_lossy().into_owned();
+
+    // the source names the server and export, which outlive the mount
+    if remote::is_network_fs_type(&fs_type) {
+        let source = mount.block_device.to_string_lossy().into_owned();
+        return vec!["remote".into(), fs_type, source, root];
+    }
+    match device_link_in(mount, by_uuid) {
+        Some(uuid) => vec!["uuid".into(), fs_type, uuid, root],
+        // device names may be reassigned across reboots; the best we have
+        None => {
+            let device = mount.block_device.to_string_lossy().into_owned();
+            vec!["device".into(), fs_type, device, root]
+        }
+    }
+}
+
+/// Undoes udev's `\xHH` escaping of label names (e.g. `\x20` for a space).
+fn unescape_udev(name: &str) -> String {
+    let mut bytes = Vec::with_capacity(name.len());
+    let mut rest = name.as_bytes();
+    while let Some((&b, tail)) = rest.split_first() {
+        if b == b'\\'
+            && let Some(hex) = tail.get(1..3).filter(|_| tail[0] == b'x')
+            && let Some(byte) = std::str::from_utf8(hex)
+                .ok()
+                .and_then(|h| u8::from_str_radix(h, 16).ok())
+        {
+            bytes.push(byte);
+            rest = &tail[3..];
+            continue;
+        }
+        bytes.push(b);
+        rest = tail;
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// The `major:minor` device number of a mount.
+///
+/// On FreeBSD, OpenBSD and macOS, which have no device numbers for mounts,
+/// the two words of the filesystem id stand in for them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct DeviceNumber {
+    major: u32,
+    minor: u32,
+}
+
+impl DeviceNumber {
+    pub fn major(&self) -> u32 {
+        self.major
+    }
+
+    pub fn minor(&self) -> u32 {
+        self.minor
+    }
+}
+
+/// One entry of the mount table: a line of `/proc/self/mountinfo`, or on
+/// FreeBSD, OpenBSD and macOS an entry from `getfsstat` in the same shape.
+///
+/// Returned by [`PathInfo::mount_info`](crate::PathInfo::mount_info).
+///
+/// # Examples
+///
+/// ```rust
+/// use std::path::Path;
+/// use inspect_path::MountInfo;
+///
+/// let mount = MountInfo::parse_line(
+///     "36 35 98:0 /mnt1 /mnt2 rw,noatime master:1 - ext3 /dev/root rw,errors=continue",
+/// )
+/// .unwrap();
+/// assert_eq!((mount.mount_id(), mount.parent_id()), (36, 35));
+/// assert_eq!(mount.device_number().major(), 98);
+/// assert_eq!(mount.fs_root(), Path::new("/mnt1"));
+/// assert_eq!(mount.mount_point(), Path::new("/mnt2"));
+/// assert_eq!(mount.fs_type(), "ext3");
+/// assert_eq!(mount.source(), Path::new("/dev/root"));
+/// assert_eq!(mount.options(), "rw,errors=continue");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct MountInfo {
+    mount_id: u32,
+    parent_id: u32,
+    device_number: DeviceNumber,
+    fs_root: PathBuf,
+    mount_point: PathBuf,
+    vfs_options: String,
+    fs_type: String,
+    block_device: PathBuf,
+    mount_options: String,
+    line: String,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    table: Table,
+}
+
+/// The mountinfo file a [`MountInfo`] was read from, e.g.
+/// `/proc/4242/mountinfo`, so that its parent is looked up in the same
+/// namespace. Not part of the mount's identity: any two compare equal.
+#[derive(Debug, Clone, Default)]
+struct Table(Option<Arc<Path>>);
+
+impl PartialEq for Table {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for Table {}
+
+impl PartialOrd for Table {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Table {
+    fn cmp(&self, _: &Self) -> std::cmp::Ordering {
+        std::cmp::Ordering::Equal
+    }
+}
+
+impl MountInfo {
+    /// Parses a single mountinfo line, as documented in `proc_pid_mountinfo(5)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InspectPathError::ParseGen`] or
+    /// [`InspectPathError::ParseInt`] if a required field is missing or
+    /// malformed. The line didn't come from a file, so the error's path is
+    /// empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use inspect_path::MountInfo;
+    ///
+    /// let nfs = MountInfo::parse_line(
+    ///     "90 32 0:50 / /mnt/nfs rw,relatime shared:60 - nfs4 srv:/export rw,vers=4.2,hard",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(nfs.fs_type(), "nfs4");
+    /// assert_eq!(nfs.source().to_str(), Some("srv:/export"));
+    ///
+    /// assert!(MountInfo::parse_line("not a mountinfo line").is_err());
+    /// ```
+    pub fn parse_line(line: &str) -> Result<Self, InspectPathError> {
+        parse_mountinfo_line(line, Path::new(""))
+    }
+
+    /// The mount's unique id.
+    pub fn mount_id(&self) -> u32 {
+        self.mount_id
+    }
+
+    /// The id of the parent mount, or of itself for the root of the mount
+    /// namespace.
+    pub fn parent_id(&self) -> u32 {
+        self.parent_id
+    }
+
+    /// The device number of the filesystem, `st_dev` of files on it.
+    pub fn device_number(&self) -> DeviceNumber {
+        self.device_number
+    }
+
+    /// The directory within the filesystem that is mounted, `/` unless this
+    /// is a bind mount or a btrfs subvolume.
+    pub fn fs_root(&self) -> &Path {
+        &self.fs_root
+    }
+
+    /// The mount source, e.g. `/dev/sda1`, `srv:/export` or `tmpfs`.
+    pub fn source(&self) -> &Path {
+        &self.block_device
+    }
+
+    /// The superblock options, e.g. `rw,errors=remount-ro`. The per-mount
+    /// options are in [`vfs_options`](Self::vfs_options).
+    pub fn options(&self) -> &str {
+        &self.mount_options
+    }
+
+    /// Classifies this mount the way [`inspect_path`] classifies a path on it.
+    pub fn classify(&self) -> Result<(PathType, Option<RemoteType>), InspectPathError> {
+        let (kind, _, remote_kind) = self.classify_in(Path::new(SYS_PATH))?;
+        Ok((kind, remote_kind))
+    }
+
+    fn classify_in(
+        &self,
+        sys: &Path,
+    ) -> Result<(PathType, Confidence, Option<RemoteType>), InspectPathError> {
+        self.classify_recorded(sys, &mut Recorder::off())
+    }
+
+    fn classify_recorded(
+        &self,
+        sys: &Path,
+        rec: &mut Recorder,
+    ) -> Result<(PathType, Confidence, Option<RemoteType>), InspectPathError> {
+        let (kind, confidence) = get_kind(self, sys, rec)?;
+        let remote_kind = if kind != PathType::Remote {
+            None
+        } else {
+            get_remote_kind(self)?
+        };
+        if let Some(remote) = &remote_kind {
+            rec.decide(format_args!("{} -> {remote:?}", self.fs_type));
+        }
+        Ok((kind, confidence, remote_kind))
+    }
+
+    /// Returns the value of a `key=value` entry in the super options.
+    pub(crate) fn option(&self, key: &str) -> Option<&str> {
+        self.mount_options
+            .split(',')
+            .find_map(|o| o.strip_prefix(key)?.strip_prefix('='))
+    }
+
+    /// Returns whether a bare flag (e.g. `hard`) is present in the super options.
+    pub(crate) fn has_flag(&self, flag: &str) -> bool {
+        self.mount_options.split(',').any(|o| o == flag)
+    }
+
+    /// The per-mount options, e.g. `rw,nosuid,relatime`, as opposed to the
+    /// superblock's in [`options`](Self::options).
+    pub fn vfs_options(&self) -> &str {
+        &self.vfs_options
+    }
+
+    /// Read-only if either the mount (e.g. a read-only bind mount) or the
+    /// superblock is `ro`, or the filesystem format is read-only.
+    pub(crate) fn is_read_only(&self) -> bool {
+        self.vfs_options().split(',').any(|o| o == "ro")
+            || self.has_flag("ro")
+            || READ_ONLY_FS_TYPES.contains(&self.fs_type.as_str())
+    }
+
+    /// Whether a writable format's superblock is `ro` while the mount is
+    /// still `rw`, which is what an error-triggered remount
+    /// (`errors=remount-ro`) leaves behind. Remounting `ro` on purpose
+    /// changes both.
+    pub(crate) fn is_forced_read_only(&self) -> bool {
+        ERRORS_REMOUNT_RO_FS_TYPES.contains(&self.fs_type.as_str())
+            && self.has_flag("ro")
+            && !self.vfs_options().split(',').any(|o| o == "ro")
+    }
+
+    /// The access time policy named in the per-mount options, where the
+    /// kernel reports it. The last one given wins, as with `mount(8)`.
+    pub fn atime_policy(&self) -> Option<AtimePolicy> {
+        self.vfs_options()
+            .split(',')
+            .filter_map(|o| match o {
+                "noatime" => Some(AtimePolicy::NoAtime),
+                "relatime" => Some(AtimePolicy::RelAtime),
+                "strictatime" => Some(AtimePolicy::StrictAtime),
+                _ => None,
+            })
+            .next_back()
+    }
+
+    /// Returns whether an NFS mount is `hard` (the default) or `soft`.
+    pub(crate) fn is_hard_mount(&self) -> Option<bool> {
+        if !NFS.contains(&self.fs_type.as_str()) {
+            return None;
+        }
+        Some(!self.has_flag("soft"))
+    }
+
+    /// Where the filesystem is mounted.
+    pub fn mount_point(&self) -> &Path {
+        &self.mount_point
+    }
+
+    /// The filesystem type, e.g. `ext4`, `nfs4` or `fuse.sshfs`.
+    pub fn fs_type(&self) -> &str {
+        &self.fs_type
+    }
+
+    /// Returns the btrfs subvolume (`subvol=`, else `subvolid=`) of a btrfs mount.
+    pub(crate) fn btrfs_subvolume(&self) -> Option<&str> {
+        if self.fs_type != "btrfs" {
+            return None;
+        }
+        self.option("subvol").or_else(|| self.option("subvolid"))
+    }
+
+    pub(crate) fn device_id(&self) -> DeviceId {
+        DeviceId::from_number(self.device_number.major, self.device_number.minor)
+    }
+
+    /// The mountinfo line this entry was parsed from.
+    pub fn raw_line(&self) -> &str {
+        &self.line
+    }
+
+    /// Returns the protocol version of a network mount (`vers=` / `nfsvers=`).
+    pub(crate) fn protocol_version(&self) -> Option<&str> {
+        self.option("vers").or_else(|| self.option("nfsvers"))
+    }
+}
+
+/// Reads `/proc/self/mountinfo` one line at a time, parsing each entry as it
+/// is reached, so that a search that stops early doesn't read or allocate the
+/// rest of a large table.
+///
+/// A line that doesn't parse is yielded as an error and iteration carries on
+/// with the next one; a read error ends the iteration after it is yielded.
+///
+/// On FreeBSD, OpenBSD and macOS the entries come from `getfsstat`, which
+/// returns them all at once.
+///
+/// # Errors
+///
+/// Returns an error if the mount table can't be opened.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// let first_cifs = inspect_path::mounts()
+///     .unwrap()
+///     .filter_map(Result::ok)
+///     .find(|m| m.fs_type() == "cifs");
+/// if let Some(mount) = first_cifs {
+///     println!("{} is shared from {}", mount.mount_point().display(), mount.source().display());
+/// }
+/// ```
+pub fn mounts()
+-> Result<impl Iterator<Item = Result<MountInfo, InspectPathError>>, InspectPathError> {
+    mounts_in(&Sources::default())
+}
+
+type Mounts = Box<dyn Iterator<Item = Result<MountInfo, InspectPathError>> + Send>;
+
+/// [`mounts`] over the mount table `sources` points at.
+///
+/// On FreeBSD, OpenBSD and macOS there is no mountinfo file; unless a fixture is
+/// configured the table comes from `getfsstat` instead.
+fn mounts_in(sources: &Sources) -> Result<Mounts, InspectPathError> {
+    #[cfg(any(target_os = "freebsd", target_os = "openbsd", target_os = "macos"))]
+    if sources.mountinfo == Path::new(MOUNTINFO_PATH) {
+        return Ok(Box::new(statfs_mounts()?.into_iter().map(Ok)));
+    }
+    let path = sources.mountinfo.clone();
+    let file = fs::File::open(&path).map_err(|source| InspectPathError::Io {
+        path: path.clone(),
+        source,
+    })?;
+    Ok(Box::new(mounts_from(BufReader::new(file), path)))
+}
+
+/// Parses mountinfo lines from `reader` as they are pulled; errors name
+/// `source`.
+fn mounts_from(
+    mut reader: impl BufRead,
+    source: PathBuf,
+) -> impl Iterator<Item = Result<MountInfo, InspectPathError>> {
+    let table: Arc<Path> = Arc::from(source.as_path());
+    let mut line = Vec::new();
+    let mut failed = false;
+    std::iter::from_fn(move || {
+        if failed {
+            return None;
+        }
+        line.clear();
+        let len = match read_line_capped(&mut reader, &mut line, MAX_MOUNTINFO_LINE) {
+            Ok(len) => len?,
+            Err(e) => {
+                // a read error may repeat on every call; report it once
+                failed = true;
+                return Some(Err(InspectPathError::Io {
+                    path: source.clone(),
+                    source: e,
+                }));
+            }
+        };
+        if len > MAX_MOUNTINFO_LINE {
+            return Some(Err(InspectPathError::TooLarge {
+                path: source.clone(),
+                what: "mountinfo line length",
+                limit: MAX_MOUNTINFO_LINE,
+            }));
+        }
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+        Some(match std::str::from_utf8(&line) {
+            Ok(line) => parse_mountinfo_line(line, &source).map(|mount| MountInfo {
+                table: Table(Some(table.clone())),
+                ..mount
+            }),
+            Err(_) => Err(InspectPathError::ParseGen {
+                path: source.clone(),
+                msg: "mountinfo line is not UTF-8",
+            }),
+        })
+    })
+}
+
+/// Reads one line into `buf` without its `\n`, keeping at most `max` bytes
+/// of it and skipping the rest, so that an endless line can't exhaust
+/// memory. Returns the full length of the line, or `None` at the end of the
+/// input.
+fn read_line_capped(
+    reader: &mut impl BufRead,
+    buf: &mut Vec<u8>,
+    max: usize,
+) -> std::io::Result<Option<usize>> {
+    let mut len = None;
+    loop {
+        let chunk = match reader.fill_buf() {
+            Ok(chunk) => chunk,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        if chunk.is_empty() {
+            return Ok(len);
+        }
+        let newline = chunk.iter().position(|&b| b == b'\n');
+        let part = &chunk[..newline.unwrap_or(chunk.len())];
+        let room = max.saturating_sub(buf.len());
+        buf.extend_from_slice(&part[..part.len().min(room)]);
+        let total = len.unwrap_or(0) + part.len();
+        len = Some(total);
+
+        let used = part.len() + usize::from(newline.is_some());
+        reader.consume(used);
+        if newline.is_some() {
+            return Ok(len);
+        }
+    }
+}
+
+/// Reads the whole mount table `sources` points at, failing on the first
+/// bad entry.
+fn load_mounts(sources: &Sources) -> Result<Vec<MountInfo>, InspectPathError> {
+    mounts_in(sources)?.collect()
+}
+
+/// Builds the mount table from `getfsstat`, classifying by `f_fstypename`.
+///
+/// `MNT_NOWAIT` returns the kernel's cached entries, so an unresponsive
+/// network mount doesn't block the listing. The filesystem id stands in for
+/// the device number.
+#[cfg(any(target_os = "freebsd", target_os = "openbsd", target_os = "macos"))]
+fn statfs_mounts() -> Result<Vec<MountInfo>, InspectPathError> {
+    use std::ffi::CStr;
+
+    let entries = getfsstat()?;
+    let text = |chars: &[libc::c_char]| {
+        unsafe { CStr::from_ptr(chars.as_ptr()) }
+            .to_string_lossy()
+            .into_owned()
+    };
+    Ok(entries
+        .iter()
+        .zip(1..)
+        .map(|(sfs, mount_id)| {
+            // fsid_t keeps its two words private
+            let fsid: [i32; 2] = unsafe { std::mem::transmute(sfs.f_fsid) };
+            let device_number = DeviceNumber {
+                major: fsid[0] as u32,
+                minor: fsid[1] as u32,
+            };
+            let mount_point = text(&sfs.f_mntonname);
+            let fs_type = text(&sfs.f_fstypename);
+            let source = text(&sfs.f_mntfromname);
+            // f_flags is u64 on FreeBSD and u32 on OpenBSD and macOS
+            #[allow(clippy::unnecessary_cast)]
+            let options = if sfs.f_flags as u64 & libc::MNT_RDONLY as u64 != 0 {
+                "ro"
+            } else {
+                "rw"
+            };
+            MountInfo {
+                mount_id,
+                parent_id: 0,
+                line: format!(
+                    "{mount_id} 0 {}:{} / {} {options} - {} {} {options}",
+                    device_number.major,
+                    device_number.minor,
+                    escape_octal(Path::new(&mount_point)),
+                    escape_octal(Path::new(&fs_type)),
+                    escape_octal(Path::new(&source)),
+                ),
+                device_number,
+                fs_root: PathBuf::from("/"),
+                mount_point: PathBuf::from(mount_point),
+                vfs_options: options.to_string(),
+                fs_type,
+                block_device: PathBuf::from(source),
+                mount_options: options.to_string(),
+                table: Table::default(),
+            }
+        })
+        .collect())
+}
+
+/// Copies the kernel's mount list into a buffer this call owns.
+/// `getmntinfo` would hand back a static buffer that the next call on any
+/// thread overwrites.
+#[cfg(any(target_os = "freebsd", target_os = "openbsd", target_os = "macos"))]
+fn getfsstat() -> Result<Vec<libc::statfs>, InspectPathError> {
+    let error = || InspectPathError::Io {
+        path: PathBuf::from("getfsstat"),
+        source: std::io::Error::last_os_error(),
+    };
+
+    loop {
+        let count = unsafe { libc::getfsstat(std::ptr::null_mut(), 0, libc::MNT_NOWAIT) };
+        if count < 0 {
+            return Err(error());
+        }
+        // room for a mount added between the two calls
+        let capacity = count as usize + 8;
+        let mut entries: Vec<libc::statfs> = Vec::with_capacity(capacity);
+        let size = capacity * std::mem::size_of::<libc::statfs>();
+        let filled = unsafe { libc::getfsstat(entries.as_mut_ptr(), size as _, libc::MNT_NOWAIT) };
+        if filled < 0 {
+            return Err(error());
+        }
+        // a full buffer may have been truncated, so count again
+        if (filled as usize) < capacity {
+            unsafe { entries.set_len(filled as usize) };
+            return Ok(entries);
+        }
+    }
+}
+
+fn parse_mountinfo_line(line: &str, source: &Path) -> Result<MountInfo, InspectPathError> {
+    let bad = |msg| InspectPathError::ParseGen {
+        path: source.to_path_buf(),
+        msg,
+    };
+    let int = |e| InspectPathError::ParseInt {
+        path: source.to_path_buf(),
+        source: e,
+    };
+    let too_large = |what, limit| InspectPathError::TooLarge {
+        path: source.to_path_buf(),
+        what,
+        limit,
+    };
+    if line.len() > MAX_MOUNTINFO_LINE {
+        return Err(too_large("mountinfo line length", MAX_MOUNTINFO_LINE));
+    }
+
+    let mut fields = mountinfo_fields(line);
+    let mut next = |missing| fields.next().ok_or_else(|| bad(missing));
+
+    let mount_id: u32 = next("missing mount id")?.parse().map_err(int)?;
+    let parent_id: u32 = next("missing parent id")?.parse().map_err(int)?;
+
+    let (major, minor) = next("missing device number")?
+        .split_once(":")
+        .ok_or_else(|| bad("malformed device number"))?;
+
+    let device_number: DeviceNumber = DeviceNumber {
+        major: major.parse().map_err(int)?,
+        minor: minor.parse().map_err(int)?,
+    };
+
+    let fs_root = unescape_octal(next("missing root")?);
+    let mount_point = unescape_octal(next("missing mount point")?);
+    let vfs_options: String = next("missing mount options")?.into();
+
+    // optional fields (`shared:1`, `master:2`, ...) up to the separator
+    let mut count = 6;
+    while next("missing ' - ' separator")? != "-" {
+        count += 1;
+        if count > MAX_MOUNTINFO_FIELDS {
+            return Err(too_large("mountinfo field count", MAX_MOUNTINFO_FIELDS));
+        }
+    }
+
+    let fs_type = unescape_octal(next("missing fs type")?)
+        .to_string_lossy()
+        .into_owned();
+    let block_device = unescape_octal(next("missing mount source")?);
+    let mount_options: String = next("missing super options")?.into();
+
+    Ok(MountInfo {
+        mount_id,
+        parent_id,
+        device_number,
+        fs_root,
+        mount_point,
+        vfs_options,
+        fs_type,
+        block_device,
+        mount_options,
+        line: line.to_string(),
+        table: Table::default(),
+    })
+}
+
+/// The space-separated fields of a mountinfo line. Only spaces separate:
+/// the kernel escapes tabs and newlines in names, but not other whitespace.
+fn mountinfo_fields(line: &str) -> impl Iterator<Item = &str> {
+    line.split(' ').filter(|f| !f.is_empty())
+}
+
+/// Undoes the kernel's `\ooo` escaping of space, tab, newline and backslash
+/// in mountinfo names. Other bytes are taken as they are, so a name that
+/// isn't UTF-8 keeps its bytes.
+fn unescape_octal(field: &str) -> PathBuf {
+    use std::os::unix::ffi::OsStringExt;
+
+    let mut bytes = Vec::with_capacity(field.len());
+    let mut rest = field.as_bytes();
+    while let Some((&b, tail)) = rest.split_first() {
+        if b == b'\\'
+            && let [d0 @ b'0'..=b'3', d1 @ b'0'..=b'7', d2 @ b'0'..=b'7', ..] = tail
+        {
+            bytes.push((d0 - b'0') << 6 | (d1 - b'0') << 3 | (d2 - b'0'));
+            rest = &tail[3..];
+            continue;
+        }
+        bytes.push(b);
+        rest = tail;
+    }
+    PathBuf::from(std::ffi::OsString::from_vec(bytes))
+}
+
+/// Escapes a name the way the kernel does in mountinfo, the inverse of
+/// [`unescape_octal`].
+#[cfg_attr(
+    not(any(
+        test,
+        target_os = "freebsd",
+        target_os = "openbsd",
+        target_os = "macos"
+    )),
+    allow(dead_code)
+)]
+fn escape_octal(name: &Path) -> String {
+    use std::os::unix::ffi::OsStrExt;
+
+    let mut out = String::new();
+    for chunk in name.as_os_str().as_bytes().utf8_chunks() {
+        for c in chunk.valid().chars() {
+            match c {
+                ' ' | '\t' | '\n' | '\\' => out.push_str(&format!("\\{:03o}", c as u32)),
+                c => out.push(c),
+            }
+        }
+        for &b in chunk.invalid() {
+            out.push_str(&format!("\\{b:03o}"));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mountinfo_into_vec(s: &str) -> Result<Vec<MountInfo>, InspectPathError> {
+        mounts_from(s.as_bytes(), PathBuf::from(MOUNTINFO_PATH)).collect()
+    }
+
+    const FIXTURE: &str = "\
+32 2 259:2 / / rw,relatime shared:1 - ext4 /dev/nvme0n1p2 rw,errors=remount-ro
+30 32 0:26 / /run rw,nosuid,nodev,noexec,relatime shared:5 - tmpfs tmpfs rw,size=382124k,mode=755
+27 32 0:24 / /proc rw,nosuid,nodev,noexec,relatime shared:13 - proc proc rw
+90 32 0:50 / /mnt/nfs rw,relatime shared:60 - nfs4 srv:/export rw,vers=4.2,hard,proto=tcp
+91 32 0:51 / /mnt/smb rw,relatime shared:61 - cifs //srv/share rw,vers=3.1.1
+92 32 0:52 / /mnt/ssh rw,nosuid,nodev,relatime shared:62 - fuse.sshfs bob@srv:/home rw,user_id=1000
+93 32 0:53 / /var/lib/docker/overlay2/abc/merged rw,relatime - overlay overlay rw,lowerdir=/l,upperdir=/u
+94 32 8:17 / /media/usb rw,nosuid,nodev,relatime shared:63 - vfat /dev/sdb1 rw,fmask=0022
+96 32 8:1 / /data rw,relatime shared:65 - ext4 /dev/sda1 rw
+95 90 0:54 / /mnt/nfs/nested rw,relatime shared:64 - nfs srv:/other rw,vers=3
+";
+
+    #[test]
+    fn mount_table_network_paths() {
+        let table = MountTable {
+            mounts: mountinfo_into_vec(FIXTURE).unwrap(),
+        };
+        assert!(table.is_network(Path::new("/mnt/nfs")));
+        assert!(table.is_network(Path::new("/mnt/nfs/nested/deep")));
+        assert!(table.is_network(Path::new("/mnt/smb/file")));
+        assert!(table.is_network(Path::new("/mnt/ssh")));
+        assert!(!table.is_network(Path::new("/mnt/nfsx")));
+        assert!(!table.is_network(Path::new("/media/usb")));
+        assert!(!table.is_network(Path::new("/etc/fstab")));
+        assert!(!table.is_network(Path::new("mnt/nfs")));
+    }
+
+    #[test]
+    fn bsd_fs_type_names() {
+        let mounts = mountinfo_into_vec(
+            "1 0 1:1 / / rw - ufs /dev/ada0p2 rw\n\
+             2 1 1:2 / /tank rw - zfs tank rw\n\
+             3 1 1:3 / /mnt/nfs rw - nfs srv:/export rw\n\
+             4 1 1:4 / /mnt/smb rw - smbfs //bob@srv/share rw\n\
+             5 1 1:5 / /cdrom ro - cd9660 /dev/cd0 ro\n\
+             6 1 1:6 / /tmp rw - tmpfs tmpfs rw\n\
+             7 1 1:7 / /dev rw - devfs devfs rw",
+        )
+        .unwrap();
+        let kinds: Vec<_> = mounts.iter().map(|m| m.classify().unwrap()).collect();
+        assert_eq!(
+            kinds,
+            [
+                (PathType::Fixed, None),
+                (PathType::Fixed, None),
+                (PathType::Remote, Some(RemoteType::NFS)),
+                (PathType::Remote, Some(RemoteType::SMB)),
+                (PathType::CDRom, None),
+                (PathType::RamDisk, None),
+                (PathType::Virtual("devfs".into()), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn macos_fs_type_names() {
+        let mounts = mountinfo_into_vec(
+            "1 0 1:4 / / ro - apfs /dev/disk3s1s1 ro\n\
+             2 1 1:5 / /Volumes/Old rw - hfs /dev/disk4s2 rw\n\
+             3 1 1:6 / /Volumes/share rw - smbfs //bob@srv/share rw",
+        )
+        .unwrap();
+        let kinds: Vec<_> = mounts.iter().map(|m| m.classify().unwrap()).collect();
+        assert_eq!(
+            kinds,
+            [
+                (PathType::Fixed, None),
+                (PathType::Fixed, None),
+                (PathType::Remote, Some(RemoteType::SMB)),
+            ]
+        );
+        assert_eq!(is_case_sensitive(&mounts[0], Path::new("/")), Some(false));
+        assert_eq!(link_support(&mounts[0]), Some((true, true)));
+    }
+
+    #[test]
+    fn case_sensitivity_by_fs_type() {
+        let mounts = mountinfo_into_vec(FIXTURE).unwrap();
+        let at = |point: &str| {
+            let mount = mounts
+                .iter()
+                .find(|m| m.mount_point == Path::new(point))
+                .unwrap();
+            is_case_sensitive(mount, &std::env::temp_dir())
+        };
+
+        assert_eq!(at("/"), Some(true));
+        assert_eq!(at("/media/usb"), Some(false));
+        assert_eq!(at("/mnt/nfs"), None);
+        assert_eq!(at("/mnt/smb"), None);
+        assert_eq!(at("/proc"), None);
+
+        let live = inspect_path(std::env::temp_dir()).unwrap();
+        if live.fs_type_name() == Some("ext4") {
+            assert_eq!(live.is_case_sensitive(), Some(true));
+        }
+    }
+
+    #[test]
+    fn link_support_by_fs_type() {
+        let mounts = mountinfo_into_vec(FIXTURE).unwrap();
+        let at = |point: &str| {
+            let mount = mounts
+                .iter()
+                .find(|m| m.mount_point == Path::new(point))
+                .unwrap();
+            link_support(mount)
+        };
+
+        assert_eq!(at("/"), Some((true, true)));
+        assert_eq!(at("/mnt/nfs"), Some((true, true)));
+        assert_eq!(at("/media/usb"), Some((false, false)));
+        assert_eq!(at("/mnt/smb"), None);
+        assert_eq!(at("/proc"), None);
+    }
+
+    /// [`FIXTURE`] and a sysfs tree with `sdb` removable, in a temporary
+    /// directory that is removed when this is dropped.
+    struct Fixture {
+        sources: Sources,
+        _dir: tempfile::TempDir,
+    }
+
+    impl std::ops::Deref for Fixture {
+        type Target = Sources;
+
+        fn deref(&self) -> &Sources {
+            &self.sources
+        }
+    }
+
+    fn fixture_sources() -> Fixture {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        // sda is an internal disk, sdb a USB stick; only disks have the flag
+        let block = root.join("sys/dev/block");
+        fs::create_dir_all(&block).unwrap();
+        for (disk, dev, removable) in [("sda", 0, "0\n"), ("sdb", 16, "1\n")] {
+            let disk_dir = root.join("sys/devices").join(disk);
+            let part = disk_dir.join(format!("{disk}1"));
+            fs::create_dir_all(&part).unwrap();
+            fs::write(disk_dir.join("removable"), removable).unwrap();
+            std::os::unix::fs::symlink(&disk_dir, block.join(format!("8:{dev}"))).unwrap();
+            std::os::unix::fs::symlink(&part, block.join(format!("8:{}", dev + 1))).unwrap();
+        }
+        fs::write(root.join("mountinfo"), FIXTURE).unwrap();
+
+        Fixture {
+            sources: Sources {
+                mountinfo: root.join("mountinfo"),
+                sys: root.join("sys"),
+            },
+            _dir: dir,
+        }
+    }
+
+    #[test]
+    fn parent_mounts_walk_to_the_root() {
+        let sources = fixture_sources();
+        let nfs = inspect_path_from(Path::new("/mnt/nfs/file"), &sources).unwrap();
+
+        let root = parent_mount_from(nfs.mount.as_ref().unwrap(), &sources)
+            .unwrap()
+            .unwrap();
+        assert_eq!(root.mount_point(), Some(Path::new("/")));
+        assert!(root.is_fixed());
+        // its parent, 2, isn't in the table
+        assert_eq!(
+            parent_mount_from(root.mount.as_ref().unwrap(), &sources).unwrap(),
+            None
+        );
+
+        // the parent comes from the table the mount was read from, not the
+        // one the caller would read by default
+        let elsewhere = Sources {
+            mountinfo: sources.mountinfo.with_extension("missing"),
+            sys: sources.sys.clone(),
+        };
+        let again = parent_mount_from(nfs.mount.as_ref().unwrap(), &elsewhere).unwrap();
+        assert_eq!(again, Some(root));
+        let parsed = MountInfo::parse_line(nfs.raw_mountinfo_line().unwrap()).unwrap();
+        assert!(parent_mount_from(&parsed, &elsewhere).is_err());
+    }
+
+    #[test]
+    fn session_cache_reads_mountinfo_once() {
+        let sources = fixture_sources();
+        let mut cache = SessionCache::default();
+        let first = inspect_cached_from(Path::new("/mnt/nfs/a"), &mut cache, &sources).unwrap();
+
+        // later lookups, even on other mounts, come from the cached table
+        fs::remove_file(&sources.mountinfo).unwrap();
+        let again = inspect_cached_from(Path::new("/mnt/nfs/b"), &mut cache, &sources).unwrap();
+        assert_eq!(again.kind(), first.kind());
+        assert_eq!(again.path(), Path::new("/mnt/nfs/b"));
+        let smb = inspect_cached_from(Path::new("/mnt/smb/x"), &mut cache, &sources).unwrap();
+        assert_eq!(smb.remote_type(), Some(&RemoteType::SMB));
+
+        let mut cache = SessionCache::default();
+        assert!(inspect_cached_from(Path::new("/mnt/nfs/a"), &mut cache, &sources).is_err());
+    }
+
+    #[test]
+    fn errors_name_their_path() {
+        let sources = fixture_sources();
+        fs::write(
+            &sources.mountinfo,
+            "90 32 0:50 / /mnt/nfs rw - nfs4 srv:/export rw\n",
+        )
+        .unwrap();
+
+        let paths = ["/mnt/nfs/a", "/srv/elsewhere/b", "/mnt/nfs/c"].map(Path::new);
+        let results = inspect_paths_from(&paths, &sources);
+        let failed: Vec<_> = results.iter().filter_map(|r| r.as_ref().err()).collect();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].path(), Some(Path::new("/srv/elsewhere/b")));
+        assert!(failed[0].to_string().contains("/srv/elsewhere/b"));
+
+        fs::write(&sources.mountinfo, "90 32 0:50 / /mnt/nfs rw nfs4\n").unwrap();
+        let err = inspect_path_from(Path::new("/mnt/nfs/a"), &sources).unwrap_err();
+        assert_eq!(err.path(), Some(sources.mountinfo.as_path()));
+    }
+
+    #[test]
+    fn fixture_confidence() {
+        let sources = fixture_sources();
+        let confidence = |p: &str| {
+            inspect_path_from(Path::new(p), &sources)
+                .unwrap()
+                .confidence()
+        };
+
+        assert_eq!(confidence("/mnt/nfs"), Confidence::High);
+        assert_eq!(confidence("/proc/self"), Confidence::High);
+        // an unlisted anonymous device is only assumed to be virtual
+        assert_eq!(
+            confidence("/var/lib/docker/overlay2/abc/merged/etc"),
+            Confidence::Medium
+        );
+    }
+
+    #[test]
+    fn report_redacts_mount_point() {
+        let sources = fixture_sources();
+        fs::write(
+            &sources.mountinfo,
+            "1 0 8:2 / / rw - ext4 /dev/sda2 rw\n\
+             2 1 0:50 / /home/alice rw - nfs4 srv:/export/alice rw\n",
+        )
+        .unwrap();
+        let path = Path::new("/home/alice/notes.txt");
+        let report =
+            crate::report::report_with(path, |rec| inspect_path_recorded(path, &sources, rec));
+
+        assert!(report.to_string().contains("/home/alice "));
+        let redacted = format!("{report:#}");
+        assert!(!redacted.contains("alice"), "{redacted}");
+        assert!(redacted.contains("<mount>"), "{redacted}");
+    }
+
+    #[test]
+    fn removable_flag_of_partition_disk() {
+        let sources = fixture_sources();
+        let kind = |path: &str| {
+            inspect_path_from(Path::new(path), &sources)
+                .unwrap()
+                .kind()
+                .clone()
+        };
+        assert_eq!(kind("/media/usb/photo.jpg"), PathType::Removable);
+        assert_eq!(kind("/data/file"), PathType::Fixed);
+    }
+
+    #[test]
+    fn virtual_mounts_skip_sysfs() {
+        let sources = fixture_sources();
+        // unparsable, so any read of it would fail the inspection
+        let anonymous = sources.sys.join("dev/block/0:0");
+        fs::create_dir_all(&anonymous).unwrap();
+        fs::write(anonymous.join("removable"), "garbage\n").unwrap();
+
+        let info = inspect_path_from(Path::new("/proc/self/status"), &sources).unwrap();
+        assert!(info.is_virtual());
+        let info = inspect_path_from(Path::new("/mnt/nfs/file"), &sources).unwrap();
+        assert!(info.is_remote());
+
+        // a bulk scan reads the mount table once and nothing per path after it
+        let mut cache = SessionCache::default();
+        let first = inspect_cached_from(Path::new("/proc/1/status"), &mut cache, &sources);
+        assert!(first.unwrap().is_virtual());
+        fs::remove_file(&sources.mountinfo).unwrap();
+        fs::remove_dir_all(&sources.sys).unwrap();
+        for path in ["/proc/2/status", "/proc/sys/kernel/hostname", "/mnt/nfs/b"] {
+            let info = inspect_cached_from(Path::new(path), &mut cache, &sources).unwrap();
+            assert_eq!(info.is_virtual(), path.starts_with("/proc"), "{path}");
+        }
+    }
+
+    #[test]
+    fn fixture_crosses_mount() {
+        let sources = fixture_sources();
+        let crosses =
+            |a: &str, d: &str| crosses_mount_from(Path::new(a), Path::new(d), &sources).unwrap();
+
+        assert!(crosses("/mnt", "/mnt/nfs/file"));
+        assert!(crosses("/mnt/nfs", "/mnt/nfs/nested/file"));
+        assert!(!crosses("/mnt/nfs", "/mnt/nfs/projects/file"));
+        assert!(!crosses("/opt", "/opt/a/b"));
+    }
+
+    #[test]
+    fn fixture_same_device() {
+        let sources = fixture_sources();
+        let info = |p: &str| inspect_path_from(Path::new(p), &sources).unwrap();
+
+        assert_eq!(info("/opt/a").same_device(&info("/srv/b")), Some(true));
+        assert_eq!(info("/opt/a").same_device(&info("/mnt/nfs/c")), Some(false));
+    }
+
+    #[test]
+    fn fixture_display_names() {
+        let sources = fixture_sources();
+        let name = |p: &str| {
+            inspect_path_from(Path::new(p), &sources)
+                .unwrap()
+                .name_with_label(None)
+        };
+
+        assert_eq!(name("/media/usb/photos"), "Removable Disk (/media/usb)");
+        assert_eq!(name("/mnt/nfs/file"), "Network Drive (/mnt/nfs)");
+        assert_eq!(name("/etc"), "Local Disk (/)");
+    }
+
+    #[test]
+    fn nonexistent_path_resolves_through_ancestor() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let target = root.join("target");
+        let link = root.join("link");
+        fs::create_dir_all(&target).unwrap();
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        let (resolved, _) = get_resolved_path(&link.join("new/file.txt"), &mut Recorder::off());
+
+        assert_eq!(
+            resolved,
+            Some(fs::canonicalize(&target).unwrap().join("new/file.txt"))
+        );
+    }
+
+    #[test]
+    fn lexical_keeps_the_final_symlink() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let dir = root.join("dir");
+        fs::create_dir_all(&dir).unwrap();
+        let (to_proc, to_dir) = (root.join("proc"), root.join("to_dir"));
+        for (target, link) in [(Path::new("/proc"), &to_proc), (dir.as_path(), &to_dir)] {
+            std::os::unix::fs::symlink(target, link).unwrap();
+        }
+        let root = fs::canonicalize(root).unwrap();
+
+        let full = inspect_path(&to_proc).unwrap();
+        let lexical = inspect_path_lexical(&to_proc).unwrap();
+        assert!(full.is_virtual());
+        assert!(!lexical.is_virtual() && lexical.is_symlink());
+        assert_eq!(lexical.resolved_path(), &Some(root.join("proc")));
+
+        // parents are still followed
+        let (resolved, is_symlink) =
+            get_parent_resolved_path(&to_dir.join("new.txt"), &mut Recorder::off());
+        assert_eq!(resolved, Some(root.join("dir/new.txt")));
+        assert!(!is_symlink);
+        let (resolved, _) = get_parent_resolved_path(&to_dir.join(".."), &mut Recorder::off());
+        assert_eq!(resolved, Some(root.clone()));
+    }
+
+    #[test]
+    fn fixture_refresh_detects_changes() {
+        let before = fixture_sources();
+        let after = Sources {
+            mountinfo: before.mountinfo.with_file_name("mountinfo_after"),
+            sys: before.sys.clone(),
+        };
+        fs::write(&after.mountinfo, FIXTURE.replace("/mnt/nfs ", "/mnt/gone ")).unwrap();
+
+        let mut info = inspect_path_from(Path::new("/mnt/nfs/file"), &before).unwrap();
+        assert!(info.is_remote());
+
+        let unchanged = inspect_path_from(Path::new("/mnt/nfs/file"), &before);
+        assert!(!info.apply_refresh(unchanged, false));
+
+        let moved = inspect_path_from(Path::new("/mnt/nfs/file"), &after);
+        assert!(info.apply_refresh(moved, false));
+        assert!(info.is_fixed());
+        assert_eq!(info.path(), Path::new("/mnt/nfs/file"));
+
+        let failed = Err(InspectPathError::PathTypeError(PathBuf::from(
+            "/mnt/nfs/file",
+        )));
+        assert!(info.apply_refresh(failed, false));
+        assert!(info.is_fixed());
+        assert!(info.is_status_disconnected());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn inspect_in_own_namespace() {
+        let ours = inspect_in_namespace(std::process::id(), Path::new("/")).unwrap();
+        assert_eq!(ours.kind(), inspect_path(Path::new("/")).unwrap().kind());
+
+        assert!(matches!(
+            inspect_in_namespace(u32::MAX, Path::new("/")),
+            Err(InspectPathError::Io { .. })
+        ));
+    }
+
+    #[test]
+    fn errno_status_table() {
+        for (errno, status) in ERRNO_STATUS {
+            let err = std::io::Error::from_raw_os_error(*errno);
+            assert_eq!(
+                &super::super::status_from_error(&err, ERRNO_STATUS),
+                status,
+                "errno {errno}"
+            );
+        }
+
+        let unmapped = std::io::Error::from_raw_os_error(libc::EBADF);
+        assert_eq!(
+            super::super::status_from_error(&unmapped, ERRNO_STATUS),
+            PathStatus::Unknown
+        );
+    }
+
+    #[test]
+    fn probe_status_outcomes() {
+        assert_eq!(probe_status(Path::new("/")).unwrap(), PathStatus::Mounted);
+        assert_eq!(
+            probe_status(Path::new("/definitely/not/here")).unwrap(),
+            PathStatus::Disconnected
+        );
+        assert!(matches!(
+            probe_status(Path::new("bad\0path")),
+            Err(InspectPathError::InvalidPath(_))
+        ));
+        assert_eq!(crate::check_status(Path::new("")), PathStatus::Unknown);
+    }
+
+    #[test]
+    fn mountinfo_env_override() {
+        assert_eq!(mountinfo_path(None), Path::new(MOUNTINFO_PATH));
+        assert_eq!(mountinfo_path(Some("".into())), Path::new(MOUNTINFO_PATH));
+        assert_eq!(
+            mountinfo_path(Some("/tmp/fixture".into())),
+            Path::new("/tmp/fixture")
+        );
+    }
+
+    #[test]
+    fn inspect_options_pick_mount_table_and_symlinks() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = fs::canonicalize(tmp.path()).unwrap();
+        fs::create_dir_all(dir.join("share")).unwrap();
+        std::os::unix::fs::symlink(dir.join("share"), dir.join("link")).unwrap();
+        let table = format!(
+            "20 1 8:1 / / rw - ext4 /dev/sda1 rw\n\
+             30 20 0:50 / {} rw - nfs4 server:/export rw,addr=10.0.0.1\n",
+            dir.join("share").display()
+        );
+        fs::write(dir.join("mountinfo"), table).unwrap();
+
+        let link = dir.join("link");
+        let live = inspect_with(&link, &InspectOptions::new()).unwrap();
+        assert_ne!(live.mount_point(), Some(dir.join("share").as_path()));
+
+        let opts = InspectOptions::new().mount_source(dir.join("mountinfo"));
+        let target = inspect_with(&link, &opts).unwrap();
+        assert!(target.is_remote());
+        assert_eq!(
+            target.resolved_path().as_deref(),
+            Some(dir.join("share").as_path())
+        );
+
+        // the link itself lives on the root filesystem
+        let lexical = inspect_with(&link, &opts.follow_symlinks(false)).unwrap();
+        assert!(lexical.is_fixed());
+        assert!(lexical.is_symlink());
+        assert_eq!(lexical.mount_point(), Some(Path::new("/")));
+
+        // parents are still followed
+        let (resolved, is_symlink) =
+            get_parent_resolved_path(&link.join("new.txt"), &mut Recorder::off());
+        assert_eq!(resolved, Some(dir.join("share/new.txt")));
+        assert!(!is_symlink);
+    }
+
+    #[test]
+    fn io_error_keeps_source_and_path() {
+        use std::error::Error;
+
+        let sources = Sources {
+            mountinfo: PathBuf::from("/nonexistent/mountinfo"),
+            sys: PathBuf::from(SYS_PATH),
+        };
+        let err = inspect_path_from(Path::new("/"), &sources).unwrap_err();
+
+        assert!(
+            matches!(&err, InspectPathError::Io { path, .. } if path == Path::new("/nonexistent/mountinfo"))
+        );
+        let io = err
+            .source()
+            .unwrap()
+            .downcast_ref::<std::io::Error>()
+            .unwrap();
+        assert_eq!(io.raw_os_error(), Some(libc::ENOENT));
+    }
+
+    fn classify(sources: &Sources, path: &str) -> (PathType, Option<RemoteType>) {
+        let info = inspect_path_from(Path::new(path), sources).unwrap();
+        (info.kind, info.remote_kind)
+    }
+
+    #[test]
+    fn fixture_classification() {
+        let sources = fixture_sources();
+
+        assert_eq!(
+            classify(&sources, "/mnt/nfs/projects/a.txt"),
+            (PathType::Remote, Some(RemoteType::NFS))
+        );
+        assert_eq!(
+            classify(&sources, "/mnt/smb/doc.pdf"),
+            (PathType::Remote, Some(RemoteType::SMB))
+        );
+        assert_eq!(
+            classify(&sources, "/mnt/ssh/notes"),
+            (PathType::Remote, Some(RemoteType::SSHFS))
+        );
+        assert_eq!(
+            classify(&sources, "/var/lib/docker/overlay2/abc/merged/etc"),
+            (PathType::Virtual("overlay".into()), None)
+        );
+        assert_eq!(
+            classify(&sources, "/media/usb/photos"),
+            (PathType::Removable, None)
+        );
+        assert_eq!(
+            classify(&sources, "/run/lock"),
+            (PathType::Virtual("tmpfs".into()), None)
+        );
+        assert_eq!(classify(&sources, "/opt/thing"), (PathType::Fixed, None));
+    }
+
+    #[test]
+    fn fixture_raw_mountinfo_line() {
+        let sources = fixture_sources();
+        let info = inspect_path_from(Path::new("/mnt/smb/doc.pdf"), &sources).unwrap();
+        let expected = FIXTURE.lines().find(|l| l.contains(" /mnt/smb ")).unwrap();
+        assert_eq!(info.raw_mountinfo_line(), Some(expected));
+    }
+
+    #[test]
+    fn fixture_batch_matches_single() {
+        let sources = fixture_sources();
+        let paths = [
+            Path::new("/mnt/nfs/a"),
+            Path::new(""),
+            Path::new("/mnt/nfs/b"),
+            Path::new("/media/usb/c"),
+            Path::new("/run/d"),
+        ];
+
+        let batch = inspect_paths_from(&paths, &sources);
+        assert_eq!(batch.len(), paths.len());
+        for (path, result) in paths.iter().zip(&batch) {
+            match inspect_path_from(path, &sources) {
+                Ok(single) => assert_eq!(result.as_ref().unwrap(), &single),
+                Err(_) => assert!(result.is_err(), "{}", path.display()),
+            }
+        }
+        assert!(batch[1].is_err());
+        assert_eq!(batch[2].as_ref().unwrap().path(), Path::new("/mnt/nfs/b"));
+    }
+
+    #[test]
+    fn fixture_root_filesystem() {
+        let sources = fixture_sources();
+        let inspect = |path: &str| inspect_path_from(Path::new(path), &sources).unwrap();
+
+        assert!(inspect("/opt/thing").is_root_filesystem());
+        assert!(!inspect("/mnt/nfs/a").is_root_filesystem());
+        assert!(!inspect("/run/lock").is_root_filesystem());
+    }
+
+    #[test]
+    fn fs_type_names() {
+        let sources = fixture_sources();
+        let inspect = |path: &str| inspect_path_from(Path::new(path), &sources).unwrap();
+        assert_eq!(inspect("/mnt/nfs/a").fs_type_name(), Some("nfs4"));
+        assert_eq!(inspect("/mnt/ssh/x").fs_type_name(), Some("fuse.sshfs"));
+        assert_eq!(
+            inspect("/mnt/nfs/a").device_id().unwrap().to_string(),
+            "0:50"
+        );
+
+        #[cfg(target_os = "linux")]
+        {
+            let proc = inspect_path(Path::new("/proc")).unwrap();
+            assert_eq!(proc.fs_type_name(), Some("proc"));
+
+            assert_eq!(fs_type_from_magic(0x9fa0), "proc");
+            assert_eq!(fs_type_from_magic(0x1234_5678), "unknown(0x12345678)");
+            assert_eq!(
+                statfs_magic(Path::new("/proc")).map(fs_type_from_magic),
+                Some("proc".to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn read_only_mounts() {
+        let mounts = mountinfo_into_vec(
+            "70 1 8:2 / /data ro,relatime - ext4 /dev/sda2 rw\n\
+             71 1 8:2 / /data-rw rw,relatime - ext4 /dev/sda2 rw\n\
+             72 1 8:3 / /backup rw,relatime - ext4 /dev/sda3 ro,errors=remount-ro\n\
+             73 1 7:0 / /snap/core/1 rw,nodev,relatime - squashfs /dev/loop0 rw\n\
+             74 1 11:0 / /media/cdrom rw,relatime - iso9660 /dev/sr0 rw",
+        )
+        .unwrap();
+        let read_only: Vec<bool> = mounts.iter().map(MountInfo::is_read_only).collect();
+        assert_eq!(read_only, [true, false, true, true, true]);
+
+        let sources = fixture_sources();
+        let root = inspect_path_from(Path::new("/etc"), &sources).unwrap();
+        assert_eq!(root.is_read_only(), Some(false));
+        assert!(
+            inspect_path(Path::new("/proc"))
+                .unwrap()
+                .is_read_only()
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn proc_is_virtual() {
+        let proc = inspect_path(Path::new("/proc")).unwrap();
+        assert!(proc.is_virtual());
+        assert!(!proc.is_fixed());
+        assert_eq!(proc.kind(), &PathType::Virtual("proc".into()));
+        assert_eq!(proc.confidence(), Confidence::High);
+
+        let sources = fixture_sources();
+        let kind = |p: &str| inspect_path_from(Path::new(p), &sources).unwrap().kind;
+        assert_eq!(kind("/proc/1/status"), PathType::Virtual("proc".into()));
+        assert_eq!(kind("/run/lock"), PathType::Virtual("tmpfs".into()));
+
+        let mounts = mountinfo_into_vec(
+            "40 1 0:40 / /tmp rw - tmpfs tmpfs rw\n\
+             41 1 0:41 / /dev/shm rw - tmpfs tmpfs rw\n\
+             42 1 0:42 / /sys/kernel/security rw - securityfs securityfs rw\n\
+             43 1 0:43 / /sys/fs/bpf rw - bpf bpf rw\n\
+             44 1 0:44 / /sys/fs/pstore rw - pstore pstore rw",
+        )
+        .unwrap();
+        let kinds: Vec<_> = mounts.iter().map(|m| m.classify().unwrap().0).collect();
+        assert_eq!(
+            kinds,
+            [
+                PathType::RamDisk,
+                PathType::Virtual("tmpfs".into()),
+                PathType::Virtual("securityfs".into()),
+                PathType::Virtual("bpf".into()),
+                PathType::Virtual("pstore".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn fixture_verbose_candidates() {
+        let sources = fixture_sources();
+        let path = Path::new("/mnt/nfs/nested/file");
+        let (info, candidates) = inspect_path_verbose_from(path, &sources).unwrap();
+
+        let points: Vec<&Path> = candidates
+            .iter()
+            .map(|c| c.mount.as_ref().unwrap().mount_point())
+            .collect();
+        assert_eq!(points, ["/", "/mnt/nfs", "/mnt/nfs/nested"].map(Path::new));
+        assert_eq!(candidates[0].kind(), &PathType::Fixed);
+        assert_eq!(candidates[1].remote_type(), Some(&RemoteType::NFS));
+        assert_eq!(info, inspect_path_from(path, &sources).unwrap());
+        assert_eq!(candidates.last(), Some(&info));
+        assert_eq!(info.mount_point(), Some(Path::new("/mnt/nfs/nested")));
+    }
+
+    #[test]
+    fn fixture_list_volumes() {
+        let sources = fixture_sources();
+        let points = |filter: &VolumeFilter| -> Vec<PathBuf> {
+            list_volumes_from(filter, &sources)
+                .unwrap()
+                .into_iter()
+                .map(|v| v.path().to_path_buf())
+                .collect()
+        };
+
+        let all = points(&VolumeFilter::new());
+        assert!(all.contains(&PathBuf::from("/")));
+        assert!(all.contains(&PathBuf::from("/mnt/nfs")));
+        assert!(!all.contains(&PathBuf::from("/proc")));
+        assert!(!all.contains(&PathBuf::from("/run")));
+        assert!(!all.contains(&PathBuf::from("/var/lib/docker/overlay2/abc/merged")));
+
+        let with_virtual = points(&VolumeFilter::new().include_virtual(true));
+        assert!(with_virtual.contains(&PathBuf::from("/proc")));
+
+        assert_eq!(
+            points(&VolumeFilter::new().kind(PathType::Remote)),
+            ["/mnt/nfs", "/mnt/smb", "/mnt/ssh", "/mnt/nfs/nested"].map(PathBuf::from)
+        );
+        assert_eq!(
+            points(&VolumeFilter::new().kind(PathType::Removable)),
+            [PathBuf::from("/media/usb")]
+        );
+    }
+
+    #[test]
+    fn bind_mounts_deduplicated() {
+        let dir = tempfile::tempdir().unwrap();
+        let mountinfo = dir.path().join("mountinfo");
+        fs::write(
+            &mountinfo,
+            "1 0 8:2 / / rw - ext4 /dev/sda2 rw\n\
+             2 1 8:2 /srv /mnt/srv rw - ext4 /dev/sda2 rw\n\
+             3 1 8:2 / /mnt/again rw - ext4 /dev/sda2 rw\n",
+        )
+        .unwrap();
+        let sources = Sources {
+            mountinfo,
+            sys: dir.path().join("sys"),
+        };
+        let points = |filter: &VolumeFilter| -> Vec<PathBuf> {
+            list_volumes_from(filter, &sources)
+                .unwrap()
+                .into_iter()
+                .map(|v| v.path().to_path_buf())
+                .collect()
+        };
+
+        assert_eq!(
+            points(&VolumeFilter::new()),
+            ["/", "/mnt/srv"].map(PathBuf::from)
+        );
+        let all = VolumeFilter::new().dedup_bind_mounts(false);
+        assert_eq!(points(&all).len(), 3);
+    }
+
+    #[test]
+    fn live_volumes_include_root() {
+        let volumes = list_volumes(&VolumeFilter::new()).unwrap();
+        assert!(volumes.iter().any(|v| v.path() == Path::new("/")));
+    }
+
+    #[test]
+    fn fixture_deepest_mount_wins() {
+        let sources = fixture_sources();
+        let info = inspect_path_from(Path::new("/mnt/nfs/nested/file"), &sources).unwrap();
+
+        assert_eq!(info.protocol_version().as_deref(), Some("3"));
+    }
+
+    #[test]
+    fn mountinfo_to_vec_virtual() {
+        let line =
+            "40 28 0:20 / /dev/mqueue rw,nosuid,nodev,noexec,relatime shared:15 - mqueue mqueue rw";
+        let right = mountinfo_into_vec(line).unwrap();
+
+        let device_number = DeviceNumber {
+            major: 0,
+            minor: 20,
+        };
+        let left = vec![MountInfo {
+            mount_id: 40,
+            parent_id: 28,
+            device_number,
+            fs_root: PathBuf::from("/"),
+            mount_point: PathBuf::from("/dev/mqueue"),
+            vfs_options: String::from("rw,nosuid,nodev,noexec,relatime"),
+            fs_type: String::from("mqueue"),
+            block_device: PathBuf::from("mqueue"),
+            mount_options: String::from("rw"),
+            line: String::from(line),
+            table: Table::default(),
         }];
 
         assert_eq!(left, right);
     }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn check_status_fast_probe() {
+        assert_eq!(check_status_fast(Path::new("/")), PathStatus::Mounted);
+        let missing = Path::new("/definitely/not/a/real/path");
+        assert_eq!(check_status_fast(missing), PathStatus::Disconnected);
+        assert_eq!(check_status_fast(missing), crate::check_status(missing));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn diff_mount_tables() {
+        use crate::{MountEvent, MountEventKind};
+
+        let old = mountinfo_into_vec(FIXTURE).unwrap();
+        let mut new: Vec<MountInfo> = old
+            .iter()
+            .filter(|m| m.mount_point != Path::new("/mnt/smb"))
+            .cloned()
+            .collect();
+        let usb = new
+            .iter_mut()
+            .find(|m| m.mount_point == Path::new("/media/usb"))
+            .unwrap();
+        usb.mount_options = usb.mount_options.replacen("rw", "ro", 1);
+        new.extend(mountinfo_into_vec("200 32 0:90 / /mnt/scratch rw - tmpfs tmpfs rw").unwrap());
+
+        let event = |kind, mount_point: &str, fs_type: &str| MountEvent {
+            kind,
+            mount_point: mount_point.into(),
+            fs_type: Some(fs_type.into()),
+        };
+        assert_eq!(
+            diff_mounts(&old, &new),
+            vec![
+                event(MountEventKind::Removed, "/mnt/smb", "cifs"),
+                event(MountEventKind::Changed, "/media/usb", "vfat"),
+                event(MountEventKind::Added, "/mnt/scratch", "tmpfs"),
+            ]
+        );
+        assert!(diff_mounts(&new, &new).is_empty());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn mount_events_shut_down() {
+        let (events, rx) = crate::MountEvents::spawn().unwrap();
+        drop(events);
+        assert!(rx.recv().is_err());
+    }
+
+    #[test]
+    fn classify_mount_directly() {
+        let mounts = mountinfo_into_vec(FIXTURE).unwrap();
+        let classify = |point: &str| {
+            mounts
+                .iter()
+                .find(|m| m.mount_point == Path::new(point))
+                .unwrap()
+                .classify()
+                .unwrap()
+        };
+
+        assert_eq!(
+            classify("/mnt/nfs"),
+            (PathType::Remote, Some(RemoteType::NFS))
+        );
+        assert_eq!(
+            classify("/mnt/smb"),
+            (PathType::Remote, Some(RemoteType::SMB))
+        );
+        assert_eq!(classify("/run"), (PathType::Virtual("tmpfs".into()), None));
+        assert_eq!(classify("/proc"), (PathType::Virtual("proc".into()), None));
+
+        let shares = mountinfo_into_vec(
+            "60 1 0:60 / /mnt/host rw,relatime - virtiofs myfs rw\n\
+             61 1 0:61 / /mnt/wsl rw,relatime - 9p drvfs rw,trans=virtio",
+        )
+        .unwrap();
+        assert_eq!(
+            shares[0].classify().unwrap(),
+            (PathType::Remote, Some(RemoteType::VirtioFs))
+        );
+        assert_eq!(
+            shares[1].classify().unwrap(),
+            (PathType::Remote, Some(RemoteType::NinePfs))
+        );
+    }
+
+    #[test]
+    fn same_filesystem_checks() {
+        let tmp = std::env::temp_dir();
+        assert!(same_filesystem(&tmp, &tmp).unwrap());
+        assert!(same_filesystem(&tmp, tmp.join("inspect_path_missing/file.txt")).unwrap());
+        if Path::new("/proc/self").exists() {
+            assert!(!same_filesystem(&tmp, Path::new("/proc")).unwrap());
+        }
+    }
+
+    #[test]
+    fn btrfs_subvolumes() {
+        let parse = |line: &str| mountinfo_into_vec(line).unwrap().remove(0);
+        let home = parse(
+            "40 1 0:35 /@home /home rw,relatime - btrfs /dev/sda2 rw,ssd,subvolid=257,subvol=/@home",
+        );
+        let by_id = parse("41 1 0:35 / /data rw - btrfs /dev/sda2 rw,subvolid=300");
+        let ext4 = parse("42 1 8:3 / /srv rw - ext4 /dev/sda3 rw,subvol=/@x");
+
+        assert_eq!(home.btrfs_subvolume(), Some("/@home"));
+        assert_eq!(by_id.btrfs_subvolume(), Some("300"));
+        assert_eq!(ext4.btrfs_subvolume(), None);
+    }
+
+    #[test]
+    fn volume_label_from_by_label_links() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let by_label = dir.join("by-label");
+        fs::create_dir_all(&by_label).unwrap();
+        fs::write(dir.join("sdb1"), "").unwrap();
+        fs::write(dir.join("sdc1"), "").unwrap();
+        std::os::unix::fs::symlink(dir.join("sdb1"), by_label.join("KINGSTON\\x2016GB")).unwrap();
+
+        let line = |dev: &Path| format!("50 1 8:17 / /media/usb rw - vfat {} rw", dev.display());
+        let usb = mountinfo_into_vec(&line(&dir.join("sdb1")))
+            .unwrap()
+            .remove(0);
+        let other = mountinfo_into_vec(&line(&dir.join("sdc1")))
+            .unwrap()
+            .remove(0);
+
+        assert_eq!(
+            device_link_in(&usb, &by_label).as_deref(),
+            Some("KINGSTON 16GB")
+        );
+        assert_eq!(device_link_in(&other, &by_label), None);
+        assert_eq!(unescape_udev(r"a\x2fb\xzz"), r"a/b\xzz");
+    }
+
+    #[test]
+    fn identity_parts_prefer_uuid() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let by_uuid = dir.join("by-uuid");
+        fs::create_dir_all(&by_uuid).unwrap();
+        fs::write(dir.join("sdb1"), "").unwrap();
+        std::os::unix::fs::symlink(dir.join("sdb1"), by_uuid.join("1234-ABCD")).unwrap();
+
+        let sdb1 = dir.join("sdb1");
+        let mounts = mountinfo_into_vec(&format!(
+            "50 1 8:17 / /media/usb rw - vfat {} rw\n\
+             51 1 8:33 / /media/other rw - ext4 /dev/sdc1 rw\n\
+             52 1 0:50 / /mnt/nfs rw - nfs4 srv:/export rw",
+            sdb1.display()
+        ))
+        .unwrap();
+        let parts: Vec<_> = mounts
+            .iter()
+            .map(|m| identity_parts_in(m, &by_uuid))
+            .collect();
+
+        assert_eq!(parts[0], ["uuid", "vfat", "1234-ABCD", "/"]);
+        assert_eq!(parts[1], ["device", "ext4", "/dev/sdc1", "/"]);
+        assert_eq!(parts[2], ["remote", "nfs4", "srv:/export", "/"]);
+
+        // a source that merely resolves relative to the cwd isn't a device
+        let relative = mountinfo_into_vec("53 1 0:24 / /proc rw - proc proc rw")
+            .unwrap()
+            .remove(0);
+        assert_eq!(device_link_in(&relative, &by_uuid), None);
+        assert_eq!(device_link_in(&mounts[2], &by_uuid), None);
+    }
+
+    #[test]
+    fn sector_sizes_from_sysfs() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let disk = dir.join("sys/devices/sda");
+        fs::create_dir_all(disk.join("queue")).unwrap();
+        fs::create_dir_all(disk.join("sda1")).unwrap();
+        fs::write(disk.join("queue/logical_block_size"), "512\n").unwrap();
+        fs::write(disk.join("queue/physical_block_size"), "4096\n").unwrap();
+        fs::create_dir_all(dir.join("sys/dev/block")).unwrap();
+        std::os::unix::fs::symlink(disk.join("sda1"), dir.join("sys/dev/block/8:1")).unwrap();
+        std::os::unix::fs::symlink(&disk, dir.join("sys/dev/block/8:0")).unwrap();
+
+        let mount = |dev: &str| {
+            let line = format!("50 1 {dev} / /data rw - ext4 /dev/sda1 rw");
+            mountinfo_into_vec(&line).unwrap().remove(0)
+        };
+        let sys = dir.join("sys");
+        assert_eq!(sector_sizes_in(&mount("8:1"), &sys), Some((512, 4096)));
+        assert_eq!(sector_sizes_in(&mount("8:0"), &sys), Some((512, 4096)));
+        assert_eq!(sector_sizes_in(&mount("0:42"), &sys), None);
+    }
+
+    #[test]
+    fn disk_images_from_sysfs() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let loop0 = dir.join("sys/devices/loop0");
+        fs::create_dir_all(loop0.join("loop")).unwrap();
+        fs::create_dir_all(loop0.join("loop0p1")).unwrap();
+        fs::create_dir_all(dir.join("sys/devices/sda")).unwrap();
+        fs::create_dir_all(dir.join("sys/dev/block")).unwrap();
+        let link = |target: &str, dev: &str| {
+            let at = dir.join("sys/dev/block").join(dev);
+            std::os::unix::fs::symlink(dir.join("sys/devices").join(target), at).unwrap();
+        };
+        link("loop0", "7:0");
+        link("loop0/loop0p1", "259:0");
+        link("sda", "8:0");
+
+        let mount = |dev: &str| {
+            let line = format!("50 1 {dev} / /data rw - ext4 /dev/x rw");
+            mountinfo_into_vec(&line).unwrap().remove(0)
+        };
+        let sys = dir.join("sys");
+        assert_eq!(is_disk_image_in(&mount("7:0"), &sys), Some(true));
+        assert_eq!(is_disk_image_in(&mount("259:0"), &sys), Some(true));
+        assert_eq!(is_disk_image_in(&mount("8:0"), &sys), Some(false));
+        assert_eq!(is_disk_image_in(&mount("0:42"), &sys), Some(false));
+        assert_eq!(is_disk_image_in(&mount("8:16"), &sys), None);
+    }
+
+    #[test]
+    fn diskutil_protocol_of_image() {
+        let info = "   Device Identifier:         disk4s1\n\
+                    \x20  Device Node:               /dev/disk4s1\n\
+                    \x20  Protocol:                  Disk Image\n\
+                    \x20  Removable Media:           Removable\n";
+        assert_eq!(diskutil_protocol(info), Some("Disk Image"));
+        assert_eq!(
+            diskutil_protocol("   Protocol:                  Apple Fabric\n"),
+            Some("Apple Fabric")
+        );
+        assert_eq!(diskutil_protocol("Could not find disk: disk9\n"), None);
+        assert_eq!(
+            diskutil_is_image(Path::new("//user@server/share")),
+            Some(false)
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn iscsi_from_sysfs() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let sys = dir.join("sys");
+        let sdb = sys.join("devices/platform/host3/session1/target3:0:0/3:0:0:0/block/sdb");
+        let sdc = sys.join("devices/platform/host4/target4:0:0/4:0:0:0/block/sdc");
+        let sda =
+            sys.join("devices/pci0000:00/0000:00:17.0/ata1/host0/target0:0:0/0:0:0:0/block/sda");
+        for disk in [&sdb, &sdc, &sda] {
+            fs::create_dir_all(disk).unwrap();
+        }
+        fs::create_dir_all(sdb.join("sdb1")).unwrap();
+        fs::create_dir_all(sys.join("class/iscsi_session/session1")).unwrap();
+        for (host, driver) in [("host4", "iscsi_tcp"), ("host0", "ahci")] {
+            let host = sys.join("class/scsi_host").join(host);
+            fs::create_dir_all(&host).unwrap();
+            fs::write(host.join("proc_name"), format!("{driver}\n")).unwrap();
+        }
+        fs::create_dir_all(sys.join("dev/block")).unwrap();
+        let link = |target: &Path, dev: &str| {
+            std::os::unix::fs::symlink(target, sys.join("dev/block").join(dev)).unwrap();
+        };
+        link(&sdb.join("sdb1"), "8:17");
+        link(&sdc, "8:32");
+        link(&sda, "8:0");
+
+        let mount = |dev: &str| {
+            let line = format!("50 1 {dev} / /data rw - ext4 /dev/x rw");
+            mountinfo_into_vec(&line).unwrap().remove(0)
+        };
+        assert_eq!(is_iscsi_in(&mount("8:17"), &sys), Some(true));
+        assert_eq!(is_iscsi_in(&mount("8:32"), &sys), Some(true));
+        assert_eq!(is_iscsi_in(&mount("8:0"), &sys), Some(false));
+        assert_eq!(is_iscsi_in(&mount("0:42"), &sys), Some(false));
+        assert_eq!(is_iscsi_in(&mount("8:48"), &sys), None);
+
+        // multipath over sdb and sda, LVM on top of it, and a local RAID
+        let virt = sys.join("devices/virtual/block");
+        let slave = |holder: &str, dev: &Path| {
+            let slaves = virt.join(holder).join("slaves");
+            fs::create_dir_all(&slaves).unwrap();
+            let name = dev.file_name().unwrap();
+            std::os::unix::fs::symlink(dev, slaves.join(name)).unwrap();
+        };
+        slave("dm-0", &sdb);
+        slave("dm-0", &sda);
+        slave("dm-1", &virt.join("dm-0"));
+        slave("md0", &sda);
+        fs::create_dir_all(virt.join("md0/md0p1")).unwrap();
+        fs::write(virt.join("md0/md0p1/partition"), "1\n").unwrap();
+        link(&virt.join("dm-1"), "253:1");
+        link(&virt.join("md0/md0p1"), "259:1");
+        assert_eq!(is_iscsi_in(&mount("253:1"), &sys), Some(true));
+        assert_eq!(is_iscsi_in(&mount("259:1"), &sys), Some(false));
+
+        slave("md0", &sdc);
+        assert_eq!(is_iscsi_in(&mount("259:1"), &sys), Some(true));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn quota_limits() {
+        let dq = |hard: u64, soft: u64, valid: u32| libc::dqblk {
+            dqb_bhardlimit: hard,
+            dqb_bsoftlimit: soft,
+            dqb_curspace: 4096,
+            dqb_ihardlimit: 0,
+            dqb_isoftlimit: 0,
+            dqb_curinodes: 1,
+            dqb_btime: 0,
+            dqb_itime: 0,
+            dqb_valid: valid,
+        };
+        let limited = |used, limit| Some(crate::Quota { used, limit });
+
+        assert_eq!(
+            quota_from(&dq(100, 80, libc::QIF_ALL)),
+            limited(4096, 102_400)
+        );
+        assert_eq!(quota_from(&dq(0, 80, libc::QIF_ALL)), limited(4096, 81_920));
+        assert_eq!(quota_from(&dq(0, 0, libc::QIF_ALL)), None);
+        assert_eq!(quota_from(&dq(100, 80, libc::QIF_USAGE)), None);
+        assert_eq!(crate::Quota { used: 5, limit: 3 }.remaining(), 0);
+
+        // a path on a filesystem without a block device has no quota
+        let proc = mountinfo_into_vec("27 1 0:24 / /proc rw - proc proc rw").unwrap();
+        assert_eq!(quota(&proc[0]), None);
+    }
+
+    #[test]
+    fn parse_line_matches_table() {
+        let table = mountinfo_into_vec(FIXTURE).unwrap();
+        for (line, mount) in FIXTURE.lines().zip(&table) {
+            assert_eq!(&MountInfo::parse_line(line).unwrap(), mount);
+        }
+        let nfs = &table[3];
+        assert_eq!(
+            nfs.device_number(),
+            DeviceNumber {
+                major: 0,
+                minor: 50
+            }
+        );
+        assert_eq!(nfs.parent_id(), 32);
+
+        assert_eq!(nfs.vfs_options(), "rw,relatime");
+
+        let err = MountInfo::parse_line("32 2 259:2 / / rw").unwrap_err();
+        assert!(matches!(err, InspectPathError::ParseGen { .. }));
+        assert_eq!(err.path(), Some(Path::new("")));
+    }
+
+    /// Counts the bytes pulled through it.
+    struct Counting<R> {
+        inner: R,
+        read: std::rc::Rc<std::cell::Cell<usize>>,
+    }
+
+    impl<R: std::io::Read> std::io::Read for Counting<R> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let n = self.inner.read(buf)?;
+            self.read.set(self.read.get() + n);
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn mounts_parse_lazily() {
+        let mut table = String::new();
+        for id in 0..10_000 {
+            table += &format!("{id} 1 0:{id} / /run/c/{id} rw - overlay overlay rw\n");
+        }
+        table.insert_str(table.find("\n").unwrap() + 1, "garbage\n");
+        let read = std::rc::Rc::default();
+        let reader = Counting {
+            inner: table.as_bytes(),
+            read: std::rc::Rc::clone(&read),
+        };
+
+        let mut mounts = mounts_from(BufReader::new(reader), PathBuf::from("fixture"));
+        assert_eq!(mounts.next().unwrap().unwrap().mount_id(), 0);
+        // a bad line is yielded in place, not fatal
+        assert!(matches!(
+            mounts.next(),
+            Some(Err(InspectPathError::ParseInt { .. }))
+        ));
+        assert_eq!(mounts.next().unwrap().unwrap().mount_id(), 1);
+        assert!(read.get() < table.len() / 10, "read {} bytes", read.get());
+
+        assert_eq!(mounts.count(), 9_998);
+    }
+
+    #[test]
+    fn escaped_names_and_limits() {
+        let usb = MountInfo::parse_line(
+            r"40 32 8:17 / /media/my\040disk rw,noatime shared:7 master:1 - vfat /dev/sdb1 rw",
+        )
+        .unwrap();
+        assert_eq!(usb.mount_point(), Path::new("/media/my disk"));
+        assert_eq!(usb.atime_policy(), Some(AtimePolicy::NoAtime));
+
+        // ` - ` inside a name is escaped, and only a field of its own separates
+        let dash = MountInfo::parse_line(r"41 32 8:2 / /mnt/a\040-\040b rw - ext4 /dev/sdb2 rw");
+        assert_eq!(dash.unwrap().mount_point(), Path::new("/mnt/a - b"));
+        // the kernel doesn't escape a no-break space
+        let nbsp = MountInfo::parse_line("42 32 8:3 / /mnt/a\u{a0}b rw - ext4 /dev/sdb3 rw");
+        assert_eq!(nbsp.unwrap().mount_point(), Path::new("/mnt/a\u{a0}b"));
+
+        let many = format!(
+            "43 32 8:4 / /mnt rw{} - ext4 /dev/sdb4 rw",
+            " x:1".repeat(64)
+        );
+        assert!(matches!(
+            MountInfo::parse_line(&many),
+            Err(InspectPathError::TooLarge {
+                limit: MAX_MOUNTINFO_FIELDS,
+                ..
+            })
+        ));
+
+        let long = format!(
+            "{}\n{}\n",
+            "7".repeat(MAX_MOUNTINFO_LINE + 1),
+            FIXTURE.lines().next().unwrap()
+        );
+        let mut mounts = mounts_from(long.as_bytes(), PathBuf::from("fixture"));
+        assert!(matches!(
+            mounts.next(),
+            Some(Err(InspectPathError::TooLarge {
+                limit: MAX_MOUNTINFO_LINE,
+                ..
+            }))
+        ));
+        assert_eq!(mounts.next().unwrap().unwrap().mount_id(), 32);
+        assert!(mounts.next().is_none());
+    }
+
+    mod props {
+        use super::*;
+        use proptest::{collection::vec, prelude::*};
+
+        /// An absolute path whose names include what the kernel escapes.
+        fn name() -> impl Strategy<Value = PathBuf> {
+            vec("[a-z0-9 \t\n\\\\\u{a0}é-]{1,8}", 0..4)
+                .prop_map(|parts| PathBuf::from(format!("/{}", parts.join("/"))))
+        }
+
+        proptest! {
+            #[test]
+            fn mountinfo_round_trips(
+                (mount_id, parent_id, major, minor) in any::<(u32, u32, u32, u32)>(),
+                fs_root in name(),
+                mount_point in name(),
+                fs_type in "[a-z0-9.]{1,10}",
+                source in "[a-zA-Z0-9:/@. -]{1,16}",
+                vfs in "[a-z,]{1,20}",
+                optional in vec("(shared|master|propagate_from):[0-9]{1,3}", 0..4),
+                options in "[a-z0-9=,]{1,20}",
+            ) {
+                let line = format!(
+                    "{mount_id} {parent_id} {major}:{minor} {} {} {vfs}{} - {} {} {options}",
+                    escape_octal(&fs_root),
+                    escape_octal(&mount_point),
+                    optional.iter().map(|o| format!(" {o}")).collect::<String>(),
+                    escape_octal(Path::new(&fs_type)),
+                    escape_octal(Path::new(&source)),
+                );
+                let expected = MountInfo {
+                    mount_id,
+                    parent_id,
+                    device_number: DeviceNumber { major, minor },
+                    fs_root,
+                    mount_point,
+                    vfs_options: vfs.clone(),
+                    fs_type,
+                    block_device: PathBuf::from(source),
+                    mount_options: options,
+                    line: line.clone(),
+                    table: Table::default(),
+                };
+                let parsed = MountInfo::parse_line(&line).unwrap();
+                prop_assert_eq!(parsed.vfs_options(), vfs.as_str());
+                prop_assert_eq!(parsed, expected);
+            }
+
+            #[test]
+            fn parser_never_panics(
+                line in prop_oneof![any::<String>(), "[0-9a-z :/\\\\-]{0,80}"],
+            ) {
+                if let Ok(mount) = MountInfo::parse_line(&line) {
+                    let _ = (mount.atime_policy(), mount.is_read_only());
+                }
+            }
+
+            #[test]
+            fn reader_never_panics(bytes in vec(any::<u8>(), 0..512)) {
+                for mount in mounts_from(&bytes[..], PathBuf::from("fuzz")).take(600) {
+                    let _ = mount.map(|m| m.vfs_options().len());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn atime_policies() {
+        let policy = |vfs: &str| {
+            let line = format!("32 2 259:2 / / {vfs} shared:1 - ext4 /dev/sda2 rw");
+            MountInfo::parse_line(&line).unwrap().atime_policy()
+        };
+        assert_eq!(policy("rw,relatime"), Some(AtimePolicy::RelAtime));
+        assert_eq!(policy("rw,nosuid,noatime"), Some(AtimePolicy::NoAtime));
+        assert_eq!(
+            policy("rw,noatime,strictatime"),
+            Some(AtimePolicy::StrictAtime)
+        );
+        // nodiratime only covers directories
+        assert_eq!(policy("rw,nodiratime"), None);
+        assert_eq!(policy("rw"), None);
+    }
+
+    #[test]
+    fn forced_read_only() {
+        let mount = |vfs: &str, fs_type: &str, sb: &str| {
+            let line = format!("50 1 8:1 / /data {vfs} - {fs_type} /dev/sda1 {sb}");
+            mountinfo_into_vec(&line).unwrap().remove(0)
+        };
+        let failed = mount("rw,relatime", "ext4", "ro,errors=remount-ro");
+        assert!(failed.is_forced_read_only());
+        assert!(failed.is_read_only());
+        assert!(mount("rw", "btrfs", "ro,space_cache").is_forced_read_only());
+        // read-only on purpose
+        assert!(!mount("ro,relatime", "ext4", "ro").is_forced_read_only());
+        assert!(!mount("rw", "ext4", "rw,errors=remount-ro").is_forced_read_only());
+        assert!(!mount("rw", "iso9660", "ro").is_forced_read_only());
+        assert!(!mount("rw", "vfat", "ro").is_forced_read_only());
+    }
+
+    #[test]
+    fn boot_partitions() {
+        let mount = |point: &str, fs_type: &str| {
+            let line = format!("50 1 8:1 / {point} rw - {fs_type} /dev/sda1 rw");
+            mountinfo_into_vec(&line).unwrap().remove(0)
+        };
+        assert!(is_boot_partition(&mount("/boot", "ext4")));
+        assert!(is_boot_partition(&mount("/boot/efi", "vfat")));
+        assert!(is_boot_partition(&mount("/efi", "vfat")));
+        assert!(is_boot_partition(&mount("/boot/efi/EFI", "vfat")));
+        assert!(!is_boot_partition(&mount("/boot/grub", "ext4")));
+        assert!(!is_boot_partition(&mount("/bootstrap", "vfat")));
+        assert!(!is_boot_partition(&mount("/media/usb", "vfat")));
+        assert!(!is_boot_partition(&mount("/", "ext4")));
+    }
+
+    #[test]
+    fn space_of_temp_dir() {
+        let space = space_info(&std::env::temp_dir()).unwrap();
+        assert!(space.total > 0);
+        assert!(space.available <= space.free && space.free <= space.total);
+
+        match space_info(Path::new("/inspect_path/does/not/exist")) {
+            Err(InspectPathError::Unavailable { status, .. }) => {
+                assert_eq!(status, PathStatus::Disconnected)
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn max_filename_length_of_temp_dir() {
+        let info = inspect_path(std::env::temp_dir()).unwrap();
+        assert!(info.max_filename_length().is_some_and(|n| n >= 14));
+        assert_eq!(
+            max_filename_length(Path::new("/inspect_path/does/not/exist")),
+            None
+        );
+    }
+
+    #[test]
+    fn hard_and_soft_nfs_mounts() {
+        let parse = |line: &str| mountinfo_into_vec(line).unwrap().remove(0);
+        let hard = parse("90 32 0:50 / /mnt/a rw - nfs4 srv:/a rw,vers=4.2,hard,proto=tcp");
+        let soft = parse("91 32 0:51 / /mnt/b rw - nfs srv:/b rw,vers=3,soft,timeo=600");
+        let default = parse("92 32 0:52 / /mnt/c rw - nfs srv:/c rw,vers=3");
+        let smb = parse("93 32 0:53 / /mnt/d rw - cifs //srv/d rw,soft");
+
+        assert_eq!(hard.is_hard_mount(), Some(true));
+        assert_eq!(soft.is_hard_mount(), Some(false));
+        assert_eq!(default.is_hard_mount(), Some(true));
+        assert_eq!(smb.is_hard_mount(), None);
+    }
+
+    #[test]
+    fn protocol_version_from_options() {
+        let cifs = "98 32 0:52 / /mnt/share rw,relatime shared:60 - cifs //srv/share rw,vers=3.1.1,cache=strict,username=bob";
+        let nfs = "99 32 0:53 / /mnt/nfs rw,relatime shared:61 - nfs4 srv:/export rw,vers=4.2,rsize=1048576,hard,proto=tcp";
+        let local =
+            "32 2 259:2 / / rw,relatime shared:1 - ext4 /dev/nvme0n1p2 rw,errors=remount-ro";
+
+        let cifs = &mountinfo_into_vec(cifs).unwrap()[0];
+        let nfs = &mountinfo_into_vec(nfs).unwrap()[0];
+        let local = &mountinfo_into_vec(local).unwrap()[0];
+
+        assert_eq!(cifs.protocol_version(), Some("3.1.1"));
+        assert_eq!(nfs.protocol_version(), Some("4.2"));
+        assert_eq!(local.protocol_version(), None);
+    }
 }
@@ -0,0 +1,764 @@
+//! Mounting network shares, behind the `mount` feature.
+use super::MountTable;
+#[cfg(target_os = "linux")]
+use crate::credentials::{CredentialStore, Credentials};
+use crate::{InspectPathError, PathStatus};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+#[cfg(target_os = "linux")]
+use zeroize::Zeroizing;
+
+// stderr of `mount` and `systemd-mount` when the caller lacks privileges
+const PERMISSION_MESSAGES: &[&str] = &[
+    "must be superuser",
+    "only root",
+    "permission denied",
+    "operation not permitted",
+    "access denied",
+    "authentication required",
+];
+
+/// How [`try_mount_if_needed_with`] mounts a share. **Unix only.**
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MountMethod {
+    /// The `mount(2)` system call, like `mount_path`. Needs root or
+    /// `CAP_SYS_ADMIN`, and a remote; Linux only.
+    #[default]
+    Syscall,
+    /// Runs `mount(8)`. Without a remote this is `mount <target>`, which
+    /// mounts the target's `/etc/fstab` entry and works unprivileged for
+    /// entries with the `user` option; with one,
+    /// `mount -t <type> -o <options> <remote> <target>`, which uses the
+    /// `mount.nfs` and `mount.cifs` helpers.
+    Command,
+    /// Runs `systemd-mount <remote> <target>`, which polkit may allow
+    /// without root. Needs a remote.
+    SystemdMount,
+}
+
+/// What [`try_mount_if_needed_with`] mounts where, and how. **Unix only.**
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use inspect_path::{MountMethod, MountSpec, try_mount_if_needed_with};
+///
+/// // mount /mnt/data from its fstab entry, as an unprivileged user
+/// let fstab = MountSpec::new().method(MountMethod::Command);
+/// try_mount_if_needed_with("/mnt/data/report.csv", &fstab)?;
+///
+/// let nfs = MountSpec::new()
+///     .remote("nas:/export/media")
+///     .fs_type("nfs4")
+///     .options("ro,vers=4.2")
+///     .target("/mnt/media");
+/// try_mount_if_needed_with("/mnt/media", &nfs)?;
+/// # Ok::<(), inspect_path::InspectPathError>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MountSpec {
+    remote: Option<String>,
+    fs_type: Option<String>,
+    options: Option<String>,
+    target: Option<PathBuf>,
+    method: MountMethod,
+}
+
+impl MountSpec {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The share to mount: `server:/export` for NFS, `//server/share` for
+    /// SMB. An empty remote fails with [`InspectPathError::NoRemote`] once a
+    /// mount is needed; leave it unset to mount the target's `/etc/fstab`
+    /// entry with [`MountMethod::Command`].
+    pub fn remote(mut self, remote: impl Into<String>) -> Self {
+        self.remote = Some(remote.into());
+        self
+    }
+
+    /// The filesystem type, e.g. `nfs4`. Defaults to `nfs` for
+    /// `server:/export` and `cifs` for `//server/share`.
+    pub fn fs_type(mut self, fs_type: impl Into<String>) -> Self {
+        self.fs_type = Some(fs_type.into());
+        self
+    }
+
+    /// Comma-separated mount options, e.g. `ro,vers=4.2`.
+    pub fn options(mut self, options: impl Into<String>) -> Self {
+        self.options = Some(options.into());
+        self
+    }
+
+    /// The directory to mount on, which must exist. Defaults to the deepest
+    /// existing directory of the path being checked, `/mnt/data` for
+    /// `/mnt/data/report.csv` while nothing is mounted there, but only if it
+    /// is empty: mounting would hide what is in it.
+    pub fn target(mut self, target: impl Into<PathBuf>) -> Self {
+        self.target = Some(target.into());
+        self
+    }
+
+    /// Defaults to [`MountMethod::Syscall`].
+    pub fn method(mut self, method: MountMethod) -> Self {
+        self.method = method;
+        self
+    }
+}
+
+/// Mounts `remote` on the directory `local` with the `mount(2)` system
+/// call, which needs root or `CAP_SYS_ADMIN`. **Linux only** on Unix.
+///
+/// On Windows, maps `remote` (`\\server\share`) to the drive `local`
+/// (`Z:`) with `WNetAddConnection2W`, using credentials from the
+/// `CredentialStore` if there are any for the server, and may prompt for
+/// credentials depending on system configuration.
+///
+/// On Linux, `remote` is `server:/export`, mounted as `nfs`, or
+/// `//server/share`, mounted as `cifs`, with no options beyond the server's
+/// address, which is resolved here as `mount.nfs` and `mount.cifs` would, and
+/// for `cifs` the credentials in the `CredentialStore`.
+/// Use [`try_mount_if_needed_with`] for other types and options, or for the
+/// helpers themselves.
+///
+/// # Errors
+///
+/// - [`InspectPathError::NotPermitted`] without the privileges to mount.
+/// - [`InspectPathError::MountFailed`] if the type of `remote` can't be told
+///   or the kernel refuses the mount.
+/// - On Windows, [`InspectPathError::Sys`] with the Win32 error code.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use inspect_path::mount_path;
+///
+/// #[cfg(windows)]
+/// mount_path("Z:", r"\\server\share").unwrap();
+/// #[cfg(target_os = "linux")]
+/// mount_path("/mnt/share", "//server/share").unwrap();
+/// ```
+#[cfg(target_os = "linux")]
+pub fn mount_path(
+    local: impl AsRef<std::ffi::OsStr>,
+    remote: impl AsRef<std::ffi::OsStr>,
+) -> Result<(), InspectPathError> {
+    use crate::PathInspector;
+    crate::SystemInspector.mount(local.as_ref(), remote.as_ref())
+}
+
+/// [`mount_path`] for [`SystemInspector`](crate::SystemInspector).
+#[cfg(target_os = "linux")]
+pub(crate) fn mount_share(
+    local: &std::ffi::OsStr,
+    remote: &std::ffi::OsStr,
+) -> Result<(), InspectPathError> {
+    let target = Path::new(local);
+    let remote = remote.to_string_lossy();
+    let fs_type = infer_fs_type(target, &remote)?;
+    mount_syscall(target, &remote, fs_type, None)
+}
+
+/// Mounts the share a path lives on if it isn't mounted yet.
+///
+/// On Windows, `path` is inspected and, if its drive doesn't exist, `remote`
+/// (`\\server\share`) is mapped to the drive letter of `path`, like
+/// [`mount_path`](crate::mount_path).
+///
+/// On Unix, `remote` is `server:/export` or `//server/share`, mounted with
+/// `mount(2)` on the deepest existing directory of `path` unless that is
+/// already a mount point or on a network filesystem. This is
+/// [`try_mount_if_needed_with`] with a [`MountSpec`] of just `remote`.
+///
+/// On both, an empty `remote` fails with [`InspectPathError::NoRemote`] if
+/// a mount is needed.
+///
+/// # Errors
+///
+/// - On Windows, the inspection error, or the error from mapping the drive.
+/// - On Unix, see [`try_mount_if_needed_with`].
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use inspect_path::try_mount_if_needed;
+///
+/// #[cfg(windows)]
+/// try_mount_if_needed(r"Z:\partcount.csv", r"\\server\share")?;
+/// #[cfg(unix)]
+/// try_mount_if_needed("/mnt/share/partcount.csv", "//server/share")?;
+/// # Ok::<(), inspect_path::InspectPathError>(())
+/// ```
+pub fn try_mount_if_needed(
+    path: impl AsRef<Path>,
+    remote: impl AsRef<Path>,
+) -> Result<(), InspectPathError> {
+    let remote = remote.as_ref().to_string_lossy();
+    try_mount_if_needed_with(path, &MountSpec::new().remote(remote))
+}
+
+/// Mounts what `spec` describes on the directory `path` lives in, unless
+/// something is mounted there already. **Unix only.**
+///
+/// The target is [`MountSpec::target`], or else the deepest existing
+/// directory of `path` if that is empty. Nothing is done if the target is a
+/// mount point or on a network filesystem: the share is there, even if
+/// `path` isn't on it (yet). A mount there that no longer answers (`ESTALE`,
+/// or a FUSE daemon that died) doesn't count; on Linux it is detached first
+/// and the share mounted afresh.
+///
+/// On Linux, CIFS mounts with [`MountMethod::Syscall`] and
+/// [`MountMethod::Command`] use the credentials stored for the server in the
+/// `CredentialStore` unless the options name a user or credentials file.
+///
+/// # Errors
+///
+/// - [`InspectPathError::InvalidPath`] for an empty path, a target that
+///   isn't a directory, or, without [`MountSpec::target`], a deepest
+///   existing directory that isn't empty.
+/// - [`InspectPathError::NoRemote`] if a mount is needed but `spec` has no
+///   remote and its method needs one.
+/// - [`InspectPathError::NotPermitted`] without the privileges to mount.
+/// - [`InspectPathError::MountFailed`] if the system call or command
+///   fails, with its error output.
+/// - [`InspectPathError::Unsupported`] for [`MountMethod::Syscall`] outside
+///   Linux.
+/// - An error reading the mount table.
+pub fn try_mount_if_needed_with(
+    path: impl AsRef<Path>,
+    spec: &MountSpec,
+) -> Result<(), InspectPathError> {
+    let path = path.as_ref();
+    crate::platform::validate_path(path)?;
+    let target = match &spec.target {
+        Some(target) => target.clone(),
+        None => deepest_dir(path)
+            .ok_or_else(|| InspectPathError::InvalidPath(path.display().to_string()))?,
+    };
+    if is_mounted(&target)? {
+        return Ok(());
+    }
+    // only a target the caller named may be mounted over its contents
+    let usable = fs::read_dir(&target)
+        .is_ok_and(|mut entries| spec.target.is_some() || entries.next().is_none());
+    if !usable {
+        return Err(InspectPathError::InvalidPath(target.display().to_string()));
+    }
+    mount(&target, spec)
+}
+
+/// The deepest existing directory among `path` and its ancestors.
+fn deepest_dir(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .map(|p| {
+            if p.as_os_str().is_empty() {
+                Path::new(".")
+            } else {
+                p
+            }
+        })
+        .find(|p| match fs::metadata(p) {
+            Ok(meta) => meta.is_dir(),
+            // a mount that stopped answering is still the place to mount
+            Err(e) => matches!(e.raw_os_error(), Some(libc::ESTALE | libc::ENOTCONN)),
+        })
+        .map(Path::to_path_buf)
+}
+
+/// Whether a share that answers is mounted at or above `target` already.
+/// On Linux, a mount on `target` that doesn't answer is detached.
+fn is_mounted(target: &Path) -> Result<bool, InspectPathError> {
+    let table = MountTable::load()?;
+    let status = crate::check_status(target);
+    if matches!(status, PathStatus::Stale | PathStatus::Disconnected) {
+        // `Disconnected` is also a missing path, which isn't a mount point
+        #[cfg(target_os = "linux")]
+        {
+            let target = std::path::absolute(target).unwrap_or_else(|_| target.to_path_buf());
+            if table.mount_root(&target).as_deref() == Some(target.as_path()) {
+                unmount_syscall(&target, true)?;
+            }
+        }
+        return Ok(false);
+    }
+    let Ok(target) = fs::canonicalize(target) else {
+        // a missing target can't be mounted on
+        return Ok(false);
+    };
+    Ok(table.mount_root(&target).as_deref() == Some(target.as_path()) || table.is_network(&target))
+}
+
+fn mount(target: &Path, spec: &MountSpec) -> Result<(), InspectPathError> {
+    let no_remote = || InspectPathError::NoRemote(target.to_path_buf());
+    let remote = match spec.remote.as_deref() {
+        Some("") => return Err(no_remote()),
+        remote => remote,
+    };
+    match spec.method {
+        MountMethod::Syscall => {
+            let remote = remote.ok_or_else(no_remote)?;
+            let fs_type = match &spec.fs_type {
+                Some(fs_type) => fs_type,
+                None => infer_fs_type(target, remote)?,
+            };
+            #[cfg(target_os = "linux")]
+            {
+                mount_syscall(target, remote, fs_type, spec.options.as_deref())
+            }
+            #[cfg(not(target_os = "linux"))]
+            {
+                let _ = fs_type;
+                Err(InspectPathError::Unsupported)
+            }
+        }
+        MountMethod::Command => {
+            let mut command = Command::new("mount");
+            if let Some(fs_type) = &spec.fs_type {
+                command.args(["-t", fs_type]);
+            }
+            #[cfg_attr(not(target_os = "linux"), allow(unused_mut))]
+            let mut options = spec.options.clone();
+            // mount.cifs reads the password from the environment, keeping it
+            // off the command line
+            #[cfg(target_os = "linux")]
+            if let Some(remote) = remote
+                && let Some(fs_type) = spec
+                    .fs_type
+                    .as_deref()
+                    .or_else(|| infer_fs_type(target, remote).ok())
+                && let Some(credentials) = stored_credentials(remote, fs_type, options.as_deref())
+            {
+                let user = user_options(&credentials);
+                options = Some(match options {
+                    Some(options) if !options.is_empty() => format!("{user},{options}"),
+                    _ => user,
+                });
+                command.env("PASSWD", credentials.password.as_str());
+            }
+            if let Some(options) = &options {
+                command.args(["-o", options]);
+            }
+            command.args(remote).arg(target);
+            run(command, target)
+        }
+        MountMethod::SystemdMount => {
+            let remote = remote.ok_or_else(no_remote)?;
+            let mut command = Command::new("systemd-mount");
+            if let Some(fs_type) = &spec.fs_type {
+                command.arg(format!("--type={fs_type}"));
+            }
+            if let Some(options) = &spec.options {
+                command.arg(format!("--options={options}"));
+            }
+            command.arg(remote).arg(target);
+            run(command, target)
+        }
+    }
+}
+
+/// `nfs` for `server:/export`, `cifs` for `//server/share`.
+fn infer_fs_type(target: &Path, remote: &str) -> Result<&'static str, InspectPathError> {
+    if remote.starts_with("//") {
+        Ok("cifs")
+    } else if remote.contains(":/") {
+        Ok("nfs")
+    } else {
+        Err(InspectPathError::MountFailed {
+            path: target.to_path_buf(),
+            msg: format!("can't tell the filesystem type of '{remote}'"),
+        })
+    }
+}
+
+/// Runs a mount command, mapping its failure output to an error.
+fn run(mut command: Command, target: &Path) -> Result<(), InspectPathError> {
+    let program = command.get_program().to_string_lossy().into_owned();
+    let output = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .output()
+        .map_err(|e| InspectPathError::MountFailed {
+            path: target.to_path_buf(),
+            msg: format!("can't run {program}: {e}"),
+        })?;
+    if output.status.success() {
+        return Ok(());
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    let lower = stderr.to_lowercase();
+    if PERMISSION_MESSAGES.iter().any(|m| lower.contains(m)) {
+        return Err(InspectPathError::NotPermitted {
+            path: target.to_path_buf(),
+        });
+    }
+    Err(InspectPathError::MountFailed {
+        path: target.to_path_buf(),
+        msg: if stderr.is_empty() {
+            format!("{program} exited with {}", output.status)
+        } else {
+            stderr
+        },
+    })
+}
+
+// generic options that are mount flags, not filesystem data
+#[cfg(target_os = "linux")]
+const FLAG_OPTIONS: &[(&str, libc::c_ulong)] = &[
+    ("ro", libc::MS_RDONLY),
+    ("nosuid", libc::MS_NOSUID),
+    ("nodev", libc::MS_NODEV),
+    ("noexec", libc::MS_NOEXEC),
+    ("sync", libc::MS_SYNCHRONOUS),
+    ("dirsync", libc::MS_DIRSYNC),
+    ("noatime", libc::MS_NOATIME),
+    ("nodiratime", libc::MS_NODIRATIME),
+    ("relatime", libc::MS_RELATIME),
+    ("strictatime", libc::MS_STRICTATIME),
+    ("lazytime", libc::MS_LAZYTIME),
+];
+
+// options `mount(8)` and fstab take that mean nothing to the kernel, or are
+// its defaults; `x-*` and `comment=` are skipped too
+#[cfg(target_os = "linux")]
+const USERSPACE_OPTIONS: &[&str] = &[
+    "rw", "defaults", "auto", "noauto", "user", "nouser", "users", "owner", "group", "nofail",
+    "_netdev", "async", "suid", "dev", "exec", "atime", "diratime",
+];
+
+/// Splits `options` into `mount(2)` flags and the filesystem's data string,
+/// adding what the kernel expects from the mount helpers: the server
+/// address, `addr=` for NFS and `ip=` for CIFS, and for CIFS the user and
+/// password from `credentials`.
+#[cfg(target_os = "linux")]
+fn kernel_options(
+    remote: &str,
+    fs_type: &str,
+    options: Option<&str>,
+    credentials: Option<&Credentials>,
+) -> (libc::c_ulong, Zeroizing<String>) {
+    let mut flags = 0;
+    let mut data: Vec<String> = Vec::new();
+    for option in options
+        .unwrap_or_default()
+        .split(',')
+        .filter(|o| !o.is_empty())
+    {
+        match FLAG_OPTIONS.iter().find(|(name, _)| *name == option) {
+            Some((_, flag)) => flags |= flag,
+            None if USERSPACE_OPTIONS.contains(&option)
+                || option.starts_with("x-")
+                || option.starts_with("comment=") => {}
+            None => data.push(option.to_string()),
+        }
+    }
+
+    let key = match fs_type {
+        "cifs" | "smb3" => "ip",
+        t if t.starts_with("nfs") => "addr",
+        _ => return (flags, Zeroizing::new(data.join(","))),
+    };
+    let has_key = data.iter().any(|o| o.split('=').next() == Some(key));
+    if !has_key && let Some(ip) = resolve(remote) {
+        data.insert(0, format!("{key}={ip}"));
+    }
+    let mut data = Zeroizing::new(data.join(","));
+    if let Some(credentials) = credentials {
+        // the kernel reads a doubled comma as one in the password
+        let password = Zeroizing::new(credentials.password.replace(',', ",,"));
+        let mut with = Zeroizing::new(format!(
+            "{},password={}",
+            user_options(credentials),
+            password.as_str()
+        ));
+        if !data.is_empty() {
+            with.push(',');
+            with.push_str(&data);
+        }
+        data = with;
+    }
+    (flags, data)
+}
+
+/// The credentials stored for the server of a CIFS `remote`, unless
+/// `options` name a user or a credentials file already.
+#[cfg(target_os = "linux")]
+fn stored_credentials(remote: &str, fs_type: &str, options: Option<&str>) -> Option<Credentials> {
+    if !matches!(fs_type, "cifs" | "smb3") {
+        return None;
+    }
+    let named = options.unwrap_or_default().split(',').any(|o| {
+        o.split_once('=')
+            .is_some_and(|(key, _)| matches!(key, "username" | "user" | "credentials"))
+    });
+    if named {
+        return None;
+    }
+    CredentialStore::global().lookup(remote)
+}
+
+/// `username=` and, for a `CORP\user` name, `domain=` options.
+#[cfg(target_os = "linux")]
+fn user_options(credentials: &Credentials) -> String {
+    match credentials.username.split_once(['\\', '/']) {
+        Some((domain, user)) => format!("username={user},domain={domain}"),
+        None => format!("username={}", credentials.username),
+    }
+}
+
+/// The first address of the server named in `remote`.
+#[cfg(target_os = "linux")]
+fn resolve(remote: &str) -> Option<std::net::IpAddr> {
+    use std::net::ToSocketAddrs;
+
+    let host = crate::remote::host_of(remote)?;
+    let mut addrs = (host, 0).to_socket_addrs().ok()?;
+    addrs.next().map(|addr| addr.ip())
+}
+
+#[cfg(target_os = "linux")]
+fn mount_syscall(
+    target: &Path,
+    remote: &str,
+    fs_type: &str,
+    options: Option<&str>,
+) -> Result<(), InspectPathError> {
+    use std::{ffi::CString, os::unix::ffi::OsStrExt};
+
+    let failed = |msg: String| InspectPathError::MountFailed {
+        path: target.to_path_buf(),
+        msg,
+    };
+    let c_string = |s: &[u8]| CString::new(s).map_err(|_| failed("NUL in mount argument".into()));
+    let credentials = stored_credentials(remote, fs_type, options);
+    let (flags, data) = kernel_options(remote, fs_type, options, credentials.as_ref());
+    let source = c_string(remote.as_bytes())?;
+    let c_target = c_string(target.as_os_str().as_bytes())?;
+    let fs_type = c_string(fs_type.as_bytes())?;
+    // may hold a password, so kept out of a CString, which can't be zeroed
+    if data.contains('\0') {
+        return Err(failed("NUL in mount argument".into()));
+    }
+    let data = Zeroizing::new([data.as_bytes(), b"\0"].concat());
+
+    let result = unsafe {
+        libc::mount(
+            source.as_ptr(),
+            c_target.as_ptr(),
+            fs_type.as_ptr(),
+            flags,
+            data.as_ptr().cast(),
+        )
+    };
+    if result == 0 {
+        return Ok(());
+    }
+    let err = std::io::Error::last_os_error();
+    match err.raw_os_error() {
+        Some(libc::EPERM | libc::EACCES) => Err(InspectPathError::NotPermitted {
+            path: target.to_path_buf(),
+        }),
+        _ => Err(failed(err.to_string())),
+    }
+}
+
+/// Unmounts `target` with `umount2(2)`. With `force` the mount is detached
+/// lazily (`MNT_DETACH`): it leaves the namespace at once and goes away when
+/// the last open file on it is closed.
+#[cfg(target_os = "linux")]
+pub(crate) fn unmount_syscall(target: &Path, force: bool) -> Result<(), InspectPathError> {
+    use std::{ffi::CString, os::unix::ffi::OsStrExt};
+
+    let c_target = CString::new(target.as_os_str().as_bytes())
+        .map_err(|_| InspectPathError::InvalidPath(target.display().to_string()))?;
+    let flags = if force { libc::MNT_DETACH } else { 0 };
+    if unsafe { libc::umount2(c_target.as_ptr(), flags) } == 0 {
+        return Ok(());
+    }
+    match std::io::Error::last_os_error().raw_os_error() {
+        Some(libc::EPERM) => Err(InspectPathError::NotPermitted {
+            path: target.to_path_buf(),
+        }),
+        errno => Err(InspectPathError::Sys {
+            path: target.to_path_buf(),
+            errno: errno.unwrap_or(0),
+            context: "umount2",
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mounted_targets_are_left_alone() {
+        // `/proc` is a mount point, so nothing is mounted and no remote needed
+        assert!(try_mount_if_needed_with("/proc/not/there", &MountSpec::new()).is_ok());
+        assert!(matches!(
+            try_mount_if_needed("", "//server/share"),
+            Err(InspectPathError::InvalidPath(_))
+        ));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn unmounting_a_plain_directory_fails() {
+        let tmp = tempfile::tempdir().unwrap();
+        // EINVAL as root, EPERM otherwise
+        assert!(matches!(
+            unmount_syscall(tmp.path(), false),
+            Err(InspectPathError::Sys {
+                errno: libc::EINVAL,
+                context: "umount2",
+                ..
+            } | InspectPathError::NotPermitted { .. })
+        ));
+        assert!(matches!(
+            unmount_syscall(Path::new("a\0b"), true),
+            Err(InspectPathError::InvalidPath(_))
+        ));
+    }
+
+    #[test]
+    fn no_remote_given() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let file = dir.join("missing.csv");
+        for method in [MountMethod::Syscall, MountMethod::SystemdMount] {
+            let spec = MountSpec::new().method(method);
+            assert!(
+                matches!(
+                    try_mount_if_needed_with(&file, &spec),
+                    Err(InspectPathError::NoRemote(target)) if target == dir
+                ),
+                "{method:?}"
+            );
+        }
+        assert!(matches!(
+            try_mount_if_needed(&file, ""),
+            Err(InspectPathError::NoRemote(_))
+        ));
+        // an empty remote isn't the fstab entry
+        let spec = MountSpec::new().remote("").method(MountMethod::Command);
+        assert!(matches!(
+            try_mount_if_needed_with(&file, &spec),
+            Err(InspectPathError::NoRemote(_))
+        ));
+    }
+
+    #[test]
+    fn only_empty_directories_are_mounted_on() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        fs::write(dir.join("kept.txt"), "").unwrap();
+        let file = dir.join("share/report.csv");
+        assert!(matches!(
+            try_mount_if_needed(&file, "//server/share"),
+            Err(InspectPathError::InvalidPath(target)) if Path::new(&target) == dir
+        ));
+
+        // a missing target
+        let spec = MountSpec::new()
+            .remote("//server/share")
+            .target(dir.join("share"));
+        assert!(matches!(
+            try_mount_if_needed_with(&file, &spec),
+            Err(InspectPathError::InvalidPath(_))
+        ));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn not_permitted_unprivileged() {
+        if unsafe { libc::geteuid() } == 0 {
+            return;
+        }
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        assert!(matches!(
+            try_mount_if_needed(dir.join("x"), "//127.0.0.1/share"),
+            Err(InspectPathError::NotPermitted { path }) if path == dir
+        ));
+        assert!(matches!(
+            mount_path(dir, "127.0.0.1:/export"),
+            Err(InspectPathError::NotPermitted { .. })
+        ));
+    }
+
+    #[test]
+    fn mount_failed() {
+        // not in /etc/fstab, so `mount` fails for root and users alike
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let spec = MountSpec::new().method(MountMethod::Command);
+        assert!(matches!(
+            try_mount_if_needed_with(dir, &spec),
+            Err(InspectPathError::MountFailed { path, .. }) if path == dir
+        ));
+
+        // no type to infer from a bare name
+        assert!(matches!(
+            try_mount_if_needed(dir, "share"),
+            Err(InspectPathError::MountFailed { .. })
+        ));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn options_split_into_flags_and_data() {
+        let (flags, data) = kernel_options(
+            "10.0.0.5:/export",
+            "nfs4",
+            Some("ro,vers=4.2,noatime"),
+            None,
+        );
+        assert_eq!(flags, libc::MS_RDONLY | libc::MS_NOATIME);
+        assert_eq!(data.as_str(), "addr=10.0.0.5,vers=4.2");
+
+        let (_, data) = kernel_options("//10.0.0.5/share", "cifs", Some("ip=10.0.0.6,rw"), None);
+        assert_eq!(data.as_str(), "ip=10.0.0.6");
+        let (flags, data) = kernel_options("/dev/sdb1", "ext4", None, None);
+        assert_eq!((flags, data.as_str()), (0, ""));
+
+        // fstab options that only mean something to mount(8)
+        let (flags, data) = kernel_options(
+            "10.0.0.5:/export",
+            "nfs",
+            Some("defaults,nofail,_netdev,user,noauto,x-systemd.automount,comment=x,hard"),
+            None,
+        );
+        assert_eq!((flags, data.as_str()), (0, "addr=10.0.0.5,hard"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn cifs_credentials_come_from_the_store() {
+        let credentials = Credentials {
+            username: r"CORP\backup".into(),
+            password: Zeroizing::new("a,b".into()),
+        };
+        let (_, data) = kernel_options(
+            "//10.0.0.5/share",
+            "cifs",
+            Some("vers=3.1.1"),
+            Some(&credentials),
+        );
+        assert_eq!(
+            data.as_str(),
+            "username=backup,domain=CORP,password=a,,b,ip=10.0.0.5,vers=3.1.1"
+        );
+
+        CredentialStore::global().set_credentials("cifs-store-test", "backup", "secret");
+        let remote = "//cifs-store-test/share";
+        assert!(stored_credentials(remote, "cifs", None).is_some());
+        assert!(stored_credentials(remote, "cifs", Some("username=other")).is_none());
+        assert!(stored_credentials(remote, "nfs", None).is_none());
+        CredentialStore::global().remove("cifs-store-test");
+    }
+}
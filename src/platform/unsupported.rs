@@ -0,0 +1,154 @@
+//! The backend for targets that are neither Windows nor Unix, such as
+//! `wasm32-unknown-unknown`: the crate's types are all there, but nothing can
+//! be inspected. It mirrors the Windows backend's signatures so that
+//! [`PathInfo`] uses the same layout, with every volume field unset.
+use crate::report::Recorder;
+use crate::{
+    Capabilities, InspectOptions, InspectPathError, PathInfo, PathInspector, PathStatus, SpaceInfo,
+    VolumeFilter,
+};
+use std::path::{Path, PathBuf};
+
+/// Always fails with [`InspectPathError::Unsupported`] on this target.
+pub fn inspect_path(path: impl AsRef<Path>) -> Result<PathInfo, InspectPathError> {
+    crate::SystemInspector.inspect(path.as_ref())
+}
+
+/// [`inspect_path_with`](crate::inspect_path_with) ignores the options here.
+pub(crate) fn inspect_with(
+    _path: &Path,
+    _opts: &InspectOptions,
+) -> Result<PathInfo, InspectPathError> {
+    Err(InspectPathError::Unsupported)
+}
+
+pub(crate) fn inspect_recorded(
+    _path: &Path,
+    rec: &mut Recorder,
+) -> Result<PathInfo, InspectPathError> {
+    rec.backend("unsupported");
+    Err(InspectPathError::Unsupported)
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct SessionCache;
+
+pub(crate) fn inspect_cached(
+    path: &Path,
+    _cache: &mut SessionCache,
+) -> Result<PathInfo, InspectPathError> {
+    inspect_path(path)
+}
+
+/// Fails every path with [`InspectPathError::Unsupported`] on this target.
+pub fn inspect_paths<P: AsRef<Path>>(
+    paths: impl IntoIterator<Item = P>,
+) -> Vec<Result<PathInfo, InspectPathError>> {
+    paths.into_iter().map(inspect_path).collect()
+}
+
+/// Always fails with [`InspectPathError::Unsupported`] on this target.
+pub fn list_volumes(_filter: &VolumeFilter) -> Result<Vec<PathInfo>, InspectPathError> {
+    Err(InspectPathError::Unsupported)
+}
+
+/// A mount table, which can't be loaded on this target.
+#[derive(Debug, Clone)]
+pub struct MountTable {
+    _private: (),
+}
+
+impl MountTable {
+    /// Always fails with [`InspectPathError::Unsupported`] on this target.
+    pub fn load() -> Result<Self, InspectPathError> {
+        Err(InspectPathError::Unsupported)
+    }
+
+    pub(crate) fn is_network(&self, _path: &Path) -> bool {
+        false
+    }
+
+    pub(crate) fn mount_root(&self, _path: &Path) -> Option<PathBuf> {
+        None
+    }
+}
+
+/// Always fails with [`InspectPathError::Unsupported`] on this target.
+pub fn crosses_mount(
+    _ancestor: impl AsRef<Path>,
+    _descendant: impl AsRef<Path>,
+) -> Result<bool, InspectPathError> {
+    Err(InspectPathError::Unsupported)
+}
+
+/// Always fails with [`InspectPathError::Unsupported`] on this target.
+pub fn same_filesystem(
+    _a: impl AsRef<Path>,
+    _b: impl AsRef<Path>,
+) -> Result<bool, InspectPathError> {
+    Err(InspectPathError::Unsupported)
+}
+
+/// Always fails with [`InspectPathError::Unsupported`] on this target.
+pub fn probe_status(_path: impl AsRef<Path>) -> Result<PathStatus, InspectPathError> {
+    Err(InspectPathError::Unsupported)
+}
+
+pub(crate) fn is_system_volume(_path: &Path) -> bool {
+    false
+}
+
+pub(crate) fn link_support(_info: &PathInfo) -> Option<(bool, bool)> {
+    None
+}
+
+pub(crate) fn is_case_sensitive(_info: &PathInfo) -> Option<bool> {
+    None
+}
+
+pub(crate) fn is_dev_drive(_info: &PathInfo) -> bool {
+    false
+}
+
+pub(crate) fn is_snapshot(_path: &Path) -> bool {
+    false
+}
+
+pub(crate) fn snapshot_origin(_path: &Path) -> Option<PathBuf> {
+    None
+}
+
+pub(crate) fn space_info(_path: &Path) -> Result<SpaceInfo, InspectPathError> {
+    Err(InspectPathError::Unsupported)
+}
+
+pub(crate) fn identity_parts(info: &PathInfo) -> Vec<String> {
+    vec!["path".into(), info.path.to_string_lossy().into_owned()]
+}
+
+pub(crate) fn sector_sizes(_path: &Path) -> Option<(u32, u32)> {
+    None
+}
+
+pub(crate) fn is_boot_partition(_path: &Path) -> bool {
+    false
+}
+
+pub(crate) fn max_filename_length(_path: &Path) -> Option<u64> {
+    None
+}
+
+pub(crate) fn capabilities() -> Capabilities {
+    Capabilities {
+        can_mount: false,
+        has_mountinfo: false,
+        has_device_numbers: false,
+        has_volume_labels: false,
+        can_watch_mounts: false,
+        has_quotas: false,
+    }
+}
+
+pub(crate) fn volume_label(_path: &Path) -> Option<String> {
+    None
+}
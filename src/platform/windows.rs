@@ -1,17 +1,107 @@
-use crate::{InspectPathError, PathInfo, PathStatus, PathType, RemoteType};
-use std::{ffi::c_void, io::ErrorKind, path::Path};
-use windows::Win32::Foundation::NO_ERROR;
+use crate::{
+    Capabilities, Confidence, DeviceId, InspectOptions, InspectPathError, MountEvent,
+    MountEventKind, PathInfo, PathInspector, PathStatus, PathType, RemoteType, SpaceInfo,
+    VolumeFilter, remote, report::Recorder,
+};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    ffi::c_void,
+    path::{Component, Path, PathBuf},
+    sync::mpsc,
+    thread::{self, JoinHandle},
+};
+use windows::Win32::Foundation::{
+    CloseHandle, HANDLE, HINSTANCE, HWND, LPARAM, LRESULT, NO_ERROR, WPARAM,
+};
 use windows::Win32::NetworkManagement::WNet::{
-    NETRESOURCEW, RESOURCETYPE_DISK, WNetAddConnection2W, WNetGetUniversalNameW,
+    NETRESOURCEW, RESOURCETYPE_DISK, WNetGetResourceInformationW, WNetGetUniversalNameW,
+};
+use windows::Win32::System::IO::DeviceIoControl;
+use windows::Win32::System::Ioctl::{
+    FILE_FS_PERSISTENT_VOLUME_INFORMATION, FSCTL_QUERY_PERSISTENT_VOLUME_STATE,
+    IOCTL_DISK_GET_PARTITION_INFO_EX, IOCTL_STORAGE_QUERY_PROPERTY, PARTITION_INFORMATION_EX,
+    PARTITION_MSFT_RECOVERY, PARTITION_STYLE_GPT, PARTITION_STYLE_MBR,
+    PERSISTENT_VOLUME_STATE_DEV_VOLUME, PropertyStandardQuery, STORAGE_ACCESS_ALIGNMENT_DESCRIPTOR,
+    STORAGE_PROPERTY_QUERY, StorageAccessAlignmentProperty,
+};
+use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows::Win32::System::SystemServices::{
+    FILE_CASE_SENSITIVE_SEARCH, FILE_CS_FLAG_CASE_SENSITIVE_DIR, FILE_READ_ONLY_VOLUME,
+    FILE_SUPPORTS_HARD_LINKS, FILE_SUPPORTS_REPARSE_POINTS,
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    CreateWindowExW, DBT_DEVICEARRIVAL, DBT_DEVICEREMOVECOMPLETE, DBT_DEVTYP_VOLUME,
+    DEV_BROADCAST_HDR, DEV_BROADCAST_VOLUME, DefWindowProcW, DestroyWindow, DispatchMessageW,
+    GetMessageW, MSG, PostMessageW, PostQuitMessage, RegisterClassW, WINDOW_EX_STYLE, WINDOW_STYLE,
+    WM_CLOSE, WM_DESTROY, WM_DEVICECHANGE, WNDCLASSW,
 };
 use windows::{
     Win32::{
         Foundation::ERROR_MORE_DATA,
         NetworkManagement::WNet::{UNC_INFO_LEVEL, UNIVERSAL_NAME_INFOW},
-        Storage::FileSystem::GetDriveTypeW,
+        Storage::FileSystem::{
+            CreateFileW, FILE_CASE_SENSITIVE_INFO, FILE_FLAG_BACKUP_SEMANTICS,
+            FILE_FLAGS_AND_ATTRIBUTES, FILE_READ_ATTRIBUTES, FILE_SHARE_DELETE, FILE_SHARE_READ,
+            FILE_SHARE_WRITE, FileCaseSensitiveInfo, GetDiskFreeSpaceExW, GetDriveTypeW,
+            GetFileInformationByHandleEx, GetLogicalDrives, GetVolumeInformationW,
+            GetVolumeNameForVolumeMountPointW, GetVolumePathNameW, OPEN_EXISTING,
+            PARTITION_MSFT_RECOVERY_GUID, PARTITION_SYSTEM_GUID, QueryDosDeviceW,
+        },
     },
     core::{PCWSTR, PWSTR},
 };
+
+#[cfg(feature = "mount")]
+mod mount;
+#[cfg(feature = "mount")]
+pub(crate) use mount::mount_share;
+#[cfg(feature = "mount")]
+pub use mount::{
+    mount_path, mount_path_as_user, try_mount_if_needed, try_mount_if_needed_as_user, unmount_path,
+};
+
+thread_local! {
+    // names of the Win32 calls made on this thread, while one is recording
+    static WIN32_CALLS: RefCell<Option<Vec<&'static str>>> = const { RefCell::new(None) };
+}
+
+/// Notes a Win32 call the classifier is about to make, for
+/// [`record_win32_calls`].
+fn win32_call(name: &'static str) {
+    WIN32_CALLS.with(|calls| {
+        if let Some(calls) = calls.borrow_mut().as_mut() {
+            calls.push(name);
+        }
+    });
+}
+
+/// Runs `f` and returns the Win32 calls inspections on this thread made
+/// meanwhile, by name and in order: `GetVolumePathNameW`,
+/// `GetVolumeInformationW`, `GetVolumeNameForVolumeMountPointW`,
+/// `GetDriveTypeW`, `WNetGetUniversalNameW` and
+/// `WNetGetResourceInformationW`. Built with the `test-util` feature, for
+/// tests and benchmarks of what an [`InspectSession`](crate::InspectSession)
+/// saves.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use inspect_path::{InspectSession, record_win32_calls};
+///
+/// let session = InspectSession::new();
+/// session.inspect(r"Z:.txt").unwrap();
+/// let (_, calls) = record_win32_calls(|| session.inspect(r"Z:.txt"));
+/// assert_eq!(calls, ["GetVolumePathNameW"]);
+/// ```
+#[cfg(any(test, feature = "test-util"))]
+pub fn record_win32_calls<T>(f: impl FnOnce() -> T) -> (T, Vec<&'static str>) {
+    let outer = WIN32_CALLS.with(|calls| calls.replace(Some(Vec::new())));
+    let out = f();
+    let calls = WIN32_CALLS.with(|calls| calls.replace(outer));
+    (out, calls.unwrap_or_default())
+}
+
 /// Inspects a filesystem path and returns detailed information about it.
 ///
 /// This function determines the general type of the path (fixed, removable,
@@ -23,58 +113,729 @@ use windows::{
 /// # Errors
 ///
 /// Returns an error if the path is invalid or its type cannot be determined.
-pub fn inspect_path(path: &Path) -> Result<PathInfo, InspectPathError> {
+///
+/// # Nonexistent paths
+///
+/// A path that doesn't exist yet is classified by the volume it would be
+/// created on, so inspecting `C:\data\new.txt` reports the type of `C:\`.
+/// Use [`InspectOptions::require_existing`] with [`inspect_path_with`] for
+/// strict "must exist" semantics.
+///
+/// # UNC paths
+///
+/// `\\server\share\...` paths are classified from the string alone, so an
+/// unreachable server can't stall the call. Their mount point is the share
+/// root; the volume id, filesystem type and read-only flag are left unset.
+///
+/// # Examples
+///
+/// Anything that converts to a `&Path` is accepted:
+///
+/// ```rust
+/// use std::path::PathBuf;
+/// use inspect_path::inspect_path;
+///
+/// let dir: PathBuf = std::env::temp_dir();
+/// let name: String = dir.to_string_lossy().into_owned();
+///
+/// let from_ref = inspect_path(&dir).unwrap();
+/// assert_eq!(inspect_path(dir.clone()).unwrap().kind(), from_ref.kind());
+/// assert_eq!(inspect_path(name.as_str()).unwrap().kind(), from_ref.kind());
+/// assert_eq!(inspect_path(name).unwrap().kind(), from_ref.kind());
+/// ```
+///
+/// # See also
+///
+/// - [`inspect_path_and_status`] — inspect and verify availability in one call
+///
+/// [`InspectOptions::require_existing`]: crate::InspectOptions::require_existing
+/// [`inspect_path_with`]: crate::inspect_path_with
+/// [`inspect_path_and_status`]: crate::inspect_path_and_status
+pub fn inspect_path(path: impl AsRef<Path>) -> Result<PathInfo, InspectPathError> {
+    crate::SystemInspector.inspect(path.as_ref())
+}
+
+/// [`inspect_path_with`](crate::inspect_path_with) ignores the Unix-only
+/// options here.
+pub(crate) fn inspect_with(
+    path: &Path,
+    opts: &InspectOptions,
+) -> Result<PathInfo, InspectPathError> {
+    crate::probe::timed(path, || {
+        if opts.follow_symlinks {
+            inspect_path_inner(path, &mut Recorder::off())
+        } else {
+            inspect_path_lexical_inner(path)
+        }
+    })
+}
+
+/// Inspects a path without following a symlink or junction in its final
+/// component, classifying the volume the link lives on rather than the one
+/// it points into. Links earlier in the path are still followed.
+///
+/// `GetVolumePathNameW` resolves every link on a path, so for a final
+/// component that is a link this inspects the directory holding it instead.
+/// A folder a volume is mounted on is not treated as a link.
+///
+/// # Errors
+///
+/// Same as [`inspect_path`].
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use inspect_path::{inspect_path, inspect_path_lexical};
+///
+/// // C:\data -> \\srv\share\data
+/// let link = inspect_path_lexical(r"C:\data").unwrap();
+/// let target = inspect_path(r"C:\data").unwrap();
+/// assert!(!link.is_remote() && target.is_remote());
+/// ```
+pub fn inspect_path_lexical(path: impl AsRef<Path>) -> Result<PathInfo, InspectPathError> {
+    crate::inspect_path_with(path, &InspectOptions::new().follow_symlinks(false))
+}
+
+fn inspect_path_lexical_inner(path: &Path) -> Result<PathInfo, InspectPathError> {
+    let is_link = std::fs::symlink_metadata(path).is_ok_and(|m| m.file_type().is_symlink());
+    let parent = path
+        .parent()
+        .filter(|p| is_link && !p.as_os_str().is_empty() && !is_mount_folder(path));
+    let Some(parent) = parent else {
+        return inspect_path_inner(path, &mut Recorder::off());
+    };
+    let mut info = inspect_path_inner(parent, &mut Recorder::off())?;
+    info.path = path.to_path_buf();
+    Ok(info)
+}
+
+/// Whether `path` is itself the root of a volume, e.g. `C:\mnt\usb` with a
+/// volume mounted there.
+fn is_mount_folder(path: &Path) -> bool {
+    let Ok(path) = std::path::absolute(path) else {
+        return false;
+    };
+    volume_root(&path_to_wide(&path)).is_some_and(|root| {
+        root.trim_end_matches('\\')
+            .eq_ignore_ascii_case(path.to_string_lossy().trim_end_matches('\\'))
+    })
+}
+
+/// [`inspect_path`] for [`inspect_report`](crate::inspect_report).
+pub(crate) fn inspect_recorded(
+    path: &Path,
+    rec: &mut Recorder,
+) -> Result<PathInfo, InspectPathError> {
+    inspect_path_inner(path, rec)
+}
+
+fn inspect_path_inner(path: &Path, rec: &mut Recorder) -> Result<PathInfo, InspectPathError> {
+    rec.backend("win32");
+    // GetDriveTypeW can't classify a VSS snapshot device, and a snapshot is
+    // read-only whatever its volume flags say
+    if let Some(device) = shadow_copy_root(path) {
+        rec.raw("shadow copy", &device);
+        rec.decide("VSS snapshot: Fixed and read-only without GetDriveTypeW");
+        let volume = volume_information(&device);
+        let device_id = volume.as_ref().map(|v| volume_id(&device, v.serial).1);
+        return Ok(PathInfo {
+            path: path.to_path_buf(),
+            kind: PathType::Fixed,
+            remote_kind: None,
+            status: PathStatus::Unknown,
+            confidence: Confidence::High,
+            device_id,
+            fs_type: volume.map(|v| v.fs_name),
+            mount_point: Some(PathBuf::from(&device)),
+            read_only: Some(true),
+        });
+    }
+    // GetDriveTypeW sees only `\\` for a UNC path and reports
+    // DRIVE_NO_ROOT_DIR, so classify those from the string alone; any volume
+    // query here would be a round trip to a server that may not answer
+    if let Some(unc) = unc_path(path) {
+        rec.raw("UNC", &unc);
+        rec.secret(unc.clone(), "<unc>");
+        rec.decide("UNC path: Remote without volume queries");
+        record_provider(&unc, rec);
+        let share = format!(r"{}\", share_of(&unc));
+        return Ok(PathInfo {
+            path: path.to_path_buf(),
+            kind: PathType::Remote,
+            remote_kind: get_remote_type(&Some(unc), None),
+            status: PathStatus::Unknown,
+            confidence: Confidence::High,
+            device_id: None,
+            fs_type: None,
+            mount_point: Some(PathBuf::from(share)),
+            read_only: None,
+        });
+    }
+
     let wide = path_to_wide(path);
-    let base_path = get_universal_name(&wide);
+    let root = rec.time("GetVolumePathNameW", || volume_root(&wide));
+    // one GetVolumeInformationW call answers the id, type and read-only flag
+    let volume = rec.time("GetVolumeInformationW", || {
+        root.as_deref().and_then(volume_information)
+    });
+    let device_id = rec.time("volume id", || {
+        Some(volume_id(root.as_deref()?, volume.as_ref()?.serial).1)
+    });
+    let read_only = volume
+        .as_ref()
+        .map(|v| v.flags & FILE_READ_ONLY_VOLUME != 0);
+    let fs_type = volume.map(|v| v.fs_name);
+    if let Some(root) = &root {
+        rec.raw("volume root", root);
+        rec.secret(root.clone(), "<mount>");
+    }
+    if let Some(fs_type) = &fs_type {
+        rec.raw("filesystem name", fs_type);
+    }
+
+    let base_path = rec.time("WNetGetUniversalNameW", || get_universal_name(&wide));
+    if let Some(universal) = &base_path {
+        rec.raw("universal name", universal);
+        rec.secret(universal.clone(), "<unc>");
+        record_provider(universal, rec);
+    }
 
     let result = match &base_path {
         Some(real_path) => {
             let wide = return_first_two(Path::new(&real_path));
-            unsafe { GetDriveTypeW(PCWSTR(wide.as_ptr())) }
+            win32_call("GetDriveTypeW");
+            rec.time("GetDriveTypeW", || unsafe {
+                GetDriveTypeW(PCWSTR(wide.as_ptr()))
+            })
+        }
+        None => {
+            // GetDriveTypeW only accepts volume roots; this also makes paths
+            // that don't exist yet classify by the volume they would land on
+            let root = root
+                .as_deref()
+                .map(to_pwstr)
+                .unwrap_or_else(|| wide.clone());
+            win32_call("GetDriveTypeW");
+            rec.time("GetDriveTypeW", || unsafe {
+                GetDriveTypeW(PCWSTR(root.as_ptr()))
+            })
         }
-        None => unsafe { GetDriveTypeW(PCWSTR(wide.as_ptr())) },
     };
+    rec.raw("GetDriveTypeW", result);
 
     let kind = match &result {
-        0 => return Err(InspectPathError::PathTypeError), // DRIVE_UNKNOWN
+        0 => return Err(InspectPathError::PathTypeError(path.to_path_buf())), // DRIVE_UNKNOWN
         1 => return Err(InspectPathError::InvalidPath(path.display().to_string())), // DRIVE_NO_ROOT_DIR
         2 => PathType::Removable, // DRIVE_REMOVABLE
         3 => PathType::Fixed,     // DRIVE_FIXED
         4 => PathType::Remote,    // DRIVE_REMOTE
         5 => PathType::CDRom,     // DRIVE_CDROM
         6 => PathType::RamDisk,   // DRIVE_RAMDISK
-        e => return Err(InspectPathError::General(e.to_string())),
+        e => {
+            return Err(InspectPathError::General {
+                path: Some(path.to_path_buf()),
+                msg: format!("unexpected drive type {e}"),
+            });
+        }
     };
 
     let remote_kind = if matches!(kind, PathType::Remote) {
-        get_remote_type(&base_path)
+        get_remote_type(&base_path, fs_type.as_deref())
     } else {
         None
     };
+    rec.decide(format_args!(
+        "drive type {result} -> {kind:?}, remote type {remote_kind:?}"
+    ));
 
     Ok(PathInfo {
         path: path.to_path_buf(),
         kind,
         remote_kind,
         status: PathStatus::Unknown,
+        confidence: Confidence::High,
+        device_id,
+        fs_type,
+        mount_point: root.as_ref().map(PathBuf::from),
+        read_only,
     })
 }
 
-fn get_remote_type(base_path: &Option<String>) -> Option<RemoteType> {
-    match base_path {
-        None => None,
-        Some(bp) => {
-            match (
-                bp.contains(r"\\"),
-                bp.contains('@'),
-                bp.contains("DavWWWRoot"),
-            ) {
-                (true, false, false) | (true, true, false) => Some(RemoteType::SMB),
-                (true, false, true) | (true, true, true) => Some(RemoteType::WebDAV),
-                (false, _, _) => Some(RemoteType::Unknown),
+/// Inspects many paths, classifying each volume only once.
+///
+/// Equivalent to calling [`inspect_path`] on each path, but the drive type,
+/// UNC name and volume id are looked up once per volume root and reused for
+/// every other path on it. Results are in input order, and a failure for one
+/// path does not affect the others.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::path::Path;
+/// use inspect_path::inspect_paths;
+///
+/// let paths = [Path::new(r"C:\Windows"), Path::new(r"Z:\share\file.txt")];
+/// for (path, info) in paths.iter().zip(inspect_paths(paths)) {
+///     println!("{}: {:?}", path.display(), info.map(|i| i.kind().clone()));
+/// }
+/// ```
+pub fn inspect_paths<P: AsRef<Path>>(
+    paths: impl IntoIterator<Item = P>,
+) -> Vec<Result<PathInfo, InspectPathError>> {
+    let mut cache = SessionCache::default();
+    paths
+        .into_iter()
+        .map(|path| inspect_cached(path.as_ref(), &mut cache))
+        .collect()
+}
+
+/// What an [`InspectSession`](crate::InspectSession) remembers: the
+/// inspection of one path per volume root, keyed by the uppercased root, or
+/// by `\\SERVER\SHARE` for UNC paths.
+#[derive(Debug, Default)]
+pub(crate) struct SessionCache {
+    by_root: HashMap<String, PathInfo>,
+}
+
+/// [`inspect_path`] through `cache`. A hit costs one `GetVolumePathNameW`
+/// for a drive path and no call at all for a UNC path; the drive type, UNC
+/// name and volume information are not queried again.
+pub(crate) fn inspect_cached(
+    path: &Path,
+    cache: &mut SessionCache,
+) -> Result<PathInfo, InspectPathError> {
+    let key = if let Some(device) = shadow_copy_root(path) {
+        device.to_ascii_uppercase()
+    } else if let Some(unc) = unc_path(path) {
+        share_of(&unc).to_ascii_uppercase()
+    } else {
+        match volume_root(&path_to_wide(path)) {
+            Some(root) => root.to_ascii_uppercase(),
+            None => return inspect_path(path),
+        }
+    };
+    let mut info = match cache.by_root.get(&key) {
+        Some(info) => info.clone(),
+        None => {
+            let info = inspect_path(path)?;
+            cache.by_root.insert(key, info.clone());
+            info
+        }
+    };
+    info.path = path.to_path_buf();
+    Ok(info)
+}
+
+/// The root of the VSS snapshot device a path is on, e.g.
+/// `\\?\GLOBALROOT\Device\HarddiskVolumeShadowCopy3\` for
+/// `\\?\GLOBALROOT\Device\HarddiskVolumeShadowCopy3\Users\a.txt`, or `None`
+/// for any other path.
+fn shadow_copy_root(path: &Path) -> Option<String> {
+    const DEVICE: &str = r"GLOBALROOT\Device\";
+    const SHADOW_COPY: &str = "HarddiskVolumeShadowCopy";
+
+    let s = path.to_string_lossy().replace('/', "\\");
+    let rest = s
+        .strip_prefix(r"\\?\")
+        .or_else(|| s.strip_prefix(r"\\.\"))?;
+    let rest = rest
+        .get(..DEVICE.len())
+        .filter(|p| p.eq_ignore_ascii_case(DEVICE))
+        .map(|_| &rest[DEVICE.len()..])?;
+    let name = rest.split('\\').next().unwrap_or_default();
+    let number = name
+        .get(..SHADOW_COPY.len())
+        .filter(|p| p.eq_ignore_ascii_case(SHADOW_COPY))
+        .map(|_| &name[SHADOW_COPY.len()..])?;
+    if number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some(format!(r"\\?\GLOBALROOT\Device\{name}\"))
+}
+
+pub(crate) fn is_snapshot(path: &Path) -> bool {
+    shadow_copy_root(path).is_some()
+}
+
+// volsnap.h, which the windows crate doesn't cover:
+// CTL_CODE(VOLSNAPCONTROLTYPE ('S'), 100, METHOD_BUFFERED, FILE_ANY_ACCESS)
+const IOCTL_VOLSNAP_QUERY_ORIGINAL_VOLUME_NAME: u32 = 0x0053_0190;
+
+/// The volume a VSS snapshot was taken of: its drive root (`C:\`) when it
+/// has a letter, else its device path under `\\?\GLOBALROOT`.
+pub(crate) fn snapshot_origin(path: &Path) -> Option<PathBuf> {
+    let root = shadow_copy_root(path)?;
+    let device = to_pwstr(root.strip_suffix('\\')?);
+    let handle = unsafe {
+        CreateFileW(
+            PCWSTR(device.as_ptr()),
+            0,
+            FILE_SHARE_READ | FILE_SHARE_WRITE,
+            None,
+            OPEN_EXISTING,
+            FILE_FLAGS_AND_ATTRIBUTES(0),
+            None,
+        )
+    }
+    .ok()?;
+
+    // VOLSNAP_NAME: the name's length in bytes, then the name, e.g.
+    // `\Device\HarddiskVolume3`
+    let mut buffer = [0u16; 512];
+    let queried = unsafe {
+        DeviceIoControl(
+            handle,
+            IOCTL_VOLSNAP_QUERY_ORIGINAL_VOLUME_NAME,
+            None,
+            0,
+            Some(buffer.as_mut_ptr().cast()),
+            size_of_val(&buffer) as u32,
+            None,
+            None,
+        )
+    };
+    let _ = unsafe { CloseHandle(handle) };
+    queried.ok()?;
+    let len = usize::from(buffer[0]) / 2;
+    let original = String::from_utf16_lossy(buffer.get(1..1 + len)?);
+
+    Some(
+        drive_for_device(&original)
+            .unwrap_or_else(|| PathBuf::from(format!(r"\\?\GLOBALROOT{original}\"))),
+    )
+}
+
+/// The root of the lettered drive whose device is `device` (e.g.
+/// `\Device\HarddiskVolume3`), found with `QueryDosDeviceW`.
+fn drive_for_device(device: &str) -> Option<PathBuf> {
+    used_drive_letters()
+        .into_iter()
+        .map(|letter| format!("{letter}:"))
+        .find(|letter| {
+            let name = to_pwstr(letter);
+            let mut target = [0u16; 261];
+            let len = unsafe { QueryDosDeviceW(PCWSTR(name.as_ptr()), Some(&mut target)) };
+            let end = target.iter().position(|&c| c == 0).unwrap_or(len as usize);
+            len != 0 && String::from_utf16_lossy(&target[..end]).eq_ignore_ascii_case(device)
+        })
+        .map(|letter| PathBuf::from(format!("{letter}\\")))
+}
+
+/// `\\server\share` of a UNC name, dropping the rest of the path.
+fn share_of(unc: &str) -> &str {
+    let end = unc[2..]
+        .match_indices('\\')
+        .nth(1)
+        .map_or(unc.len(), |(i, _)| i + 2);
+    &unc[..end]
+}
+
+/// Lists drives, each as a [`PathInfo`] whose path is its drive root.
+///
+/// Every lettered drive reported by `GetLogicalDrives` is inspected; drives
+/// that can't be (e.g. a card reader slot without a card) are skipped.
+/// Volumes mounted into folders are not listed yet.
+///
+/// # Errors
+///
+/// Currently never fails on Windows; the `Result` matches the Unix version.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use inspect_path::{PathType, VolumeFilter, list_volumes};
+///
+/// let usb = list_volumes(&VolumeFilter::new().kind(PathType::Removable)).unwrap();
+/// ```
+pub fn list_volumes(filter: &VolumeFilter) -> Result<Vec<PathInfo>, InspectPathError> {
+    let mask = unsafe { GetLogicalDrives() };
+
+    Ok(drive_letters(mask)
+        .filter_map(|letter| inspect_path(Path::new(&format!(r"{letter}:\"))).ok())
+        .filter(|info| filter.matches(info))
+        .collect())
+}
+
+/// Returns the drive letters in use, in order, from the `GetLogicalDrives`
+/// bitmask: local volumes, mapped network drives and `subst` drives alike.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use inspect_path::used_drive_letters;
+///
+/// let taken: String = used_drive_letters().into_iter().collect();
+/// println!("in use: {taken}"); // e.g. "CDZ"
+/// ```
+pub fn used_drive_letters() -> Vec<char> {
+    drive_letters(unsafe { GetLogicalDrives() }).collect()
+}
+
+/// Returns the first drive letter not in use, from `C` on, or `None` when
+/// all are taken.
+///
+/// `A` and `B` are skipped: they are kept for floppy drives by convention,
+/// and some software still treats them specially. Another process may take
+/// the letter before it is used, so a mount with it can still fail with
+/// `ERROR_ALREADY_ASSIGNED`.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use inspect_path::first_free_drive_letter;
+///
+/// match first_free_drive_letter() {
+///     Some(letter) => println!("mapping the share to {letter}:"),
+///     None => eprintln!("every drive letter is taken"),
+/// }
+/// ```
+pub fn first_free_drive_letter() -> Option<char> {
+    first_free(unsafe { GetLogicalDrives() })
+}
+
+fn first_free(mask: u32) -> Option<char> {
+    (2..26u8)
+        .find(|bit| mask & (1 << bit) == 0)
+        .map(|bit| (b'A' + bit) as char)
+}
+
+/// A snapshot of which drive letters are mapped network drives, for
+/// answering many lexical questions without querying each path.
+///
+/// See [`is_network_path_with`](crate::is_network_path_with).
+#[derive(Debug, Clone)]
+pub struct MountTable {
+    remote_drives: Vec<char>,
+}
+
+impl MountTable {
+    /// Queries every drive letter with `GetDriveTypeW`. Never blocks on the
+    /// network: the drive type is known locally.
+    ///
+    /// # Errors
+    ///
+    /// Currently never fails on Windows; the `Result` matches the Unix version.
+    pub fn load() -> Result<Self, InspectPathError> {
+        let mask = unsafe { GetLogicalDrives() };
+        let remote_drives = drive_letters(mask)
+            .filter(|letter| {
+                let root = to_pwstr(&format!(r"{letter}:\"));
+                unsafe { GetDriveTypeW(PCWSTR(root.as_ptr())) == 4 } // DRIVE_REMOTE
+            })
+            .collect();
+        Ok(Self { remote_drives })
+    }
+
+    /// Whether `path` starts with a mapped network drive letter.
+    pub(crate) fn is_network(&self, path: &Path) -> bool {
+        let s = path.to_string_lossy();
+        let s = s.strip_prefix(r"\\?\").unwrap_or(&s);
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(letter), Some(':')) => self.remote_drives.contains(&letter.to_ascii_uppercase()),
+            _ => false,
+        }
+    }
+
+    /// The drive (`C:\`) or share (`\\server\share\`) `path` is on, from
+    /// its prefix alone.
+    pub(crate) fn mount_root(&self, path: &Path) -> Option<PathBuf> {
+        match path.components().next()? {
+            Component::Prefix(prefix) => {
+                let mut root = prefix.as_os_str().to_owned();
+                root.push(r"\");
+                Some(root.into())
             }
+            _ => None,
+        }
+    }
+}
+
+/// Returns `true` if `descendant` lives on a different volume than `ancestor`.
+///
+/// This is the primitive behind `--one-file-system` style traversal: a
+/// recursive walker rooted at `ancestor` can stop descending once this
+/// returns `true`.
+///
+/// Volumes are compared by their root as reported by `GetVolumePathNameW`, so
+/// folders with a volume mounted into them (e.g. `C:\mnt\disk2\`) count as a
+/// boundary, as do different network shares.
+///
+/// # Errors
+///
+/// Returns an error if the volume root of either path cannot be determined.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::path::Path;
+/// use inspect_path::crosses_mount;
+///
+/// if crosses_mount(Path::new(r"C:\"), Path::new(r"C:\mnt\disk2")).unwrap() {
+///     println!("disk2 is a separate volume");
+/// }
+/// ```
+pub fn crosses_mount(
+    ancestor: impl AsRef<Path>,
+    descendant: impl AsRef<Path>,
+) -> Result<bool, InspectPathError> {
+    let (ancestor, descendant) = (ancestor.as_ref(), descendant.as_ref());
+    let a = volume_root(&path_to_wide(ancestor)).ok_or(InspectPathError::InvalidPath(
+        ancestor.display().to_string(),
+    ))?;
+    let d = volume_root(&path_to_wide(descendant)).ok_or(InspectPathError::InvalidPath(
+        descendant.display().to_string(),
+    ))?;
+
+    Ok(!a.eq_ignore_ascii_case(&d))
+}
+
+/// Returns `true` if `a` and `b` are on the same volume, i.e. a rename
+/// between them can succeed without copying.
+///
+/// Each path is mapped to its volume root with `GetVolumePathNameW`, which
+/// also works for paths that don't exist yet, and the roots are compared by
+/// volume serial number and volume GUID. Two mount points of the same volume
+/// (e.g. `D:\` and `C:\mnt\d\`) count as the same volume.
+///
+/// # Errors
+///
+/// Returns [`InspectPathError::InvalidPath`] if the volume of either path
+/// can't be determined.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::path::Path;
+/// use inspect_path::same_filesystem;
+///
+/// if !same_filesystem(Path::new(r"C:\data"), Path::new(r"D:\backup")).unwrap() {
+///     println!("copy, then delete");
+/// }
+/// ```
+pub fn same_filesystem(a: impl AsRef<Path>, b: impl AsRef<Path>) -> Result<bool, InspectPathError> {
+    let id = |path: &Path| {
+        volume_root(&path_to_wide(path))
+            .and_then(|root| Some(volume_id(&root, volume_information(&root)?.serial)))
+            .map(|(id, _)| id)
+            .ok_or_else(|| InspectPathError::InvalidPath(path.display().to_string()))
+    };
+    Ok(id(a.as_ref())? == id(b.as_ref())?)
+}
+
+/// Returns whether `path` is on the `%SystemDrive%` volume.
+pub(crate) fn is_system_volume(path: &Path) -> bool {
+    if unc_path(path).is_some() {
+        return false;
+    }
+    let Some(system_drive) = std::env::var_os("SystemDrive") else {
+        return false;
+    };
+    let Some(root) = volume_root(&path_to_wide(path)) else {
+        return false;
+    };
+
+    let system_root = format!(r"{}\", system_drive.to_string_lossy());
+    root.eq_ignore_ascii_case(&system_root)
+}
+
+fn volume_root(wide: &[u16]) -> Option<String> {
+    let mut buffer = vec![0u16; 261]; // MAX_PATH + 1
+
+    win32_call("GetVolumePathNameW");
+    unsafe { GetVolumePathNameW(PCWSTR(wide.as_ptr()), &mut buffer) }.ok()?;
+
+    let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+    Some(String::from_utf16_lossy(&buffer[..len]))
+}
+
+/// What `GetVolumeInformationW` reports about a volume.
+struct VolumeInformation {
+    label: String,
+    serial: u32,
+    max_component: u32,
+    flags: u32,
+    fs_name: String,
+}
+
+/// Queries everything `GetVolumeInformationW` knows about the volume at
+/// `root` in one call, so an inspection asks the volume (or the server
+/// behind a share) once.
+fn volume_information(root: &str) -> Option<VolumeInformation> {
+    let root = to_pwstr(root);
+    let mut label = vec![0u16; 261]; // MAX_PATH + 1
+    let mut name = vec![0u16; 261];
+    let (mut serial, mut max_component, mut flags) = (0u32, 0u32, 0u32);
+
+    win32_call("GetVolumeInformationW");
+    unsafe {
+        GetVolumeInformationW(
+            PCWSTR(root.as_ptr()),
+            Some(&mut label),
+            Some(&mut serial),
+            Some(&mut max_component),
+            Some(&mut flags),
+            Some(&mut name),
+        )
+    }
+    .ok()?;
+
+    let text = |buf: &[u16]| {
+        let len = buf.iter().position(|&c| c == 0).unwrap_or(buf.len());
+        String::from_utf16_lossy(&buf[..len])
+    };
+    Some(VolumeInformation {
+        label: text(&label),
+        serial,
+        max_component,
+        flags,
+        fs_name: text(&name),
+    })
+}
+
+/// Combines the volume `serial` with a hash of the volume GUID.
+///
+/// Network shares have no volume GUID, so only the serial is used for them.
+fn volume_id(root: &str, serial: u32) -> (u64, DeviceId) {
+    let root = to_pwstr(root);
+    let mut guid = vec![0u16; 50];
+    win32_call("GetVolumeNameForVolumeMountPointW");
+    match unsafe { GetVolumeNameForVolumeMountPointW(PCWSTR(root.as_ptr()), &mut guid) } {
+        Ok(()) => {
+            let len = guid.iter().position(|&c| c == 0).unwrap_or(guid.len());
+            let id = (super::fnv1a(guid[..len].iter().copied()) << 32) | serial as u64;
+            let name = String::from_utf16_lossy(&guid[..len]);
+            (id, DeviceId::from_volume_name(&name))
         }
+        Err(_) => (serial as u64, DeviceId::from_serial(serial)),
+    }
+}
+
+/// The client's filesystem name identifies redirectors such as NFS; SMB
+/// reports the server's (`NTFS`), so the UNC name decides the rest.
+fn get_remote_type(base_path: &Option<String>, fs_type: Option<&str>) -> Option<RemoteType> {
+    fs_type
+        .and_then(remote::from_fs_type)
+        .or_else(|| base_path.as_deref().map(remote::from_unc))
+}
+
+/// Returns the path in `\\server\share` form if it is a UNC path.
+///
+/// Accepts forward slashes and the `\\?\UNC\` long-path prefix; other
+/// `\\?\` and `\\.\` device paths are not UNC.
+fn unc_path(path: &Path) -> Option<String> {
+    let s = path.to_string_lossy().replace('/', "\\");
+
+    if let Some(rest) = s.strip_prefix(r"\\?\UNC\") {
+        return Some(format!(r"\\{rest}"));
     }
+    if s.starts_with(r"\\?\") || s.starts_with(r"\\.\") {
+        return None;
+    }
+
+    let rest = s.strip_prefix(r"\\")?;
+    let server = rest.split('\\').next().unwrap_or_default();
+    (!server.is_empty()).then_some(s)
 }
 
 fn return_first_two(path: &Path) -> Vec<u16> {
@@ -92,6 +853,7 @@ fn get_universal_name(wide: &[u16]) -> Option<String> {
     let dwinfolevel = UNC_INFO_LEVEL(1);
     let mut buffer: Vec<u8> = Vec::new();
 
+    win32_call("WNetGetUniversalNameW");
     let result = unsafe {
         WNetGetUniversalNameW(
             PCWSTR(wide.as_ptr()),
@@ -107,6 +869,7 @@ fn get_universal_name(wide: &[u16]) -> Option<String> {
 
     let mut buffer: Vec<u8> = vec![0u8; size as usize];
 
+    win32_call("WNetGetUniversalNameW");
     let result = unsafe {
         WNetGetUniversalNameW(
             PCWSTR(wide.as_ptr()),
@@ -120,199 +883,777 @@ fn get_universal_name(wide: &[u16]) -> Option<String> {
         return None;
     }
 
-    let un = buffer.as_ptr() as *const UNIVERSAL_NAME_INFOW;
+    parse_universal_name(&buffer)
+}
 
-    unsafe { (*un).lpUniversalName.to_string().ok() }
+/// Reads the string that a `UNIVERSAL_NAME_INFOW` at the start of `buffer`
+/// points to, returning `None` unless the pointer and the whole NUL-terminated
+/// string lie inside `buffer`.
+fn parse_universal_name(buffer: &[u8]) -> Option<String> {
+    if buffer.len() < size_of::<UNIVERSAL_NAME_INFOW>() {
+        return None;
+    }
+    // a Vec<u8> is only byte-aligned
+    let info = unsafe { std::ptr::read_unaligned(buffer.as_ptr() as *const UNIVERSAL_NAME_INFOW) };
+    string_after::<UNIVERSAL_NAME_INFOW>(buffer, info.lpUniversalName.0 as usize)
 }
 
-/// Probes a path to determine its current mount/connection status.
-///
-/// This function attempts to access filesystem metadata for the given path
-/// and classifies its availability based on the result.
-///
-/// It is primarily used to detect whether a remote or removable filesystem
-/// is currently reachable.
-///
-/// # Returns
-///
-/// - [`PathStatus::Mounted`] — The path responded to metadata access
-/// - [`PathStatus::Disconnected`] — The path appears unavailable (typically
-///   network or device not connected) *(Windows only — see below)*
-/// - [`PathStatus::Unknown`] — Status could not be determined reliably
-///
-/// # Behavior
-///
-/// This function performs a real filesystem probe using `std::fs::metadata`.
-/// On remote filesystems this may involve network I/O and can block for a
-/// noticeable amount of time if the target is unreachable.
-///
-/// # Platform differences
-///
-/// ## Windows
-///
-/// Error kinds are mapped to status:
-///
-/// - `NotFound`, `TimedOut`, `NetworkDown`, `NotConnected` → Disconnected
-/// - `PermissionDenied` → Mounted (exists but access restricted)
-/// - Other errors → Unknown
-///
-/// ## Unix
+/// Reads the NUL-terminated string at address `ptr`, which must lie in
+/// `buffer` after the `T` at its start.
+fn string_after<T>(buffer: &[u8], ptr: usize) -> Option<String> {
+    let offset = ptr.checked_sub(buffer.as_ptr() as usize)?;
+    if offset < size_of::<T>() || offset >= buffer.len() {
+        return None;
+    }
+
+    let units: Vec<u16> = buffer[offset..]
+        .chunks_exact(2)
+        .map(|c| u16::from_ne_bytes([c[0], c[1]]))
+        .take_while(|&c| c != 0)
+        .collect();
+    // no terminator before the end of the buffer
+    if (offset + units.len() * 2 + 2) > buffer.len() {
+        return None;
+    }
+    String::from_utf16(&units).ok()
+}
+
+/// Records which network provider serves `unc`, e.g. `Microsoft Windows
+/// Network` or `NFS Network`. Asks the provider, so only for a report.
+fn record_provider(unc: &str, rec: &mut Recorder) {
+    if !rec.is_enabled() {
+        return;
+    }
+    if let Some(provider) = rec.time("WNetGetResourceInformationW", || provider_name(unc)) {
+        rec.raw("provider", provider);
+    }
+}
+
+fn provider_name(unc: &str) -> Option<String> {
+    let mut remote = to_pwstr(unc);
+    let resource = NETRESOURCEW {
+        dwType: RESOURCETYPE_DISK,
+        lpRemoteName: PWSTR::from_raw(remote.as_mut_ptr()),
+        ..Default::default()
+    };
+    // the NETRESOURCEW is followed by the strings it points to
+    let mut buffer = vec![0u8; 4096];
+    let mut size = buffer.len() as u32;
+    let mut system = PWSTR::null();
+
+    win32_call("WNetGetResourceInformationW");
+    let result = unsafe {
+        WNetGetResourceInformationW(
+            &resource,
+            buffer.as_mut_ptr() as *mut c_void,
+            &mut size,
+            &mut system,
+        )
+    };
+    if result != NO_ERROR {
+        return None;
+    }
+
+    let info = unsafe { std::ptr::read_unaligned(buffer.as_ptr() as *const NETRESOURCEW) };
+    string_after::<NETRESOURCEW>(&buffer, info.lpProvider.0 as usize)
+}
+
+/// Probes a path like [`check_status`](crate::check_status), but reports
+/// malformed input as an error.
 ///
-/// Currently uses a simpler probe:
+/// Every I/O outcome maps to a [`PathStatus`]; an error is returned only for
+/// programmer-level problems such as an empty path or one containing a NUL
+/// character.
 ///
-/// - Success → Mounted
-/// - Any error → Unknown
+/// # Errors
 ///
-/// (Future versions may distinguish disconnected network mounts more precisely.)
+/// Returns [`InspectPathError::InvalidPath`] if the path cannot be passed to
+/// the OS.
 ///
 /// # Examples
 ///
 /// ```rust,no_run
 /// use std::path::Path;
-/// use inspect_path::inspect_path;
-///
-/// let mut info = inspect_path(Path::new("/")).unwrap();
-/// info.check_status();
+/// use inspect_path::{PathStatus, probe_status};
 ///
-/// if info.is_status_mounted() {
-///     println!("Path is reachable");
+/// match probe_status(Path::new(r"Z:\"))? {
+///     PathStatus::NotMounted => println!("Z: is not mapped"),
+///     status => println!("{status}"),
 /// }
+/// # Ok::<(), inspect_path::InspectPathError>(())
 /// ```
-///
-/// # Notes
-///
-/// This is a heuristic check. Some filesystems may report as available even
-/// if later operations fail, and some virtual filesystems may always appear
-/// mounted.
-pub fn check_status(path: &Path) -> PathStatus {
-    match std::fs::metadata(path) {
-        Ok(_) => PathStatus::Mounted,
-        Err(e) => match e.kind() {
-            ErrorKind::NotFound
-            | ErrorKind::TimedOut
-            | ErrorKind::NetworkDown
-            | ErrorKind::NotConnected => PathStatus::Disconnected,
-
-            ErrorKind::PermissionDenied => PathStatus::Mounted,
-
-            _ => PathStatus::Unknown,
-        },
-    }
+pub fn probe_status(path: impl AsRef<Path>) -> Result<PathStatus, InspectPathError> {
+    super::probe_with(path.as_ref(), WIN32_STATUS)
 }
 
+// Win32 error of a failed metadata probe → status
+const WIN32_STATUS: &[(i32, PathStatus)] = &[
+    (2, PathStatus::Disconnected),         // ERROR_FILE_NOT_FOUND
+    (3, PathStatus::Disconnected),         // ERROR_PATH_NOT_FOUND
+    (53, PathStatus::Disconnected),        // ERROR_BAD_NETPATH
+    (55, PathStatus::Disconnected),        // ERROR_DEV_NOT_EXIST
+    (64, PathStatus::Disconnected),        // ERROR_NETNAME_DELETED
+    (67, PathStatus::Disconnected),        // ERROR_BAD_NET_NAME
+    (121, PathStatus::Disconnected),       // ERROR_SEM_TIMEOUT
+    (1222, PathStatus::Disconnected),      // ERROR_NO_NETWORK
+    (1231, PathStatus::Disconnected),      // ERROR_NETWORK_UNREACHABLE
+    (1232, PathStatus::Disconnected),      // ERROR_HOST_UNREACHABLE
+    (5, PathStatus::PermissionLimited),    // ERROR_ACCESS_DENIED
+    (1326, PathStatus::PermissionLimited), // ERROR_LOGON_FAILURE
+    (21, PathStatus::NoMedia),             // ERROR_NOT_READY
+    (15, PathStatus::NotMounted),          // ERROR_INVALID_DRIVE
+    (2250, PathStatus::NotMounted),        // ERROR_NOT_CONNECTED
+];
+
 fn to_pwstr(s: &str) -> Vec<u16> {
     let mut v: Vec<u16> = s.encode_utf16().collect();
     v.push(0); // null terminator
     v
 }
 
-pub fn mount_path(local: &str, remote: &str) -> Result<(), InspectPathError> {
-    mount_path_internal(local, remote, None, None)?;
-    Ok(())
+/// Closes the notification window so the mount watcher thread exits.
+#[derive(Debug)]
+pub(crate) struct MountWatchStop(isize); // HWND, which isn't Send
+
+impl MountWatchStop {
+    pub(crate) fn stop(&self) {
+        let hwnd = HWND(self.0 as *mut c_void);
+        let _ = unsafe { PostMessageW(Some(hwnd), WM_CLOSE, WPARAM(0), LPARAM(0)) };
+    }
 }
 
-pub fn mount_path_as_user(
-    local: &str,
-    remote: &str,
-    user: &str,
-    password: &str,
-) -> Result<(), InspectPathError> {
-    mount_path_internal(local, remote, Some(user), Some(password))?;
-    Ok(())
+thread_local! {
+    // the window procedure runs on the watcher thread, so it finds its sender here
+    static MOUNT_EVENTS: RefCell<Option<mpsc::Sender<MountEvent>>> = const { RefCell::new(None) };
 }
 
-fn mount_path_internal(
-    local: &str,
-    remote: &str,
-    user: Option<&str>,
-    password: Option<&str>,
-) -> Result<(), InspectPathError> {
-    let mut local = to_pwstr(local); // "Z:"
-    let mut remote = to_pwstr(remote); // r"\\server\share"
+/// Spawns a thread owning a hidden window that turns volume
+/// `WM_DEVICECHANGE` broadcasts into [`MountEvent`]s.
+pub(crate) fn watch_mounts(
+    tx: mpsc::Sender<MountEvent>,
+) -> Result<(MountWatchStop, JoinHandle<()>), InspectPathError> {
+    let (ready_tx, ready_rx) = mpsc::channel();
 
-    let user_buf = user.map(to_pwstr);
-    let pass_buf = password.map(to_pwstr);
+    let handle = thread::Builder::new()
+        .name("inspect_path-mounts".into())
+        .spawn(move || {
+            MOUNT_EVENTS.with(|events| *events.borrow_mut() = Some(tx));
+            let hwnd = match create_notify_window() {
+                Ok(hwnd) => hwnd,
+                Err(e) => {
+                    let _ = ready_tx.send(Err(e));
+                    return;
+                }
+            };
+            let _ = ready_tx.send(Ok(hwnd.0 as isize));
 
-    let user_pcw = user_buf
-        .as_ref()
-        .map(|v| PCWSTR::from_raw(v.as_ptr()))
-        .unwrap_or(PCWSTR::null());
+            let mut msg = MSG::default();
+            // -1 is an error, 0 is WM_QUIT
+            while unsafe { GetMessageW(&mut msg, None, 0, 0) }.0 > 0 {
+                unsafe { DispatchMessageW(&msg) };
+            }
+        })
+        .map_err(|source| InspectPathError::Io {
+            path: PathBuf::new(),
+            source,
+        })?;
 
-    let pass_pcw = pass_buf
-        .as_ref()
-        .map(|v| PCWSTR::from_raw(v.as_ptr()))
-        .unwrap_or(PCWSTR::null());
+    match ready_rx.recv() {
+        Ok(Ok(hwnd)) => Ok((MountWatchStop(hwnd), handle)),
+        Ok(Err(e)) => {
+            let _ = handle.join();
+            Err(e)
+        }
+        Err(_) => Err(InspectPathError::General {
+            path: None,
+            msg: "mount watcher thread exited".to_string(),
+        }),
+    }
+}
 
-    let nr = NETRESOURCEW {
-        dwType: RESOURCETYPE_DISK,
-        lpLocalName: PWSTR::from_raw(local.as_mut_ptr()),
-        lpRemoteName: PWSTR::from_raw(remote.as_mut_ptr()),
-        lpProvider: PWSTR::null(),
+fn create_notify_window() -> Result<HWND, InspectPathError> {
+    let sys_error = |context| InspectPathError::Sys {
+        path: PathBuf::new(),
+        errno: std::io::Error::last_os_error().raw_os_error().unwrap_or(0),
+        context,
+    };
+
+    let class = to_pwstr("inspect_path_mount_events");
+    let instance: HINSTANCE = unsafe { GetModuleHandleW(PCWSTR::null()) }
+        .map_err(|_| sys_error("GetModuleHandleW"))?
+        .into();
+
+    let wc = WNDCLASSW {
+        lpfnWndProc: Some(mount_wnd_proc),
+        hInstance: instance,
+        lpszClassName: PCWSTR(class.as_ptr()),
         ..Default::default()
     };
+    // fails harmlessly with ERROR_CLASS_ALREADY_EXISTS for later subscriptions
+    unsafe { RegisterClassW(&wc) };
 
-    let result = unsafe {
-        WNetAddConnection2W(
-            &nr,
-            pass_pcw, // password
-            user_pcw, // username
-            windows::Win32::NetworkManagement::WNet::NET_CONNECT_FLAGS(0),
+    // not HWND_MESSAGE: message-only windows don't receive broadcasts
+    unsafe {
+        CreateWindowExW(
+            WINDOW_EX_STYLE(0),
+            PCWSTR(class.as_ptr()),
+            PCWSTR::null(),
+            WINDOW_STYLE(0),
+            0,
+            0,
+            0,
+            0,
+            None,
+            None,
+            Some(instance),
+            None,
         )
-    };
+    }
+    .map_err(|_| sys_error("CreateWindowExW"))
+}
 
-    if result == NO_ERROR {
-        Ok(())
-    } else {
-        Err(InspectPathError::General(format!(
-            "Win32 error: {}",
-            result.0
-        )))
-    }
-}
-
-pub fn try_mount_if_needed(path: &Path, remote: &Path) -> Result<(), InspectPathError> {
-    if let Err(e) = inspect_path(path) {
-        match e {
-            InspectPathError::InvalidPath(_) => {
-                mount_path(
-                    path.to_string_lossy()
-                        .chars()
-                        .take(2)
-                        .collect::<String>()
-                        .as_str(),
-                    remote
-                        .to_str()
-                        .ok_or(InspectPathError::General("Conversion Error".into()))?,
-                )?;
+unsafe extern "system" fn mount_wnd_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    match msg {
+        WM_DEVICECHANGE => {
+            let kind = match wparam.0 as u32 {
+                DBT_DEVICEARRIVAL => Some(MountEventKind::Added),
+                DBT_DEVICEREMOVECOMPLETE => Some(MountEventKind::Removed),
+                _ => None,
+            };
+            if let Some(kind) = kind
+                && lparam.0 != 0
+                && unsafe { (*(lparam.0 as *const DEV_BROADCAST_HDR)).dbch_devicetype }
+                    == DBT_DEVTYP_VOLUME
+            {
+                let volume = unsafe { &*(lparam.0 as *const DEV_BROADCAST_VOLUME) };
+                for letter in drive_letters(volume.dbcv_unitmask) {
+                    let root = format!(r"{letter}:\");
+                    let fs_type = match kind {
+                        MountEventKind::Added => volume_information(&root).map(|v| v.fs_name),
+                        _ => None,
+                    };
+                    let event = MountEvent {
+                        kind,
+                        mount_point: PathBuf::from(root),
+                        fs_type,
+                    };
+                    MOUNT_EVENTS.with(|events| {
+                        if let Some(tx) = &*events.borrow() {
+                            let _ = tx.send(event);
+                        }
+                    });
+                }
             }
-            e => return Err(e),
+            LRESULT(1)
+        }
+        WM_CLOSE => {
+            let _ = unsafe { DestroyWindow(hwnd) };
+            LRESULT(0)
+        }
+        WM_DESTROY => {
+            unsafe { PostQuitMessage(0) };
+            LRESULT(0)
         }
+        _ => unsafe { DefWindowProcW(hwnd, msg, wparam, lparam) },
+    }
+}
+
+/// Expands a `dbcv_unitmask` (bit 0 = `A:`) into drive letters.
+fn drive_letters(mask: u32) -> impl Iterator<Item = char> {
+    (0..26u8)
+        .filter(move |bit| mask & (1 << bit) != 0)
+        .map(|bit| (b'A' + bit) as char)
+}
+
+fn volume_flags(root: &str) -> Option<u32> {
+    volume_information(root).map(|v| v.flags)
+}
+
+/// Whether the volume of `info` supports `(hard links, symbolic links)`.
+///
+/// Read from `FILE_SUPPORTS_HARD_LINKS` and `FILE_SUPPORTS_REPARSE_POINTS`.
+/// Creating a symbolic link on NTFS additionally needs
+/// `SeCreateSymbolicLinkPrivilege` or Developer Mode.
+pub(crate) fn link_support(info: &PathInfo) -> Option<(bool, bool)> {
+    let root = info.mount_point.as_deref()?.to_string_lossy().into_owned();
+    let flags = volume_flags(&root)?;
+    Some((
+        flags & FILE_SUPPORTS_HARD_LINKS != 0,
+        flags & FILE_SUPPORTS_REPARSE_POINTS != 0,
+    ))
+}
+
+/// Whether names are case-sensitive at the path of `info`.
+///
+/// Volumes without `FILE_CASE_SENSITIVE_SEARCH` (FAT, exFAT) never are. NTFS
+/// and ReFS set the flag, but Win32 still folds case unless the directory has
+/// per-directory case sensitivity enabled (`fsutil file setCaseSensitiveInfo`),
+/// so the nearest existing directory is asked.
+pub(crate) fn is_case_sensitive(info: &PathInfo) -> Option<bool> {
+    let root = info.mount_point.as_deref()?.to_string_lossy().into_owned();
+    if volume_flags(&root)? & FILE_CASE_SENSITIVE_SEARCH == 0 {
+        return Some(false);
+    }
+    let dir = info.path.ancestors().find(|p| p.is_dir())?;
+    let wide = path_to_wide(dir);
+
+    let handle = unsafe {
+        CreateFileW(
+            PCWSTR(wide.as_ptr()),
+            FILE_READ_ATTRIBUTES.0,
+            FILE_SHARE_READ | FILE_SHARE_WRITE | FILE_SHARE_DELETE,
+            None,
+            OPEN_EXISTING,
+            FILE_FLAG_BACKUP_SEMANTICS,
+            None,
+        )
+    }
+    .ok()?;
+
+    let mut case_info = FILE_CASE_SENSITIVE_INFO::default();
+    let queried = unsafe {
+        GetFileInformationByHandleEx(
+            handle,
+            FileCaseSensitiveInfo,
+            &mut case_info as *mut _ as *mut c_void,
+            size_of::<FILE_CASE_SENSITIVE_INFO>() as u32,
+        )
     };
+    let _ = unsafe { CloseHandle(handle) };
+    // older Windows without the information class: folds case
+    Some(queried.is_ok() && case_info.Flags & FILE_CS_FLAG_CASE_SENSITIVE_DIR != 0)
+}
 
-    Ok(())
-}
-
-pub fn try_mount_if_needed_as_user(path: &Path, remote: &Path, user: &str, password: &str) -> Result<(), InspectPathError> {
-    if let Err(e) = inspect_path(path) {
-        match e {
-            InspectPathError::InvalidPath(_) => {
-                mount_path_as_user(
-                    path.to_string_lossy()
-                        .chars()
-                        .take(2)
-                        .collect::<String>()
-                        .as_str(),
-                    remote
-                        .to_str()
-                        .ok_or(InspectPathError::General("Conversion Error".into()))?,
-                    user,
-                    password
-                        
-                )?;
-            }
-            e => return Err(e),
+/// Whether the volume is a Dev Drive: ReFS with the persistent
+/// `PERSISTENT_VOLUME_STATE_DEV_VOLUME` state set (Windows 11 22H2 and
+/// later; older systems reject the query).
+pub(crate) fn is_dev_drive(info: &PathInfo) -> bool {
+    let is_refs = info
+        .fs_type
+        .as_deref()
+        .is_some_and(|t| t.eq_ignore_ascii_case("ReFS"));
+    let Some(root) = info.mount_point.as_deref().filter(|_| is_refs) else {
+        return false;
+    };
+    let wide = path_to_wide(root);
+
+    let Ok(handle) = (unsafe {
+        CreateFileW(
+            PCWSTR(wide.as_ptr()),
+            FILE_READ_ATTRIBUTES.0,
+            FILE_SHARE_READ | FILE_SHARE_WRITE | FILE_SHARE_DELETE,
+            None,
+            OPEN_EXISTING,
+            FILE_FLAG_BACKUP_SEMANTICS,
+            None,
+        )
+    }) else {
+        return false;
+    };
+
+    let query = FILE_FS_PERSISTENT_VOLUME_INFORMATION {
+        FlagMask: PERSISTENT_VOLUME_STATE_DEV_VOLUME,
+        Version: 1,
+        ..Default::default()
+    };
+    let mut state = FILE_FS_PERSISTENT_VOLUME_INFORMATION::default();
+    let queried = unsafe {
+        DeviceIoControl(
+            handle,
+            FSCTL_QUERY_PERSISTENT_VOLUME_STATE,
+            Some(&query as *const _ as *const c_void),
+            size_of::<FILE_FS_PERSISTENT_VOLUME_INFORMATION>() as u32,
+            Some(&mut state as *mut _ as *mut c_void),
+            size_of::<FILE_FS_PERSISTENT_VOLUME_INFORMATION>() as u32,
+            None,
+            None,
+        )
+    };
+    let _ = unsafe { CloseHandle(handle) };
+    queried.is_ok() && state.VolumeFlags & PERSISTENT_VOLUME_STATE_DEV_VOLUME != 0
+}
+
+/// Queries the size and free space of the volume `path` is on.
+pub(crate) fn space_info(path: &Path) -> Result<SpaceInfo, InspectPathError> {
+    // GetDiskFreeSpaceExW needs an existing directory; the root always is one
+    let root = volume_root(&path_to_wide(path))
+        .ok_or_else(|| InspectPathError::InvalidPath(path.display().to_string()))?;
+    let wide = to_pwstr(&root);
+    let (mut available, mut total, mut free) = (0u64, 0u64, 0u64);
+
+    crate::probe::timed(path, || {
+        unsafe {
+            GetDiskFreeSpaceExW(
+                PCWSTR(wide.as_ptr()),
+                Some(&mut available),
+                Some(&mut total),
+                Some(&mut free),
+            )
         }
+        // read the error before a slow probe handler gets to run
+        .map_err(|_| std::io::Error::last_os_error())
+    })
+    .map_err(|e| super::space_error(path, e, WIN32_STATUS))?;
+
+    Ok(SpaceInfo {
+        total,
+        free,
+        available,
+    })
+}
+
+/// The inputs to [`PathInfo::stable_id`](crate::PathInfo::stable_id) for
+/// `info`, most stable first.
+pub(crate) fn identity_parts(info: &PathInfo) -> Vec<String> {
+    let fs_type = info.fs_type_name().unwrap_or_default().to_string();
+    let root = info.mount_point().map(|r| r.to_string_lossy().into_owned());
+
+    if info.is_remote() {
+        // a mapped drive letter can change; the share it points at doesn't
+        let share = root
+            .as_deref()
+            .and_then(|r| get_universal_name(&to_pwstr(r)))
+            .or(root)
+            .unwrap_or_else(|| info.path().to_string_lossy().into_owned());
+        return vec!["remote".into(), fs_type, share.to_lowercase()];
+    }
+    match info.device_id() {
+        Some(id) => vec!["volume".into(), fs_type, id.to_string()],
+        None => vec![
+            "path".into(),
+            fs_type,
+            info.path().to_string_lossy().to_lowercase(),
+        ],
+    }
+}
+
+/// Returns the (logical, physical) sector sizes of the disk `path` is on.
+pub(crate) fn sector_sizes(path: &Path) -> Option<(u32, u32)> {
+    let handle = open_volume(path)?;
+    let query = STORAGE_PROPERTY_QUERY {
+        PropertyId: StorageAccessAlignmentProperty,
+        QueryType: PropertyStandardQuery,
+        ..Default::default()
+    };
+    let mut alignment = STORAGE_ACCESS_ALIGNMENT_DESCRIPTOR::default();
+    let queried = unsafe {
+        DeviceIoControl(
+            handle,
+            IOCTL_STORAGE_QUERY_PROPERTY,
+            Some(&query as *const _ as *const c_void),
+            size_of::<STORAGE_PROPERTY_QUERY>() as u32,
+            Some(&mut alignment as *mut _ as *mut c_void),
+            size_of::<STORAGE_ACCESS_ALIGNMENT_DESCRIPTOR>() as u32,
+            None,
+            None,
+        )
+    };
+    let _ = unsafe { CloseHandle(handle) };
+    queried.ok()?;
+
+    Some((
+        alignment.BytesPerLogicalSector,
+        alignment.BytesPerPhysicalSector,
+    ))
+}
+
+/// Opens the volume `path` is on for device queries.
+///
+/// `C:\` opens `\\.\C:` and `\\?\Volume{...}\` opens the volume by GUID;
+/// shares and folder mount points have no device to open.
+fn open_volume(path: &Path) -> Option<HANDLE> {
+    let root = volume_root(&path_to_wide(path))?;
+    let device = match root.strip_suffix('\\') {
+        Some(letter) if letter.len() == 2 && letter.ends_with(':') => format!(r"\\.\{letter}"),
+        Some(guid) if guid.starts_with(r"\\?\Volume{") => guid.to_string(),
+        _ => return None,
     };
+    let device = to_pwstr(&device);
 
-    Ok(())
+    // no access rights are needed to query device properties
+    unsafe {
+        CreateFileW(
+            PCWSTR(device.as_ptr()),
+            0,
+            FILE_SHARE_READ | FILE_SHARE_WRITE,
+            None,
+            OPEN_EXISTING,
+            FILE_FLAGS_AND_ATTRIBUTES(0),
+            None,
+        )
+    }
+    .ok()
+}
+
+/// Whether the volume `path` is on is an EFI System or recovery partition,
+/// by its GPT partition type or MBR partition id.
+pub(crate) fn is_boot_partition(path: &Path) -> bool {
+    const MBR_EFI: u8 = 0xef;
+
+    let Some(handle) = open_volume(path) else {
+        return false;
+    };
+    let mut partition = PARTITION_INFORMATION_EX::default();
+    let queried = unsafe {
+        DeviceIoControl(
+            handle,
+            IOCTL_DISK_GET_PARTITION_INFO_EX,
+            None,
+            0,
+            Some(&mut partition as *mut _ as *mut c_void),
+            size_of::<PARTITION_INFORMATION_EX>() as u32,
+            None,
+            None,
+        )
+    };
+    let _ = unsafe { CloseHandle(handle) };
+    if queried.is_err() {
+        return false;
+    }
+
+    match partition.PartitionStyle {
+        PARTITION_STYLE_GPT => {
+            let kind = unsafe { partition.Anonymous.Gpt }.PartitionType;
+            kind == PARTITION_SYSTEM_GUID || kind == PARTITION_MSFT_RECOVERY_GUID
+        }
+        PARTITION_STYLE_MBR => {
+            let kind = unsafe { partition.Anonymous.Mbr }.PartitionType;
+            kind == MBR_EFI || kind as u32 == PARTITION_MSFT_RECOVERY
+        }
+        _ => false,
+    }
+}
+
+/// Returns the longest file name, in UTF-16 units, the volume `path` is on
+/// accepts.
+pub(crate) fn max_filename_length(path: &Path) -> Option<u64> {
+    let volume = volume_information(&volume_root(&path_to_wide(path))?)?;
+    Some(volume.max_component as u64).filter(|&n| n > 0)
+}
+
+pub(crate) fn capabilities() -> Capabilities {
+    Capabilities {
+        can_mount: cfg!(feature = "mount"),
+        has_mountinfo: false,
+        has_device_numbers: false,
+        has_volume_labels: true,
+        can_watch_mounts: true,
+        has_quotas: false,
+    }
+}
+
+/// Returns the label of the volume `path` is on, if it has one.
+pub(crate) fn volume_label(path: &Path) -> Option<String> {
+    let volume = volume_information(&volume_root(&path_to_wide(path))?)?;
+    Some(volume.label).filter(|l| !l.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mount_table_drive_letters() {
+        let table = MountTable {
+            remote_drives: vec!['Z'],
+        };
+        assert!(table.is_network(Path::new(r"Z:\share\file")));
+        assert!(table.is_network(Path::new(r"z:\")));
+        assert!(table.is_network(Path::new(r"\\?\Z:\file")));
+        assert!(!table.is_network(Path::new(r"C:\Windows")));
+        assert!(!table.is_network(Path::new(r"relative\Z:")));
+    }
+
+    #[test]
+    fn shadow_copy_roots() {
+        let root = |p: &str| shadow_copy_root(Path::new(p));
+        assert_eq!(
+            root(r"\\?\GLOBALROOT\Device\HarddiskVolumeShadowCopy3\Users\a.txt").as_deref(),
+            Some(r"\\?\GLOBALROOT\Device\HarddiskVolumeShadowCopy3\")
+        );
+        assert_eq!(
+            root(r"\\.\globalroot\device\harddiskvolumeshadowcopy12").as_deref(),
+            Some(r"\\?\GLOBALROOT\Device\harddiskvolumeshadowcopy12\")
+        );
+        assert_eq!(root(r"\\?\GLOBALROOT\Device\HarddiskVolume3\x"), None);
+        assert_eq!(
+            root(r"\\?\GLOBALROOT\Device\HarddiskVolumeShadowCopy\x"),
+            None
+        );
+        assert_eq!(root(r"C:\Users"), None);
+        assert!(!is_snapshot(Path::new(r"\\server\share")));
+    }
+
+    #[test]
+    fn win32_status_table() {
+        for (code, status) in WIN32_STATUS {
+            let err = std::io::Error::from_raw_os_error(*code);
+            assert_eq!(
+                &super::super::status_from_error(&err, WIN32_STATUS),
+                status,
+                "code {code}"
+            );
+        }
+
+        let unmapped = std::io::Error::from_raw_os_error(6); // ERROR_INVALID_HANDLE
+        assert_eq!(
+            super::super::status_from_error(&unmapped, WIN32_STATUS),
+            PathStatus::Unknown
+        );
+    }
+
+    #[test]
+    fn unc_inputs_are_remote() {
+        let smb = unc_path(Path::new(r"\\server\share")).unwrap();
+        assert_eq!(
+            get_remote_type(&Some(smb), Some("NTFS")),
+            Some(RemoteType::SMB)
+        );
+
+        let dav = unc_path(Path::new(r"\\server@SSL\DavWWWRoot\files")).unwrap();
+        assert_eq!(get_remote_type(&Some(dav), None), Some(RemoteType::WebDAV));
+        assert_eq!(get_remote_type(&None, Some("NFS")), Some(RemoteType::NFS));
+
+        assert_eq!(
+            unc_path(Path::new(r"\\?\UNC\server\share\dir")).as_deref(),
+            Some(r"\\server\share\dir")
+        );
+        assert_eq!(
+            unc_path(Path::new("//server/share")).as_deref(),
+            Some(r"\\server\share")
+        );
+
+        assert_eq!(unc_path(Path::new(r"C:\data")), None);
+
+        // no volume queries, so a server that doesn't exist is no slower
+        let info = inspect_path(r"\\nonexistent.invalid\share\dir").unwrap();
+        assert_eq!(*info.kind(), PathType::Remote);
+        assert_eq!(
+            info.mount_point(),
+            Some(Path::new(r"\\nonexistent.invalid\share\"))
+        );
+        assert_eq!(info.fs_type_name(), None);
+        assert_eq!(unc_path(Path::new(r"\\?\C:\data")), None);
+        assert_eq!(unc_path(Path::new(r"\\.\PhysicalDrive0")), None);
+        assert_eq!(unc_path(Path::new(r"\\")), None);
+    }
+
+    /// Builds a buffer shaped like WNetGetUniversalNameW output.
+    fn universal_name_buffer(name: &str, terminate: bool) -> Vec<u8> {
+        let header = size_of::<UNIVERSAL_NAME_INFOW>();
+        let mut buffer = vec![0u8; header];
+        for unit in name.encode_utf16().chain(terminate.then_some(0)) {
+            buffer.extend_from_slice(&unit.to_ne_bytes());
+        }
+        let target = buffer.as_ptr() as usize + header;
+        buffer[..header].copy_from_slice(&target.to_ne_bytes());
+        buffer
+    }
+
+    #[test]
+    fn universal_name_bounds() {
+        let ok = universal_name_buffer(r"\\server\share", true);
+        assert_eq!(
+            parse_universal_name(&ok).as_deref(),
+            Some(r"\\server\share")
+        );
+
+        let unterminated = universal_name_buffer(r"\\server\share", false);
+        assert_eq!(parse_universal_name(&unterminated), None);
+
+        let mut outside = universal_name_buffer(r"\\server\share", true);
+        let bogus = (outside.as_ptr() as usize + outside.len() + 64).to_ne_bytes();
+        outside[..bogus.len()].copy_from_slice(&bogus);
+        assert_eq!(parse_universal_name(&outside), None);
+
+        assert_eq!(parse_universal_name(&[0u8; 3]), None);
+    }
+
+    #[test]
+    fn same_filesystem_checks() {
+        let tmp = std::env::temp_dir();
+        assert!(same_filesystem(&tmp, &tmp).unwrap());
+        assert!(same_filesystem(&tmp, tmp.join(r"inspect_path_missing\file.txt")).unwrap());
+    }
+
+    #[test]
+    fn session_hits_skip_win32_calls() {
+        let session = crate::InspectSession::new();
+        let tmp = std::env::temp_dir();
+        let (first, calls) = record_win32_calls(|| session.inspect(tmp.join("a")));
+        assert!(first.is_ok());
+        assert!(calls.contains(&"GetDriveTypeW"), "{calls:?}");
+
+        // a hit only finds the volume root again; nothing goes to a server
+        let (second, calls) = record_win32_calls(|| session.inspect(tmp.join("b")));
+        assert_eq!(second.unwrap().kind(), first.unwrap().kind());
+        assert_eq!(calls, ["GetVolumePathNameW"]);
+
+        let unc = r"\\nonexistent.invalid\share";
+        session.inspect(format!(r"{unc}\a")).unwrap();
+        let (_, calls) = record_win32_calls(|| session.inspect(format!(r"{unc}\b")));
+        assert!(calls.is_empty(), "{calls:?}");
+    }
+
+    #[test]
+    fn lexical_keeps_plain_paths() {
+        let tmp = std::env::temp_dir();
+        let lexical = inspect_path_lexical(&tmp).unwrap();
+        let followed = inspect_path(&tmp).unwrap();
+        assert_eq!(lexical.kind(), followed.kind());
+        assert_eq!(lexical.mount_point(), followed.mount_point());
+
+        let drive = &tmp.to_string_lossy()[..3];
+        assert!(is_mount_folder(Path::new(drive)));
+        assert!(!is_mount_folder(&tmp.join("not-a-volume")));
+    }
+
+    #[test]
+    fn share_of_unc() {
+        assert_eq!(share_of(r"\\srv\share\dir\file"), r"\\srv\share");
+        assert_eq!(share_of(r"\\srv\share"), r"\\srv\share");
+        assert_eq!(share_of(r"\\srv"), r"\\srv");
+    }
+
+    #[test]
+    fn volumes_include_system_drive() {
+        let system_drive = std::env::var("SystemDrive").unwrap();
+        let root = PathBuf::from(format!(r"{system_drive}\"));
+        let volumes = list_volumes(&VolumeFilter::new()).unwrap();
+        assert!(volumes.iter().any(|v| *v.path() == root));
+
+        let file = root.join(r"Windows\System32\notepad.exe");
+        let info = inspect_path(&file).unwrap();
+        assert_eq!(info.mount_point(), Some(&*root));
+        assert_eq!(info.is_case_sensitive(), Some(false));
+        assert_eq!(info.supports_hardlinks(), Some(true));
+        assert_eq!(info.supports_symlinks(), Some(true));
+        assert_eq!(info.max_filename_length(), Some(255));
+        assert!(info.display_name().ends_with(&format!("({system_drive})")));
+        assert!(!info.is_system_partition());
+        // the system drive can't be a Dev Drive
+        assert!(!info.is_dev_drive());
+
+        let (logical, physical) = sector_sizes(&root).unwrap();
+        assert!(logical >= 512 && physical >= logical);
+    }
+
+    #[test]
+    fn unit_mask_drive_letters() {
+        assert_eq!(drive_letters(0).count(), 0);
+        assert_eq!(drive_letters(0b1).collect::<String>(), "A");
+        assert_eq!(drive_letters(0b10100).collect::<String>(), "CE");
+        assert_eq!(drive_letters(1 << 25).collect::<String>(), "Z");
+
+        assert_eq!(first_free(0), Some('C'));
+        assert_eq!(first_free(0b1100), Some('E'));
+        assert_eq!(first_free(!0b11 & ((1 << 26) - 1)), None);
+        // A: and B: are never offered, even when free
+        assert_eq!(first_free(!0b111), Some('C'));
+    }
 }
@@ -0,0 +1,162 @@
+//! Mapping and unmapping network drives, behind the `mount` feature.
+use super::{inspect_path, to_pwstr};
+use crate::{CredentialStore, InspectPathError, PathInspector};
+use std::{
+    ffi::OsStr,
+    os::windows::ffi::OsStrExt,
+    path::{Path, PathBuf},
+};
+use windows::Win32::Foundation::NO_ERROR;
+use windows::Win32::NetworkManagement::WNet::{
+    CONNECT_UPDATE_PROFILE, NETRESOURCEW, RESOURCETYPE_DISK, WNetAddConnection2W,
+    WNetCancelConnection2W,
+};
+use windows::core::{PCWSTR, PWSTR};
+use zeroize::Zeroizing;
+
+fn os_to_pwstr(s: &OsStr) -> Vec<u16> {
+    s.encode_wide().chain(Some(0)).collect()
+}
+
+pub fn mount_path(
+    local: impl AsRef<OsStr>,
+    remote: impl AsRef<OsStr>,
+) -> Result<(), InspectPathError> {
+    crate::SystemInspector.mount(local.as_ref(), remote.as_ref())
+}
+
+/// [`mount_path`] for [`SystemInspector`](crate::SystemInspector).
+pub(crate) fn mount_share(local: &OsStr, remote: &OsStr) -> Result<(), InspectPathError> {
+    mount_path_internal(local, remote, None, None)
+}
+
+pub fn mount_path_as_user(
+    local: impl AsRef<OsStr>,
+    remote: impl AsRef<OsStr>,
+    user: &str,
+    password: &str,
+) -> Result<(), InspectPathError> {
+    mount_path_internal(local.as_ref(), remote.as_ref(), Some(user), Some(password))?;
+    Ok(())
+}
+
+fn mount_path_internal(
+    local: &OsStr,
+    remote: &OsStr,
+    user: Option<&str>,
+    password: Option<&str>,
+) -> Result<(), InspectPathError> {
+    let remote_path = remote;
+    let mut local = os_to_pwstr(local); // "Z:"
+    let mut remote = os_to_pwstr(remote); // r"\\server\share"
+
+    // explicit credentials win; otherwise those stored for the server, if any
+    let stored = match user.or(password) {
+        Some(_) => None,
+        None => CredentialStore::global().lookup(&remote_path.to_string_lossy()),
+    };
+    let user = user.or(stored.as_ref().map(|c| c.username.as_str()));
+    let password = password.or(stored.as_ref().map(|c| c.password.as_str()));
+
+    let user_buf = user.map(to_pwstr);
+    let pass_buf = password.map(|p| Zeroizing::new(to_pwstr(p)));
+
+    let user_pcw = user_buf
+        .as_ref()
+        .map(|v| PCWSTR::from_raw(v.as_ptr()))
+        .unwrap_or(PCWSTR::null());
+
+    let pass_pcw = pass_buf
+        .as_ref()
+        .map(|v| PCWSTR::from_raw(v.as_ptr()))
+        .unwrap_or(PCWSTR::null());
+
+    let nr = NETRESOURCEW {
+        dwType: RESOURCETYPE_DISK,
+        lpLocalName: PWSTR::from_raw(local.as_mut_ptr()),
+        lpRemoteName: PWSTR::from_raw(remote.as_mut_ptr()),
+        lpProvider: PWSTR::null(),
+        ..Default::default()
+    };
+
+    let result = unsafe {
+        WNetAddConnection2W(
+            &nr,
+            pass_pcw, // password
+            user_pcw, // username
+            windows::Win32::NetworkManagement::WNet::NET_CONNECT_FLAGS(0),
+        )
+    };
+
+    if result == NO_ERROR {
+        Ok(())
+    } else {
+        Err(InspectPathError::Sys {
+            path: PathBuf::from(remote_path),
+            errno: result.0 as i32,
+            context: "WNetAddConnection2W",
+        })
+    }
+}
+
+pub fn unmount_path(local: impl AsRef<OsStr>, force: bool) -> Result<(), InspectPathError> {
+    let local_path = local.as_ref();
+    let local = os_to_pwstr(local_path);
+
+    let result = unsafe {
+        WNetCancelConnection2W(
+            PCWSTR::from_raw(local.as_ptr()),
+            CONNECT_UPDATE_PROFILE,
+            force,
+        )
+    };
+
+    if result == NO_ERROR {
+        Ok(())
+    } else {
+        Err(InspectPathError::Sys {
+            path: PathBuf::from(local_path),
+            errno: result.0 as i32,
+            context: "WNetCancelConnection2W",
+        })
+    }
+}
+
+pub fn try_mount_if_needed(
+    path: impl AsRef<Path>,
+    remote: impl AsRef<Path>,
+) -> Result<(), InspectPathError> {
+    try_mount_if_needed_inner(path.as_ref(), remote.as_ref(), None)
+}
+
+pub fn try_mount_if_needed_as_user(
+    path: impl AsRef<Path>,
+    remote: impl AsRef<Path>,
+    user: &str,
+    password: &str,
+) -> Result<(), InspectPathError> {
+    try_mount_if_needed_inner(path.as_ref(), remote.as_ref(), Some((user, password)))
+}
+
+fn try_mount_if_needed_inner(
+    path: &Path,
+    remote: &Path,
+    credentials: Option<(&str, &str)>,
+) -> Result<(), InspectPathError> {
+    match inspect_path(path) {
+        Err(InspectPathError::InvalidPath(_)) if remote.as_os_str().is_empty() => {
+            Err(InspectPathError::NoRemote(path.to_path_buf()))
+        }
+        Err(InspectPathError::InvalidPath(_)) => {
+            let drive: String = path.to_string_lossy().chars().take(2).collect();
+            mount_path_internal(
+                OsStr::new(&drive),
+                remote.as_os_str(),
+                credentials.map(|(user, _)| user),
+                credentials.map(|(_, password)| password),
+            )
+        }
+        Err(e) => Err(e),
+        Ok(_) => Ok(()),
+    }
+}
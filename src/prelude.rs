@@ -0,0 +1,18 @@
+//! The types and functions most callers need, for a single glob import.
+//!
+//! ```rust
+//! use inspect_path::prelude::*;
+//!
+//! fn describe(path: &str) -> Result<String, InspectPathError> {
+//!     let mut info: PathInfo = inspect_path(path)?;
+//!     info.check_status();
+//!     Ok(match info.kind() {
+//!         PathType::Remote => format!("remote ({:?})", info.remote_type()),
+//!         kind => kind.to_string(),
+//!     })
+//! }
+//! # let _ = describe(".");
+//! ```
+pub use crate::{
+    InspectPathError, PathInfo, PathStatus, PathType, RemoteType, check_status, inspect_path,
+};
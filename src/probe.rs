@@ -0,0 +1,546 @@
+use crate::{
+    MountTable, PathStatus,
+    clock::{Clock, SystemClock},
+    platform,
+};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{
+        Arc, Mutex, OnceLock, PoisonError, RwLock,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+type SlowProbeHandler = Arc<dyn Fn(&Path, Duration) + Send + Sync>;
+
+static SLOW_PROBE: RwLock<Option<(Duration, SlowProbeHandler)>> = RwLock::new(None);
+
+/// Registers a handler that is called whenever a probe takes longer than
+/// `threshold`, replacing any previous handler.
+///
+/// [`check_status`](crate::check_status), [`probe_status`](crate::probe_status)
+/// and [`inspect_path`](crate::inspect_path) time themselves and call the
+/// handler with the path and the elapsed time once they return. It runs on the
+/// probing thread, so keep it cheap.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use inspect_path::set_slow_probe_handler;
+///
+/// set_slow_probe_handler(Duration::from_millis(500), |path, elapsed| {
+///     eprintln!("slow probe: {} took {elapsed:?}", path.display());
+/// });
+/// ```
+pub fn set_slow_probe_handler<F>(threshold: Duration, handler: F)
+where
+    F: Fn(&Path, Duration) + Send + Sync + 'static,
+{
+    *SLOW_PROBE.write().unwrap_or_else(PoisonError::into_inner) =
+        Some((threshold, Arc::new(handler)));
+}
+
+/// Removes the handler set by [`set_slow_probe_handler`].
+pub fn clear_slow_probe_handler() {
+    *SLOW_PROBE.write().unwrap_or_else(PoisonError::into_inner) = None;
+}
+
+/// Runs `f`, reporting it to the slow probe handler if it exceeds the threshold.
+pub(crate) fn timed<T>(path: &Path, f: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let out = f();
+    let elapsed = start.elapsed();
+
+    // clone out so the handler may itself probe or replace the handler
+    let handler = SLOW_PROBE
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .as_ref()
+        .filter(|(threshold, _)| elapsed > *threshold)
+        .map(|(_, handler)| Arc::clone(handler));
+    if let Some(handler) = handler {
+        handler(path, elapsed);
+    }
+    out
+}
+
+// abandoned probes allowed to keep running per path
+const MAX_ORPHANED_PER_PATH: usize = 2;
+
+static ORPHANED: OnceLock<Mutex<HashMap<PathBuf, usize>>> = OnceLock::new();
+
+fn orphaned() -> std::sync::MutexGuard<'static, HashMap<PathBuf, usize>> {
+    ORPHANED
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Probes a path like [`check_status`](crate::check_status), giving up after
+/// `timeout`.
+///
+/// The metadata probe runs on a helper thread. If it hasn't answered when the
+/// deadline passes, [`PathStatus::TimedOut`] is returned and the thread is
+/// left to finish in the background — a probe stuck in the kernel (e.g. on a
+/// hard NFS mount) cannot be cancelled.
+///
+/// To keep repeated polling of a dead mount from piling up threads, at most
+/// two abandoned probes per path are allowed to run; while that limit is
+/// reached, calls for the same path return [`PathStatus::TimedOut`]
+/// immediately without spawning.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::{path::Path, time::Duration};
+/// use inspect_path::{PathStatus, check_status_timeout};
+///
+/// let status = check_status_timeout(Path::new("/mnt/nfs"), Duration::from_secs(2));
+/// if status == PathStatus::TimedOut {
+///     println!("server not answering");
+/// }
+/// ```
+pub fn check_status_timeout(path: impl AsRef<Path>, timeout: Duration) -> PathStatus {
+    status_with_timeout(path.as_ref(), timeout, &SystemClock, |p| {
+        platform::check_status(p)
+    })
+}
+
+pub(crate) fn status_with_timeout<F>(
+    path: &Path,
+    timeout: Duration,
+    clock: &dyn Clock,
+    probe: F,
+) -> PathStatus
+where
+    F: FnOnce(&Path) -> PathStatus + Send + 'static,
+{
+    let key = path.to_path_buf();
+    if orphaned().get(&key).copied().unwrap_or(0) >= MAX_ORPHANED_PER_PATH {
+        return PathStatus::TimedOut;
+    }
+
+    let started = clock.now();
+    let (tx, rx) = mpsc::channel();
+    let abandoned = Arc::new(AtomicBool::new(false));
+    let finished = Arc::new(AtomicBool::new(false));
+    let (thread_abandoned, thread_finished) = (Arc::clone(&abandoned), Arc::clone(&finished));
+    let thread_key = key.clone();
+
+    let spawned = thread::Builder::new()
+        .name("inspect_path-probe".into())
+        .spawn(move || {
+            let status = probe(&thread_key);
+            // send under the lock so the waiter sees either the result or
+            // a consistent orphan count
+            let mut orphaned = orphaned();
+            thread_finished.store(true, Ordering::SeqCst);
+            if thread_abandoned.load(Ordering::SeqCst)
+                && let Some(count) = orphaned.get_mut(&thread_key)
+            {
+                *count -= 1;
+                if *count == 0 {
+                    orphaned.remove(&thread_key);
+                }
+            }
+            let _ = tx.send(status);
+        });
+
+    if spawned.is_err() {
+        return PathStatus::Unknown;
+    }
+
+    match clock.recv_timeout(&rx, started, timeout) {
+        Ok(status) => status,
+        Err(_) => {
+            let mut orphaned = orphaned();
+            match rx.try_recv() {
+                Ok(status) => status,
+                // the result came too late and was dropped; nothing is left
+                // running
+                Err(_) if finished.load(Ordering::SeqCst) => PathStatus::TimedOut,
+                Err(_) => {
+                    abandoned.store(true, Ordering::SeqCst);
+                    *orphaned.entry(key).or_insert(0) += 1;
+                    PathStatus::TimedOut
+                }
+            }
+        }
+    }
+}
+
+/// Checks many paths in parallel, returning their statuses in input order.
+///
+/// At most `max_concurrency` probes run at once (at least one), each bounded
+/// by `timeout` as in [`check_status_timeout`], so the whole call takes about
+/// `paths.len() / max_concurrency` timeouts in the worst case instead of
+/// `paths.len()`.
+///
+/// Paths on the same server (`\\server\...` or `//server/...`) are probed
+/// one after another rather than concurrently, and once one of them times out
+/// the rest are reported as [`PathStatus::TimedOut`] without probing. Other
+/// paths are each treated as their own server.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::{path::Path, time::Duration};
+/// use inspect_path::check_statuses;
+///
+/// let shares = [Path::new(r"\\nas\media"), Path::new(r"\\nas\backup"), Path::new(r"\\build\out")];
+/// let statuses = check_statuses(&shares, Duration::from_secs(2), 8);
+/// for (share, status) in shares.iter().zip(statuses) {
+///     println!("{}: {status}", share.display());
+/// }
+/// ```
+pub fn check_statuses<P: AsRef<Path>>(
+    paths: &[P],
+    timeout: Duration,
+    max_concurrency: usize,
+) -> Vec<PathStatus> {
+    let paths: Vec<&Path> = paths.iter().map(P::as_ref).collect();
+    statuses_with(&paths, timeout, max_concurrency, &SystemClock, |p| {
+        platform::check_status(p)
+    })
+}
+
+pub(crate) fn statuses_with<F>(
+    paths: &[&Path],
+    timeout: Duration,
+    max_concurrency: usize,
+    clock: &dyn Clock,
+    probe: F,
+) -> Vec<PathStatus>
+where
+    F: Fn(&Path) -> PathStatus + Send + Sync + 'static,
+{
+    let probe = Arc::new(probe);
+
+    // group indices by server, keeping first-seen order
+    let mut groups: Vec<(String, Vec<usize>)> = Vec::new();
+    for (i, path) in paths.iter().enumerate() {
+        let key = server_key(path);
+        match groups.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, members)) => members.push(i),
+            None => groups.push((key, vec![i])),
+        }
+    }
+
+    let results = Mutex::new(vec![PathStatus::Unknown; paths.len()]);
+    let next = AtomicUsize::new(0);
+    let workers = max_concurrency.clamp(1, groups.len().max(1));
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                while let Some((_, members)) = groups.get(next.fetch_add(1, Ordering::SeqCst)) {
+                    let mut timed_out = false;
+                    for &i in members {
+                        let status = if timed_out {
+                            PathStatus::TimedOut
+                        } else {
+                            let probe = Arc::clone(&probe);
+                            status_with_timeout(paths[i], timeout, clock, move |p| probe(p))
+                        };
+                        timed_out = status == PathStatus::TimedOut;
+                        results.lock().unwrap_or_else(PoisonError::into_inner)[i] = status;
+                    }
+                }
+            });
+        }
+    });
+
+    results.into_inner().unwrap_or_else(PoisonError::into_inner)
+}
+
+// per mount in check_status_batch, as RetryPolicy's default timeout
+const BATCH_TIMEOUT: Duration = Duration::from_secs(2);
+const BATCH_CONCURRENCY: usize = 8;
+
+/// Checks many paths, probing each mount they live on only once.
+///
+/// The paths are grouped by the mount covering them, matched lexically
+/// against one snapshot of the [`MountTable`]: the deepest mount point on
+/// Unix, the drive or `\\server\share` on Windows. Each mount point is then
+/// probed once with [`check_statuses`] (2 seconds timeout, up to 8 at once)
+/// and its status reported for every path on it, in input order.
+///
+/// The status is the mount's, so a path that doesn't exist on a reachable
+/// mount is still [`PathStatus::Mounted`]; use [`check_statuses`] to probe
+/// each path itself. Paths no mount covers, such as relative paths, and all
+/// paths if the mount table can't be read, are probed individually.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::path::PathBuf;
+/// use inspect_path::check_status_batch;
+///
+/// let watched: Vec<PathBuf> = (0..50).map(|i| format!("/mnt/nfs/job{i}").into()).collect();
+/// // one probe of /mnt/nfs
+/// for (path, status) in check_status_batch(&watched) {
+///     println!("{}: {status}", path.display());
+/// }
+/// ```
+pub fn check_status_batch(paths: &[PathBuf]) -> Vec<(PathBuf, PathStatus)> {
+    let table = MountTable::load().ok();
+    batch_with(
+        paths,
+        |path| table.as_ref()?.mount_root(path),
+        |targets| check_statuses(targets, BATCH_TIMEOUT, BATCH_CONCURRENCY),
+    )
+}
+
+fn batch_with(
+    paths: &[PathBuf],
+    root_of: impl Fn(&Path) -> Option<PathBuf>,
+    probe: impl FnOnce(&[PathBuf]) -> Vec<PathStatus>,
+) -> Vec<(PathBuf, PathStatus)> {
+    // distinct probe targets in first-seen order, and each path's target
+    let mut targets: Vec<PathBuf> = Vec::new();
+    let mut index: HashMap<PathBuf, usize> = HashMap::new();
+    let slots: Vec<usize> = paths
+        .iter()
+        .map(|path| {
+            let target = root_of(path).unwrap_or_else(|| path.clone());
+            *index.entry(target).or_insert_with_key(|target| {
+                targets.push(target.clone());
+                targets.len() - 1
+            })
+        })
+        .collect();
+
+    let statuses = probe(&targets);
+    paths
+        .iter()
+        .zip(slots)
+        .map(|(path, slot)| (path.clone(), statuses[slot].clone()))
+        .collect()
+}
+
+/// `\\server` for UNC-style paths, the whole path otherwise.
+fn server_key(path: &Path) -> String {
+    let s = path.to_string_lossy().replace('/', "\\");
+    match s.strip_prefix(r"\\") {
+        Some(rest) if !rest.is_empty() => {
+            let server = rest.split('\\').next().unwrap_or_default();
+            format!(r"\\{}", server.to_ascii_lowercase())
+        }
+        _ => path.to_string_lossy().into_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+
+    #[test]
+    fn slow_probe_handler_sees_probes() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        set_slow_probe_handler(Duration::ZERO, move |path, _| {
+            sink.lock().unwrap().push(path.to_path_buf());
+        });
+
+        let status_path = Path::new("/inspect_path_slow_probe/status");
+        let inspect_path = Path::new("/inspect_path_slow_probe/inspect");
+        crate::check_status(status_path);
+        let _ = crate::inspect_path(inspect_path);
+        clear_slow_probe_handler();
+        crate::check_status(status_path);
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.iter().filter(|p| *p == status_path).count(), 1);
+        assert!(seen.iter().any(|p| p == inspect_path));
+    }
+
+    #[test]
+    fn parallel_statuses_keep_order_and_limit() {
+        let active = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let (a, p) = (Arc::clone(&active), Arc::clone(&peak));
+        let probe = move |path: &Path| {
+            let now = a.fetch_add(1, Ordering::SeqCst) + 1;
+            p.fetch_max(now, Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(20));
+            a.fetch_sub(1, Ordering::SeqCst);
+            if path.ends_with("slow") {
+                thread::sleep(Duration::from_millis(300));
+            }
+            if path.to_string_lossy().contains("gone") {
+                PathStatus::Disconnected
+            } else {
+                PathStatus::Mounted
+            }
+        };
+
+        let names: Vec<PathBuf> = (0..10)
+            .map(|i| match i {
+                3 => PathBuf::from("/parallel/gone"),
+                7 => PathBuf::from("/parallel/slow"),
+                _ => PathBuf::from(format!("/parallel/{i}")),
+            })
+            .collect();
+        let paths: Vec<&Path> = names.iter().map(PathBuf::as_path).collect();
+
+        let statuses = statuses_with(&paths, Duration::from_millis(150), 3, &SystemClock, probe);
+        assert_eq!(statuses.len(), 10);
+        for (i, status) in statuses.iter().enumerate() {
+            let expected = match i {
+                3 => PathStatus::Disconnected,
+                7 => PathStatus::TimedOut,
+                _ => PathStatus::Mounted,
+            };
+            assert_eq!(status, &expected, "path {i}");
+        }
+        assert!(peak.load(Ordering::SeqCst) <= 3);
+        assert_eq!(active.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn parallel_statuses_dedup_servers() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let probe = move |path: &Path| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            if path.to_string_lossy().contains("deadsrv") {
+                thread::sleep(Duration::from_millis(300));
+            }
+            PathStatus::Mounted
+        };
+
+        let paths = [
+            Path::new(r"\\deadsrv\a"),
+            Path::new(r"\\okay\a"),
+            Path::new("//DEADSRV/b"),
+            Path::new(r"\\okay\b"),
+        ];
+        let statuses = statuses_with(&paths, Duration::from_millis(50), 4, &SystemClock, probe);
+
+        assert_eq!(
+            statuses,
+            [
+                PathStatus::TimedOut,
+                PathStatus::Mounted,
+                PathStatus::TimedOut,
+                PathStatus::Mounted
+            ]
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        assert_eq!(server_key(Path::new("/mnt/nfs")), "/mnt/nfs");
+    }
+
+    #[test]
+    fn batch_probes_each_mount_once() {
+        let paths: Vec<PathBuf> = [
+            "/mnt/nfs/a",
+            "/mnt/nfs/b/c",
+            "/home/me",
+            "relative",
+            "/mnt/nfs",
+            "relative",
+        ]
+        .map(PathBuf::from)
+        .to_vec();
+        let root_of = |path: &Path| {
+            ["/mnt/nfs", "/"]
+                .into_iter()
+                .map(Path::new)
+                .find(|root| path.starts_with(root))
+                .map(Path::to_path_buf)
+        };
+        let mut probed = Vec::new();
+        let results = batch_with(&paths, root_of, |targets| {
+            probed = targets.to_vec();
+            targets
+                .iter()
+                .map(|t| match t.to_str() {
+                    Some("/mnt/nfs") => PathStatus::Stale,
+                    Some("/") => PathStatus::Mounted,
+                    _ => PathStatus::Unknown,
+                })
+                .collect()
+        });
+
+        assert_eq!(
+            probed,
+            [Path::new("/mnt/nfs"), Path::new("/"), Path::new("relative")]
+        );
+        let statuses: Vec<_> = results.iter().map(|(_, s)| s.clone()).collect();
+        assert_eq!(
+            statuses,
+            [
+                PathStatus::Stale,
+                PathStatus::Stale,
+                PathStatus::Mounted,
+                PathStatus::Unknown,
+                PathStatus::Stale,
+                PathStatus::Unknown,
+            ]
+        );
+        assert!(results.iter().map(|(p, _)| p).eq(&paths));
+    }
+
+    #[test]
+    fn timeout_follows_the_clock() {
+        let clock = Arc::new(MockClock::new());
+        let slow = |secs| {
+            let clock = Arc::clone(&clock);
+            move |_: &Path| {
+                clock.sleep(Duration::from_secs(secs));
+                PathStatus::Mounted
+            }
+        };
+        let path = Path::new("/probe/mocked");
+        let timeout = Duration::from_secs(10);
+
+        assert_eq!(
+            status_with_timeout(path, timeout, &*clock, slow(9)),
+            PathStatus::Mounted
+        );
+        assert_eq!(
+            status_with_timeout(path, timeout, &*clock, slow(11)),
+            PathStatus::TimedOut
+        );
+        assert_eq!(clock.elapsed(), Duration::from_secs(20));
+        // a late result is not an orphaned probe
+        assert_eq!(orphaned().get(path), None);
+    }
+
+    #[test]
+    fn slow_probe_times_out_and_caps_orphans() {
+        let path = Path::new("/probe/slow");
+        let slow = |_: &Path| {
+            thread::sleep(Duration::from_millis(300));
+            PathStatus::Mounted
+        };
+
+        for _ in 0..MAX_ORPHANED_PER_PATH {
+            let status = status_with_timeout(path, Duration::from_millis(10), &SystemClock, slow);
+            assert_eq!(status, PathStatus::TimedOut);
+        }
+        assert_eq!(orphaned().get(path), Some(&MAX_ORPHANED_PER_PATH));
+
+        let called = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&called);
+        let status = status_with_timeout(path, Duration::from_secs(5), &SystemClock, move |_| {
+            flag.store(true, Ordering::SeqCst);
+            PathStatus::Mounted
+        });
+        assert_eq!(status, PathStatus::TimedOut);
+        assert!(!called.load(Ordering::SeqCst));
+
+        thread::sleep(Duration::from_millis(500));
+        assert_eq!(orphaned().get(path), None);
+        let status = status_with_timeout(path, Duration::from_secs(5), &SystemClock, |_| {
+            PathStatus::Mounted
+        });
+        assert_eq!(status, PathStatus::Mounted);
+    }
+}
@@ -0,0 +1,231 @@
+//! The one mapping from what a platform reports about a network mount to a
+//! [`RemoteType`], shared by both backends.
+//!
+//! Unix reports a filesystem type name (`nfs4`, `cifs`, `fuse.sshfs`);
+//! Windows reports a UNC name (`\\server\share`) and the filesystem name from
+//! `GetVolumeInformationW`, which is the server's (`NTFS`) for SMB but the
+//! client's (`NFS`) for other redirectors. Both understand URL schemes
+//! (`smb://`, `sftp://`) as found in desktop files and config.
+use crate::RemoteType;
+
+#[cfg(any(unix, windows))]
+pub(crate) const NFS: &[&str] = &["nfs", "nfs4"];
+#[cfg(any(unix, windows))]
+const SMB: &[&str] = &["cifs", "smbfs", "smb3"];
+#[cfg(any(unix, windows))]
+const AFS: &[&str] = &["afs"];
+#[cfg(any(unix, windows))]
+const SSH: &[&str] = &["sshfs", "fuse.sshfs"];
+#[cfg(any(unix, windows))]
+const CEPH: &[&str] = &["ceph", "fuse.ceph"];
+#[cfg(any(unix, windows))]
+const GLUSTER: &[&str] = &["glusterfs", "fuse.glusterfs"];
+// Plan 9 protocol, also how WSL shares its distributions
+#[cfg(any(unix, windows))]
+const NINEP: &[&str] = &["9p"];
+// host directories shared into a VM
+#[cfg(any(unix, windows))]
+const VIRTIOFS: &[&str] = &["virtiofs"];
+#[cfg(any(unix, windows))]
+const DAV: &[&str] = &["davfs", "fuse.davfs"];
+// recognized, but without a variant of their own
+#[cfg(any(unix, windows))]
+const OTHER: &[&str] = &["afp", "ncpfs", "coda", "ocfs2", "gfs", "gfs2"];
+
+/// Maps a filesystem type name to its remote type, or `None` if it isn't a
+/// network filesystem. Case-insensitive, so Windows' `NFS` matches too.
+#[cfg(any(unix, windows))]
+pub(crate) fn from_fs_type(fs_type: &str) -> Option<RemoteType> {
+    let is = |names: &[&str]| names.iter().any(|n| n.eq_ignore_ascii_case(fs_type));
+
+    if is(NFS) {
+        Some(RemoteType::NFS)
+    } else if is(SMB) {
+        Some(RemoteType::SMB)
+    } else if is(AFS) {
+        Some(RemoteType::AFS)
+    } else if is(SSH) {
+        Some(RemoteType::SSHFS)
+    } else if is(CEPH) {
+        Some(RemoteType::Ceph)
+    } else if is(GLUSTER) {
+        Some(RemoteType::GlusterFS)
+    } else if is(NINEP) {
+        Some(RemoteType::NinePfs)
+    } else if is(VIRTIOFS) {
+        Some(RemoteType::VirtioFs)
+    } else if is(DAV) {
+        Some(RemoteType::WebDAV)
+    } else if is(OTHER) {
+        Some(RemoteType::Other(fs_type.to_ascii_lowercase()))
+    } else {
+        None
+    }
+}
+
+/// Whether `fs_type` names a network filesystem.
+#[cfg(unix)]
+pub(crate) fn is_network_fs_type(fs_type: &str) -> bool {
+    from_fs_type(fs_type).is_some()
+}
+
+/// Maps a URL scheme (`smb`, `nfs`, `sftp`, ...) to the remote type it
+/// connects with, or `None` for `file` and other local or unknown schemes.
+/// Case-insensitive.
+pub(crate) fn from_scheme(scheme: &str) -> Option<RemoteType> {
+    match scheme.to_ascii_lowercase().as_str() {
+        "smb" | "cifs" => Some(RemoteType::SMB),
+        "nfs" => Some(RemoteType::NFS),
+        "sftp" | "ssh" => Some(RemoteType::SSHFS),
+        "dav" | "davs" | "webdav" => Some(RemoteType::WebDAV),
+        other @ ("afp" | "ftp") => Some(RemoteType::Other(other.to_string())),
+        _ => None,
+    }
+}
+
+/// Maps a `\\server\share` name to its remote type by the shape of the
+/// server part: the WebDAV redirector's `DavWWWRoot`, WSL's `wsl$` and
+/// `wsl.localhost`, OpenAFS's `\\afs`, and SSHFS-Win's `\\sshfs*` prefixes.
+/// Any other UNC name is an SMB share.
+#[cfg_attr(not(windows), allow(dead_code))]
+pub(crate) fn from_unc(unc: &str) -> RemoteType {
+    let Some(rest) = unc.strip_prefix(r"\\") else {
+        return RemoteType::Unknown;
+    };
+    let server = rest
+        .split('\\')
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+
+    if unc.to_ascii_lowercase().contains("davwwwroot") {
+        RemoteType::WebDAV
+    } else if server == "wsl$" || server == "wsl.localhost" {
+        RemoteType::NinePfs
+    } else if server == "afs" {
+        RemoteType::AFS
+    } else if server == "sshfs" || server.starts_with("sshfs.") {
+        RemoteType::SSHFS
+    } else {
+        RemoteType::SMB
+    }
+}
+
+/// Returns the server named by a mount source or share name: `srv` in
+/// `srv:/export`, `user@srv:/home`, `//srv/share` and `\\srv\share`, or
+/// `fe80::1` in `[fe80::1]:/export`. Used to pick credentials and mount
+/// options for a share.
+#[cfg(all(feature = "mount", any(windows, target_os = "linux", docsrs)))]
+pub(crate) fn host_of(source: &str) -> Option<&str> {
+    let is_sep = |c: char| c == '/' || c == '\\';
+    let host = if let Some(rest) = source
+        .strip_prefix("//")
+        .or_else(|| source.strip_prefix(r"\\"))
+    {
+        rest.split(is_sep).next()?
+    } else {
+        let source = match source.split_once('@') {
+            Some((user, rest)) if !user.contains([':', '/']) => rest,
+            _ => source,
+        };
+        match source.strip_prefix('[') {
+            // the address itself contains `:`
+            Some(v6) => v6.split_once("]:")?.0,
+            None => source.split_once(':')?.0,
+        }
+    };
+    Some(host).filter(|h| !h.is_empty() && !h.contains(is_sep))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every variant with an input that produces it on Unix (a filesystem
+    /// type) and on Windows (a UNC name or filesystem name), or `None` where
+    /// the variant is specific to the other platform.
+    fn inputs(remote: &RemoteType) -> (Option<&'static str>, Option<&'static str>) {
+        // exhaustive, so a new variant has to be added here
+        match remote {
+            RemoteType::WebDAV => (Some("fuse.davfs"), Some(r"\\srv@SSL\DavWWWRoot\x")),
+            RemoteType::NFS => (Some("nfs4"), Some("NFS")),
+            RemoteType::SMB => (Some("cifs"), Some(r"\\srv\share")),
+            RemoteType::AFS => (Some("afs"), Some(r"\\afs\example.com")),
+            RemoteType::SSHFS => (Some("fuse.sshfs"), Some(r"\\sshfs.r\me@host")),
+            RemoteType::Ceph => (Some("ceph"), None),
+            RemoteType::GlusterFS => (Some("glusterfs"), None),
+            RemoteType::NinePfs => (Some("9p"), Some(r"\\wsl.localhost\Ubuntu")),
+            RemoteType::VirtioFs => (Some("virtiofs"), None),
+            RemoteType::Other(_) => (Some("afp"), None),
+            // a mapped drive whose UNC name couldn't be read
+            RemoteType::Unknown => (None, Some("Z:")),
+        }
+    }
+
+    fn on_windows(input: &str) -> RemoteType {
+        from_fs_type(input).unwrap_or_else(|| from_unc(input))
+    }
+
+    #[test]
+    fn every_variant_has_a_source() {
+        let all = [
+            RemoteType::WebDAV,
+            RemoteType::NFS,
+            RemoteType::SMB,
+            RemoteType::AFS,
+            RemoteType::SSHFS,
+            RemoteType::Ceph,
+            RemoteType::GlusterFS,
+            RemoteType::NinePfs,
+            RemoteType::VirtioFs,
+            RemoteType::Other("afp".into()),
+            RemoteType::Unknown,
+        ];
+        for remote in all {
+            let (unix, windows) = inputs(&remote);
+            assert!(unix.is_some() || windows.is_some(), "{remote:?}");
+            if let Some(fs_type) = unix {
+                assert_eq!(from_fs_type(fs_type), Some(remote.clone()), "{fs_type}");
+            }
+            if let Some(input) = windows {
+                assert_eq!(on_windows(input), remote, "{input}");
+            }
+        }
+    }
+
+    #[test]
+    fn local_and_lookalike_inputs() {
+        assert_eq!(from_fs_type("ext4"), None);
+        assert_eq!(from_fs_type("NTFS"), None);
+        assert_eq!(from_fs_type("fuse"), None);
+        #[cfg(unix)]
+        assert!(is_network_fs_type("smb3"));
+        assert_eq!(
+            from_fs_type("OCFS2"),
+            Some(RemoteType::Other("ocfs2".into()))
+        );
+
+        // `@` alone is an SMB server name, not the WebDAV redirector
+        assert_eq!(from_unc(r"\\srv@home\share"), RemoteType::SMB);
+        assert_eq!(from_unc(r"\\WSL$\Debian\home"), RemoteType::NinePfs);
+        assert_eq!(from_unc(r"\\sshfsserver\share"), RemoteType::SMB);
+
+        assert_eq!(from_scheme("SMB"), Some(RemoteType::SMB));
+        assert_eq!(from_scheme("sftp"), Some(RemoteType::SSHFS));
+        assert_eq!(from_scheme("file"), None);
+        assert_eq!(from_scheme("https"), None);
+    }
+
+    #[cfg(all(feature = "mount", any(windows, target_os = "linux")))]
+    #[test]
+    fn hosts_of_sources() {
+        assert_eq!(host_of("srv:/export"), Some("srv"));
+        assert_eq!(host_of("bob@srv.lan:/home"), Some("srv.lan"));
+        assert_eq!(host_of("//nas/share"), Some("nas"));
+        assert_eq!(host_of(r"\\nas\share\dir"), Some("nas"));
+        assert_eq!(host_of("[fe80::1]:/export"), Some("fe80::1"));
+        assert_eq!(host_of("/dev/sda1"), None);
+        assert_eq!(host_of("tmpfs"), None);
+        assert_eq!(host_of("//"), None);
+    }
+}
@@ -0,0 +1,301 @@
+use crate::{PathInfo, platform};
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// Everything an inspection saw on the way to its answer, for bug reports.
+///
+/// Built by [`inspect_report`]. The [`Display`](fmt::Display) impl prints a
+/// multi-line dump; the alternate form (`{:#}`) replaces the path, the
+/// resolved path and the mount source (device, server or UNC share) with
+/// placeholders so the dump can be shared.
+///
+/// The names and contents of the raw entries and decisions are meant for
+/// humans and may change between releases.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct InspectReport {
+    /// The path as given.
+    pub path: PathBuf,
+    /// The path after `~` expansion and symlink resolution, if it resolved.
+    /// Always `None` on Windows, which classifies the path as given.
+    pub resolved_path: Option<PathBuf>,
+    /// Where the answer came from: `mountinfo`, `getfsstat` or `win32`.
+    pub backend: String,
+    /// Raw values read from the system, e.g. the mountinfo line, the statfs
+    /// magic or the `GetDriveTypeW` code, as `(name, value)`.
+    pub raw: Vec<(String, String)>,
+    /// Each classification decision, in the order it was taken.
+    pub decisions: Vec<String>,
+    /// How long each system call took, as `(call, elapsed)`.
+    pub timings: Vec<(String, Duration)>,
+    /// The result [`inspect_path`](crate::inspect_path) would have returned.
+    pub info: Option<PathInfo>,
+    /// The error [`inspect_path`](crate::inspect_path) would have returned.
+    pub error: Option<String>,
+    // kept through serde so a deserialized report still redacts; the values
+    // are already in the unredacted fields
+    #[cfg_attr(feature = "serde", serde(default))]
+    secrets: Vec<(String, String)>,
+}
+
+/// Inspects a path like [`inspect_path`](crate::inspect_path), recording the
+/// raw data, decisions and timings behind the result.
+///
+/// Never fails: an inspection error is stored in
+/// [`InspectReport::error`]. Takes a few more system calls than
+/// `inspect_path`, e.g. `statfs` on Linux even when mountinfo names the type,
+/// or asking the network provider of a Windows share for its name. A slow
+/// inspection is reported to the
+/// [slow probe handler](crate::set_slow_probe_handler) like any other.
+///
+/// # Examples
+///
+/// ```rust
+/// use inspect_path::inspect_report;
+///
+/// let report = inspect_report(std::env::temp_dir());
+/// // paste into an issue
+/// println!("{report:#}");
+/// ```
+pub fn inspect_report(path: impl AsRef<Path>) -> InspectReport {
+    let path = path.as_ref();
+    report_with(path, |rec| {
+        crate::probe::timed(path, || platform::inspect_recorded(path, rec))
+    })
+}
+
+/// Builds the report of `path` from `inspect`, run with a live recorder.
+pub(crate) fn report_with(
+    path: &Path,
+    inspect: impl FnOnce(&mut Recorder) -> Result<PathInfo, crate::InspectPathError>,
+) -> InspectReport {
+    let mut rec = Recorder::new();
+    let result = inspect(&mut rec);
+
+    let resolved_path = rec.resolved.take();
+    // first, so the path wins over a mount point of the same length
+    let found = std::mem::take(&mut rec.secrets);
+    rec.secret(path.to_string_lossy(), "<path>");
+    if let Some(resolved) = &resolved_path {
+        rec.secret(resolved.to_string_lossy(), "<resolved>");
+    }
+    rec.secrets.extend(found);
+
+    let (info, error) = match result {
+        Ok(info) => (Some(info), None),
+        Err(e) => (None, Some(e.to_string())),
+    };
+    InspectReport {
+        path: path.to_path_buf(),
+        resolved_path,
+        backend: rec.backend.to_string(),
+        raw: rec.raw,
+        decisions: rec.decisions,
+        timings: rec.timings,
+        info,
+        error,
+        secrets: rec.secrets,
+    }
+}
+
+impl fmt::Display for InspectReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let redact = f.alternate();
+        let text = |s: &str| {
+            if !redact {
+                return s.to_string();
+            }
+            // longest first, so a path is replaced before its prefixes
+            let mut secrets: Vec<_> = self.secrets.iter().collect();
+            secrets.sort_by_key(|(secret, _)| std::cmp::Reverse(secret.len()));
+            secrets
+                .into_iter()
+                .fold(s.to_string(), |s, (secret, placeholder)| {
+                    s.replace(secret, placeholder)
+                })
+        };
+
+        writeln!(f, "path: {}", text(&self.path.to_string_lossy()))?;
+        match &self.resolved_path {
+            Some(resolved) => writeln!(f, "resolved: {}", text(&resolved.to_string_lossy()))?,
+            None => writeln!(f, "resolved: -")?,
+        }
+        writeln!(f, "backend: {}", self.backend)?;
+        writeln!(f, "raw:")?;
+        for (name, value) in &self.raw {
+            writeln!(f, "  {name}: {}", text(value))?;
+        }
+        writeln!(f, "decisions:")?;
+        for decision in &self.decisions {
+            writeln!(f, "  {}", text(decision))?;
+        }
+        writeln!(f, "timings:")?;
+        for (call, elapsed) in &self.timings {
+            writeln!(f, "  {call}: {elapsed:?}")?;
+        }
+        match (&self.info, &self.error) {
+            (Some(info), _) => write!(f, "result: {}", text(&info.to_string())),
+            (None, Some(error)) => write!(f, "error: {}", text(error)),
+            (None, None) => write!(f, "result: -"),
+        }
+    }
+}
+
+/// Collects what one inspection saw. A disabled recorder, as used by every
+/// entry point except [`inspect_report`], records nothing and doesn't time.
+#[derive(Debug, Default)]
+pub(crate) struct Recorder {
+    enabled: bool,
+    backend: &'static str,
+    resolved: Option<PathBuf>,
+    raw: Vec<(String, String)>,
+    decisions: Vec<String>,
+    timings: Vec<(String, Duration)>,
+    secrets: Vec<(String, String)>,
+}
+
+impl Recorder {
+    fn new() -> Self {
+        Recorder {
+            enabled: true,
+            ..Recorder::default()
+        }
+    }
+
+    #[cfg(any(unix, windows))]
+    pub(crate) fn off() -> Self {
+        Recorder::default()
+    }
+
+    #[cfg(any(target_os = "linux", windows))]
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub(crate) fn backend(&mut self, backend: &'static str) {
+        self.backend = backend;
+    }
+
+    /// Remembers the path the inspection actually classified.
+    #[cfg(unix)]
+    pub(crate) fn resolved(&mut self, resolved: Option<&Path>) {
+        if self.enabled {
+            self.resolved = resolved.map(Path::to_path_buf);
+        }
+    }
+
+    #[cfg(any(unix, windows))]
+    pub(crate) fn raw(&mut self, name: &str, value: impl fmt::Display) {
+        if self.enabled {
+            self.raw.push((name.to_string(), value.to_string()));
+        }
+    }
+
+    #[cfg(any(unix, windows))]
+    pub(crate) fn decide(&mut self, decision: impl fmt::Display) {
+        if self.enabled {
+            self.decisions.push(decision.to_string());
+        }
+    }
+
+    /// Marks a value to replace with `placeholder` in the redacted dump.
+    pub(crate) fn secret(&mut self, value: impl Into<String>, placeholder: &str) {
+        let value = value.into();
+        // a bare `/` or `C:\` would redact every other path
+        if self.enabled && value.chars().filter(|c| c.is_alphanumeric()).count() > 1 {
+            self.secrets.push((value, placeholder.to_string()));
+        }
+    }
+
+    #[cfg(any(unix, windows))]
+    pub(crate) fn time<T>(&mut self, call: &str, f: impl FnOnce() -> T) -> T {
+        if !self.enabled {
+            return f();
+        }
+        let start = std::time::Instant::now();
+        let out = f();
+        self.timings.push((call.to_string(), start.elapsed()));
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disabled_recorder_records_nothing() {
+        let mut rec = Recorder::off();
+        rec.raw("x", 1);
+        rec.decide("y");
+        rec.secret("/home/me", "<path>");
+        assert_eq!(rec.time("z", || 7), 7);
+        assert!(rec.raw.is_empty() && rec.decisions.is_empty());
+        assert!(rec.timings.is_empty() && rec.secrets.is_empty());
+    }
+
+    #[test]
+    fn report_matches_inspect_path() {
+        let tmp = std::env::temp_dir();
+        let report = inspect_report(&tmp);
+
+        assert_eq!(report.info, crate::inspect_path(&tmp).ok());
+        assert!(!report.backend.is_empty());
+        assert!(!report.raw.is_empty());
+        assert!(!report.decisions.is_empty());
+        assert!(!report.timings.is_empty());
+        assert!(format!("{report:#}").starts_with("path: <path>\n"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialized_report_still_redacts() {
+        let report = inspect_report(std::env::temp_dir());
+        let json = serde_json::to_string(&report).unwrap();
+        let back: InspectReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(format!("{back:#}"), format!("{report:#}"));
+    }
+
+    #[test]
+    fn redacted_dump_hides_secrets() {
+        let mut rec = Recorder::new();
+        rec.backend("mountinfo");
+        rec.raw("mount source", "srv:/export/alice");
+        rec.decide("nfs4 on /home/alice: network filesystem");
+        rec.secret("/home/alice/notes.txt", "<path>");
+        rec.secret("/home/alice", "<mount>");
+        rec.secret("srv:/export/alice", "<source>");
+        rec.secret("/", "<root>");
+        let report = InspectReport {
+            path: PathBuf::from("/home/alice/notes.txt"),
+            resolved_path: None,
+            backend: rec.backend.to_string(),
+            raw: rec.raw,
+            decisions: rec.decisions,
+            timings: vec![("statfs".into(), Duration::from_micros(12))],
+            info: None,
+            error: Some("no mount point covers path".into()),
+            secrets: rec.secrets,
+        };
+
+        let plain = report.to_string();
+        assert!(plain.contains("srv:/export/alice"));
+
+        let redacted = format!("{report:#}");
+        assert!(!redacted.contains("alice"), "{redacted}");
+        assert_eq!(
+            redacted,
+            "path: <path>\n\
+             resolved: -\n\
+             backend: mountinfo\n\
+             raw:\n  mount source: <source>\n\
+             decisions:\n  nfs4 on <mount>: network filesystem\n\
+             timings:\n  statfs: 12µs\n\
+             error: no mount point covers path"
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use crate::{InspectPathError, PathInfo, platform};
+use std::{
+    path::Path,
+    sync::{Mutex, PoisonError},
+};
+
+/// Remembers per-volume results across many [`inspect`](Self::inspect)
+/// calls, for classifying thousands of paths on a few volumes.
+///
+/// - **Windows:** the first path on a volume root (`Z:\`, or
+///   `\\server\share` for UNC paths) is inspected in full; later paths on the
+///   same root reuse its drive type, UNC name, volume id and filesystem
+///   name, so a second path on a mapped drive costs a single
+///   `GetVolumePathNameW` and no network calls. `record_win32_calls`, with
+///   the `test-util` feature, lists the calls an inspection made.
+/// - **Unix:** the mount table is read once, and each mount is classified
+///   once. Symlinks are still resolved for every path.
+///
+/// Cached results go stale if the system changes mid-session: a drive letter
+/// remapped to another share, a USB stick swapped at the same mount point.
+/// Call [`invalidate`](Self::invalidate) when that may have happened, or let
+/// [`invalidate_on_mount_events`](Self::invalidate_on_mount_events) do it.
+/// Dropping the session frees the cache.
+///
+/// The session can be shared between threads; inspections through one
+/// session run one at a time.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use inspect_path::InspectSession;
+///
+/// let session = InspectSession::new();
+/// for line in std::io::stdin().lines() {
+///     let line = line.unwrap();
+///     match session.inspect(&line) {
+///         Ok(info) => println!("{info}"),
+///         Err(e) => eprintln!("{line}: {e}"),
+///     }
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct InspectSession {
+    cache: Mutex<platform::SessionCache>,
+}
+
+impl InspectSession {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inspects `path` like [`inspect_path`](crate::inspect_path), reusing
+    /// what the session already knows about its volume.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`inspect_path`](crate::inspect_path). Failures are not
+    /// cached.
+    pub fn inspect(&self, path: impl AsRef<Path>) -> Result<PathInfo, InspectPathError> {
+        let mut cache = self.cache.lock().unwrap_or_else(PoisonError::into_inner);
+        platform::inspect_cached(path.as_ref(), &mut cache)
+    }
+
+    /// Forgets everything cached, so the next inspection of each volume
+    /// queries the system again.
+    pub fn invalidate(&self) {
+        *self.cache.lock().unwrap_or_else(PoisonError::into_inner) = Default::default();
+    }
+
+    /// Starts [`MountEvents`](crate::MountEvents) and invalidates the session
+    /// on every mount or unmount (a device-change broadcast on Windows) until
+    /// the returned handle is dropped or the session is.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`MountEvents::spawn`](crate::MountEvents::spawn), and a
+    /// [`General`](InspectPathError::General) error if the forwarding thread
+    /// can't be spawned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use std::sync::Arc;
+    /// use inspect_path::InspectSession;
+    ///
+    /// let session = Arc::new(InspectSession::new());
+    /// let _events = session.invalidate_on_mount_events().unwrap();
+    /// // inspect through `session` while `_events` is alive
+    /// ```
+    #[cfg(any(target_os = "linux", target_os = "windows"))]
+    pub fn invalidate_on_mount_events(
+        self: &std::sync::Arc<Self>,
+    ) -> Result<crate::MountEvents, InspectPathError> {
+        let (events, rx) = crate::MountEvents::spawn()?;
+        let session = std::sync::Arc::downgrade(self);
+        std::thread::Builder::new()
+            .name("inspect_path-session".into())
+            .spawn(move || {
+                // ends when `events` is dropped and the sender goes with it
+                for _ in rx {
+                    match session.upgrade() {
+                        Some(session) => session.invalidate(),
+                        None => return,
+                    }
+                }
+            })
+            .map_err(|e| InspectPathError::General {
+                path: None,
+                msg: format!("can't spawn session thread: {e}"),
+            })?;
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_inspect_path() {
+        let session = InspectSession::new();
+        let tmp = std::env::temp_dir();
+        for _ in 0..2 {
+            let info = session.inspect(&tmp).unwrap();
+            assert_eq!(info, crate::inspect_path(&tmp).unwrap());
+        }
+        session.invalidate();
+        assert_eq!(session.inspect(&tmp).unwrap().path(), &tmp);
+    }
+}
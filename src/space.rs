@@ -0,0 +1,106 @@
+/// Capacity of the filesystem a path is on, in bytes.
+///
+/// Returned by [`PathInfo::space`](crate::PathInfo::space).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct SpaceInfo {
+    pub total: u64,
+    /// Free space including blocks reserved for the superuser.
+    pub free: u64,
+    /// Free space the calling user can actually write to, after quotas and
+    /// reserved blocks.
+    pub available: u64,
+}
+
+/// The calling user's disk quota on a filesystem, in bytes.
+///
+/// Returned by [`PathInfo::quota`](crate::PathInfo::quota).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Quota {
+    /// Space charged to the user.
+    pub used: u64,
+    /// The hard limit, or the soft limit if only that is set.
+    pub limit: u64,
+}
+
+impl Quota {
+    /// Bytes the user may still write before hitting the limit.
+    pub fn remaining(&self) -> u64 {
+        self.limit.saturating_sub(self.used)
+    }
+}
+
+/// When a volume counts as nearly full, see
+/// [`PathInfo::is_nearly_full`](crate::PathInfo::is_nearly_full).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpaceThreshold {
+    /// Fewer than this many bytes are available.
+    MinFreeBytes(u64),
+    /// Less than this percentage (0–100) of the volume is available.
+    MinFreePercent(f32),
+}
+
+impl SpaceInfo {
+    /// Fraction of the volume available to the caller, from `0.0` to `1.0`.
+    ///
+    /// A volume with a total size of zero (e.g. most virtual filesystems)
+    /// reports `0.0`.
+    pub fn free_fraction(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        self.available as f64 / self.total as f64
+    }
+
+    /// Returns whether the available space is strictly below `threshold`.
+    pub fn is_below(&self, threshold: SpaceThreshold) -> bool {
+        match threshold {
+            SpaceThreshold::MinFreeBytes(bytes) => self.available < bytes,
+            SpaceThreshold::MinFreePercent(percent) => {
+                self.free_fraction() * 100.0 < f64::from(percent)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn space(total: u64, available: u64) -> SpaceInfo {
+        SpaceInfo {
+            total,
+            free: available,
+            available,
+        }
+    }
+
+    #[test]
+    fn byte_thresholds() {
+        let s = space(1000, 100);
+        assert!(s.is_below(SpaceThreshold::MinFreeBytes(101)));
+        assert!(!s.is_below(SpaceThreshold::MinFreeBytes(100)));
+        assert!(!s.is_below(SpaceThreshold::MinFreeBytes(0)));
+    }
+
+    #[test]
+    fn percent_thresholds() {
+        let s = space(1000, 100);
+        assert_eq!(s.free_fraction(), 0.1);
+        assert!(!s.is_below(SpaceThreshold::MinFreePercent(10.0)));
+        assert!(s.is_below(SpaceThreshold::MinFreePercent(10.01)));
+
+        // a third isn't exactly representable either way
+        let third = space(3, 1);
+        assert!(!third.is_below(SpaceThreshold::MinFreePercent(33.3)));
+        assert!(third.is_below(SpaceThreshold::MinFreePercent(33.34)));
+
+        let empty = space(0, 0);
+        assert_eq!(empty.free_fraction(), 0.0);
+        assert!(empty.is_below(SpaceThreshold::MinFreePercent(0.5)));
+        assert!(!empty.is_below(SpaceThreshold::MinFreePercent(0.0)));
+    }
+}
@@ -0,0 +1,443 @@
+use crate::{InspectPathError, PathInfo, PathStatus, check_status, inspect_path, same_filesystem};
+use std::{
+    fmt, fs,
+    io::{ErrorKind, Read, Write},
+    path::Path,
+};
+
+/// How [`plan_move`] decided a move should be carried out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MovePlan {
+    /// Source and destination share a filesystem; a plain rename works.
+    Rename,
+    /// The move needs a copy followed by deleting the source.
+    CopyAndDelete {
+        /// Whether either end is a network mount, so the copy goes over the
+        /// network.
+        crosses_network: bool,
+        /// Size of the source if it is a single file. Directories are not
+        /// walked, since that alone can be slow on a network share.
+        estimated_bytes: Option<u64>,
+    },
+    /// The move should not be attempted.
+    Refuse(MoveRefusal),
+}
+
+/// Why [`plan_move`] returned [`MovePlan::Refuse`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MoveRefusal {
+    /// The source is not reachable; carries its probed status.
+    SourceUnavailable(PathStatus),
+    /// The destination directory is not reachable; carries its probed status.
+    DestinationUnavailable(PathStatus),
+}
+
+impl fmt::Display for MoveRefusal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoveRefusal::SourceUnavailable(status) => write!(f, "source is {status}"),
+            MoveRefusal::DestinationUnavailable(status) => write!(f, "destination is {status}"),
+        }
+    }
+}
+
+/// Decides how to move `src` to `dst` without touching either.
+///
+/// Both ends are inspected and probed: the source itself, and the directory
+/// `dst` would be created in. If either isn't [`PathStatus::Mounted`] the
+/// move is refused. Otherwise it is a [`MovePlan::Rename`] when
+/// [`same_filesystem`] says so, and a [`MovePlan::CopyAndDelete`] flagged
+/// with whether it crosses the network when not.
+///
+/// # Errors
+///
+/// Returns an error if either path can't be inspected.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::path::Path;
+/// use inspect_path::{MovePlan, plan_move};
+///
+/// let plan = plan_move(Path::new("/home/me/video.mkv"), Path::new("/mnt/nas/video.mkv")).unwrap();
+/// if let MovePlan::CopyAndDelete { crosses_network: true, estimated_bytes, .. } = plan {
+///     println!("about to upload {estimated_bytes:?} bytes");
+/// }
+/// ```
+pub fn plan_move(
+    src: impl AsRef<Path>,
+    dst: impl AsRef<Path>,
+) -> Result<MovePlan, InspectPathError> {
+    plan_move_inner(src.as_ref(), dst.as_ref())
+}
+
+fn plan_move_inner(src: &Path, dst: &Path) -> Result<MovePlan, InspectPathError> {
+    let dst_dir = match dst.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+
+    let mut src_info = inspect_path(src)?;
+    src_info.status = check_status(src);
+    let mut dst_info = inspect_path(dst)?;
+    dst_info.status = check_status(dst_dir);
+
+    if !src_info.is_status_mounted() || !dst_info.is_status_mounted() {
+        return Ok(plan(&src_info, &dst_info, false, None));
+    }
+
+    let same = same_filesystem(src, dst)?;
+    let estimated_bytes = fs::metadata(src)
+        .ok()
+        .filter(|m| m.is_file())
+        .map(|m| m.len());
+    Ok(plan(&src_info, &dst_info, same, estimated_bytes))
+}
+
+/// The decision behind [`plan_move`], given already inspected and probed ends.
+pub(crate) fn plan(
+    src: &PathInfo,
+    dst: &PathInfo,
+    same_filesystem: bool,
+    estimated_bytes: Option<u64>,
+) -> MovePlan {
+    if !src.is_status_mounted() {
+        return MovePlan::Refuse(MoveRefusal::SourceUnavailable(src.status.clone()));
+    }
+    if !dst.is_status_mounted() {
+        return MovePlan::Refuse(MoveRefusal::DestinationUnavailable(dst.status.clone()));
+    }
+    if same_filesystem {
+        return MovePlan::Rename;
+    }
+    MovePlan::CopyAndDelete {
+        crosses_network: src.is_remote() || dst.is_remote(),
+        estimated_bytes,
+    }
+}
+
+/// Carries out a [`MovePlan`] for `src` and `dst`.
+///
+/// `progress` is called with the number of bytes copied so far after every
+/// chunk of a copy; it is not called for a rename. A rename that fails
+/// because the paths are on different mounts of the same filesystem (e.g.
+/// bind mounts) falls back to copying.
+///
+/// A copy recreates symlinks as links instead of following them and keeps
+/// the permissions of every file and directory. The source is removed only
+/// after the whole copy succeeded, and a copy never writes over anything: if
+/// `dst` already exists nothing is copied.
+///
+/// # Errors
+///
+/// Returns [`InspectPathError::General`] for a refused plan and
+/// [`InspectPathError::Io`] if any filesystem operation fails, with
+/// [`ErrorKind::AlreadyExists`] if a copy finds `dst` in place.
+pub fn execute_move<F>(
+    plan: &MovePlan,
+    src: impl AsRef<Path>,
+    dst: impl AsRef<Path>,
+    mut progress: F,
+) -> Result<(), InspectPathError>
+where
+    F: FnMut(u64),
+{
+    execute_move_inner(plan, src.as_ref(), dst.as_ref(), &mut progress)
+}
+
+fn execute_move_inner(
+    plan: &MovePlan,
+    src: &Path,
+    dst: &Path,
+    progress: &mut dyn FnMut(u64),
+) -> Result<(), InspectPathError> {
+    match plan {
+        MovePlan::Refuse(reason) => Err(InspectPathError::General {
+            path: Some(src.to_path_buf()),
+            msg: format!("move refused: {reason}"),
+        }),
+        MovePlan::Rename => match fs::rename(src, dst) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::CrossesDevices => copy_and_delete(src, dst, progress),
+            Err(source) => Err(InspectPathError::Io {
+                path: src.to_path_buf(),
+                source,
+            }),
+        },
+        MovePlan::CopyAndDelete { .. } => copy_and_delete(src, dst, progress),
+    }
+}
+
+fn copy_and_delete(
+    src: &Path,
+    dst: &Path,
+    progress: &mut dyn FnMut(u64),
+) -> Result<(), InspectPathError> {
+    // unlike a rename, a copy would merge into or truncate what is there
+    if fs::symlink_metadata(dst).is_ok() {
+        return Err(InspectPathError::Io {
+            path: dst.to_path_buf(),
+            source: ErrorKind::AlreadyExists.into(),
+        });
+    }
+
+    let mut copied = 0;
+    copy_recursive(src, dst, &mut copied, progress)?;
+
+    // a symlink to a directory is removed as a link, never followed
+    let removed = if fs::symlink_metadata(src).is_ok_and(|m| m.is_dir()) {
+        fs::remove_dir_all(src)
+    } else {
+        fs::remove_file(src)
+    };
+    removed.map_err(|source| InspectPathError::Io {
+        path: src.to_path_buf(),
+        source,
+    })
+}
+
+fn copy_recursive(
+    src: &Path,
+    dst: &Path,
+    copied: &mut u64,
+    progress: &mut dyn FnMut(u64),
+) -> Result<(), InspectPathError> {
+    let io_error = |path: &Path| {
+        let path = path.to_path_buf();
+        move |source| InspectPathError::Io { path, source }
+    };
+
+    let meta = fs::symlink_metadata(src).map_err(io_error(src))?;
+    if meta.is_symlink() {
+        return copy_symlink(src, dst).map_err(io_error(dst));
+    }
+    if meta.is_dir() {
+        fs::create_dir(dst).map_err(io_error(dst))?;
+        for entry in fs::read_dir(src).map_err(io_error(src))? {
+            let entry = entry.map_err(io_error(src))?;
+            copy_recursive(
+                &entry.path(),
+                &dst.join(entry.file_name()),
+                copied,
+                progress,
+            )?;
+        }
+        // set last, so a read-only directory is still filled first
+        return fs::set_permissions(dst, meta.permissions()).map_err(io_error(dst));
+    }
+
+    let mut reader = fs::File::open(src).map_err(io_error(src))?;
+    let mut writer = fs::File::create_new(dst).map_err(io_error(dst))?;
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let n = match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(io_error(src)(e)),
+        };
+        writer.write_all(&buf[..n]).map_err(io_error(dst))?;
+        *copied += n as u64;
+        progress(*copied);
+    }
+    writer
+        .set_permissions(meta.permissions())
+        .map_err(io_error(dst))?;
+    writer.sync_all().map_err(io_error(dst))
+}
+
+/// Recreates the symlink `src` at `dst` with the same target, so a link out
+/// of the tree being moved isn't copied as the data it points to.
+#[cfg(unix)]
+fn copy_symlink(src: &Path, dst: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(fs::read_link(src)?, dst)
+}
+
+#[cfg(windows)]
+fn copy_symlink(src: &Path, dst: &Path) -> std::io::Result<()> {
+    use std::os::windows::fs::{FileTypeExt, symlink_dir, symlink_file};
+
+    let target = fs::read_link(src)?;
+    if fs::symlink_metadata(src)?.file_type().is_symlink_dir() {
+        symlink_dir(target, dst)
+    } else {
+        symlink_file(target, dst)
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn copy_symlink(_src: &Path, _dst: &Path) -> std::io::Result<()> {
+    Err(ErrorKind::Unsupported.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PathType;
+
+    fn info(kind: PathType, status: PathStatus) -> PathInfo {
+        let mut info = inspect_path(std::env::temp_dir()).unwrap();
+        info.remote_kind = None;
+        info.kind = kind;
+        info.status = status;
+        info
+    }
+
+    #[test]
+    fn plan_from_injected_infos() {
+        let local = info(PathType::Fixed, PathStatus::Mounted);
+        let remote = info(PathType::Remote, PathStatus::Mounted);
+        let gone = info(PathType::Remote, PathStatus::Disconnected);
+
+        assert_eq!(plan(&local, &local, true, Some(5)), MovePlan::Rename);
+        assert_eq!(
+            plan(&local, &remote, false, Some(5)),
+            MovePlan::CopyAndDelete {
+                crosses_network: true,
+                estimated_bytes: Some(5)
+            }
+        );
+        assert_eq!(
+            plan(
+                &local,
+                &info(PathType::Removable, PathStatus::Mounted),
+                false,
+                None
+            ),
+            MovePlan::CopyAndDelete {
+                crosses_network: false,
+                estimated_bytes: None
+            }
+        );
+        assert_eq!(
+            plan(&local, &gone, false, None),
+            MovePlan::Refuse(MoveRefusal::DestinationUnavailable(
+                PathStatus::Disconnected
+            ))
+        );
+        assert_eq!(
+            plan(&gone, &local, true, None),
+            MovePlan::Refuse(MoveRefusal::SourceUnavailable(PathStatus::Disconnected))
+        );
+    }
+
+    #[test]
+    fn rename_within_temp_dir() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let src = dir.join("a.txt");
+        let dst = dir.join("b.txt");
+        fs::write(&src, b"hello").unwrap();
+
+        let plan = plan_move(&src, &dst).unwrap();
+        assert_eq!(plan, MovePlan::Rename);
+
+        let mut calls = 0;
+        execute_move(&plan, &src, &dst, |_| calls += 1).unwrap();
+        assert_eq!(calls, 0);
+        assert!(!src.exists());
+        assert_eq!(fs::read(&dst).unwrap(), b"hello");
+    }
+
+    #[test]
+    fn copy_and_delete_tree() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let src = dir.join("src");
+        let dst = dir.join("dst");
+        fs::create_dir_all(src.join("nested")).unwrap();
+        fs::write(src.join("one.bin"), vec![1u8; 100_000]).unwrap();
+        fs::write(src.join("nested/two.bin"), vec![2u8; 10]).unwrap();
+
+        let plan = MovePlan::CopyAndDelete {
+            crosses_network: false,
+            estimated_bytes: None,
+        };
+        let mut last = 0;
+        execute_move(&plan, &src, &dst, |copied| {
+            assert!(copied > last);
+            last = copied;
+        })
+        .unwrap();
+
+        assert_eq!(last, 100_010);
+        assert!(!src.exists());
+        assert_eq!(fs::read(dst.join("one.bin")).unwrap(), vec![1u8; 100_000]);
+        assert_eq!(fs::read(dst.join("nested/two.bin")).unwrap(), vec![2u8; 10]);
+
+        let refused = MovePlan::Refuse(MoveRefusal::SourceUnavailable(PathStatus::Stale));
+        assert!(execute_move(&refused, &dst, &src, |_| {}).is_err());
+    }
+
+    #[test]
+    fn copy_and_delete_keeps_existing_destination() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let src = dir.join("a.txt");
+        let dst = dir.join("b.txt");
+        fs::write(&src, b"new").unwrap();
+        fs::write(&dst, b"old").unwrap();
+
+        let plan = MovePlan::CopyAndDelete {
+            crosses_network: false,
+            estimated_bytes: None,
+        };
+        let err = execute_move(&plan, &src, &dst, |_| {}).unwrap_err();
+        assert!(matches!(&err, InspectPathError::Io { path, source }
+            if path == &dst && source.kind() == ErrorKind::AlreadyExists));
+        assert_eq!(fs::read(&src).unwrap(), b"new");
+        assert_eq!(fs::read(&dst).unwrap(), b"old");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn copy_and_delete_keeps_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let src = dir.join("src");
+        let dst = dir.join("dst");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("run.sh"), b"#!/bin/sh\n").unwrap();
+        fs::set_permissions(src.join("run.sh"), fs::Permissions::from_mode(0o750)).unwrap();
+        fs::set_permissions(&src, fs::Permissions::from_mode(0o710)).unwrap();
+
+        let plan = MovePlan::CopyAndDelete {
+            crosses_network: false,
+            estimated_bytes: None,
+        };
+        execute_move(&plan, &src, &dst, |_| {}).unwrap();
+
+        let mode = |path: &Path| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode(&dst.join("run.sh")), 0o750);
+        assert_eq!(mode(&dst), 0o710);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn copy_and_delete_keeps_symlinks() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let outside = dir.join("outside");
+        let src = dir.join("src");
+        let dst = dir.join("dst");
+        fs::create_dir_all(&outside).unwrap();
+        fs::write(outside.join("big.bin"), vec![0u8; 1000]).unwrap();
+        fs::create_dir_all(&src).unwrap();
+        std::os::unix::fs::symlink(&outside, src.join("link")).unwrap();
+
+        let plan = MovePlan::CopyAndDelete {
+            crosses_network: false,
+            estimated_bytes: None,
+        };
+        let mut copied = 0;
+        execute_move(&plan, &src, &dst, |n| copied = n).unwrap();
+
+        assert_eq!(copied, 0);
+        assert_eq!(fs::read_link(dst.join("link")).unwrap(), outside);
+        assert!(outside.join("big.bin").exists());
+    }
+}
@@ -0,0 +1,196 @@
+use crate::{InspectPathError, PathInfo, PathStatus, PathType, RemoteType, remote};
+use std::path::PathBuf;
+
+/// Inspects a location given as a URI, as desktop file managers and drag and
+/// drop hand them out.
+///
+/// - `file:///mnt/share/doc.pdf` (or `file://localhost/...`) is decoded to a
+///   local path and passed to [`inspect_path`](crate::inspect_path). On
+///   Windows `file:///C:/x` is `C:\x` and `file://server/share/x` is the UNC
+///   path `\\server\share\x`.
+/// - `smb://`, `cifs://`, `nfs://`, `sftp://`, `ssh://`, `dav://`, `davs://`,
+///   `webdav://`, `afp://` and `ftp://` URIs are classified as
+///   [`PathType::Remote`] with the matching [`RemoteType`] without any system
+///   call, whether or not the share is mounted. The info's path is the URI
+///   itself and its status is [`PathStatus::Unknown`]; it can't be probed
+///   with [`check_status`](PathInfo::check_status), which goes through the
+///   file APIs.
+///
+/// On Unix a `file://` URI naming another host is classified the same way,
+/// as a remote of unknown type.
+///
+/// # Errors
+///
+/// [`InspectPathError::InvalidPath`] for a string that isn't a URI, an
+/// unsupported scheme, a malformed `%` escape, or (on Windows, whose paths
+/// are Unicode) a path that doesn't percent-decode to UTF-8; and any error
+/// of `inspect_path` for `file://` URIs.
+///
+/// # Examples
+///
+/// ```rust
+/// use inspect_path::{RemoteType, inspect_uri};
+///
+/// let info = inspect_uri("smb://nas/media/film.mkv").unwrap();
+/// assert!(info.is_remote());
+/// assert_eq!(info.remote_type(), Some(&RemoteType::SMB));
+/// ```
+pub fn inspect_uri(uri: &str) -> Result<PathInfo, InspectPathError> {
+    let invalid = || InspectPathError::InvalidPath(uri.to_string());
+    let (scheme, rest) = uri.split_once("://").ok_or_else(invalid)?;
+
+    if !scheme.eq_ignore_ascii_case("file") {
+        let remote = remote::from_scheme(scheme).ok_or_else(invalid)?;
+        return Ok(remote_info(uri, remote));
+    }
+
+    // the query and fragment never name part of the file
+    let rest = rest.split(['?', '#']).next().unwrap_or_default();
+    let (host, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+    let path = percent_decode(path).ok_or_else(invalid)?;
+
+    if host.is_empty() || host.eq_ignore_ascii_case("localhost") {
+        crate::inspect_path(local_path(path).ok_or_else(invalid)?)
+    } else if cfg!(windows) {
+        let path = String::from_utf8(path).map_err(|_| invalid())?;
+        crate::inspect_path(format!(r"\\{host}{}", path.replace('/', r"\")))
+    } else {
+        Ok(remote_info(uri, RemoteType::Unknown))
+    }
+}
+
+fn remote_info(uri: &str, remote: RemoteType) -> PathInfo {
+    PathInfo::synthetic(
+        PathBuf::from(uri),
+        PathType::Remote,
+        Some(remote),
+        PathStatus::Unknown,
+    )
+}
+
+/// The local path of a `file://` URI's decoded path part: the bytes as they
+/// are on Unix, and on Windows the UTF-8 text with the leading `/` of
+/// `/C:/x` dropped.
+#[cfg(unix)]
+fn local_path(path: Vec<u8>) -> Option<PathBuf> {
+    use std::os::unix::ffi::OsStringExt;
+
+    Some(PathBuf::from(std::ffi::OsString::from_vec(path)))
+}
+
+#[cfg(not(unix))]
+fn local_path(path: Vec<u8>) -> Option<PathBuf> {
+    let path = String::from_utf8(path).ok()?;
+    let path = match path.strip_prefix('/') {
+        Some(rest) if rest.as_bytes().get(1) == Some(&b':') => rest,
+        _ => &path,
+    };
+    Some(PathBuf::from(path.replace('/', r"\")))
+}
+
+/// Decodes `%XX` escapes, or `None` for a malformed escape.
+fn percent_decode(s: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut rest = s.as_bytes();
+    while let Some((&b, tail)) = rest.split_first() {
+        if b == b'%' {
+            let hex = tail
+                .get(..2)
+                .filter(|h| h.iter().all(u8::is_ascii_hexdigit))?;
+            bytes.push(u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(b);
+            rest = tail;
+        }
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn network_schemes_need_no_mount() {
+        let cases = [
+            ("smb://server/share/", RemoteType::SMB),
+            ("NFS://srv/export/data", RemoteType::NFS),
+            ("sftp://me@host/home/me", RemoteType::SSHFS),
+            ("davs://cloud/remote.php/dav", RemoteType::WebDAV),
+            ("afp://mac/Public", RemoteType::Other("afp".into())),
+        ];
+        for (uri, remote) in cases {
+            let info = inspect_uri(uri).unwrap();
+            assert!(info.is_remote(), "{uri}");
+            assert_eq!(info.remote_type(), Some(&remote), "{uri}");
+            assert_eq!(info.path(), std::path::Path::new(uri));
+            assert!(info.is_status_unknown());
+        }
+    }
+
+    #[test]
+    fn file_uris_inspect_the_local_path() {
+        let tmp = std::env::temp_dir();
+        let uri = format!("file://{}", tmp.to_string_lossy().replace('\\', "/"));
+        let uri = if cfg!(windows) {
+            uri.replacen("file://", "file:///", 1)
+        } else {
+            uri
+        };
+        let info = inspect_uri(&uri).unwrap();
+        assert_eq!(info.kind(), crate::inspect_path(&tmp).unwrap().kind());
+        assert!(inspect_uri(&format!("{uri}?x=1#frag")).is_ok());
+    }
+
+    #[test]
+    fn rejects_what_it_cannot_classify() {
+        for uri in ["/mnt/share", "https://example.com/x", "file:///a%2"] {
+            assert!(
+                matches!(inspect_uri(uri), Err(InspectPathError::InvalidPath(_))),
+                "{uri}"
+            );
+        }
+        // Windows paths are Unicode, so there is nothing to decode `%ff` to
+        #[cfg(windows)]
+        assert!(matches!(
+            inspect_uri("file:///C:/%ff"),
+            Err(InspectPathError::InvalidPath(_))
+        ));
+    }
+
+    #[test]
+    fn decodes_escapes() {
+        assert_eq!(
+            percent_decode("/mnt/My%20Share/%C3%A9t%C3%A9.pdf").as_deref(),
+            Some("/mnt/My Share/été.pdf".as_bytes())
+        );
+        assert_eq!(percent_decode("/%ff").as_deref(), Some(b"/\xff".as_slice()));
+        assert_eq!(percent_decode("%zz"), None);
+        assert_eq!(percent_decode("%+1"), None);
+    }
+
+    #[cfg(target_family = "unix")]
+    #[test]
+    fn file_uris_may_name_non_utf8_paths() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let uri = format!("file://{}/caf%E9.txt", tmp.path().display());
+        let info = inspect_uri(&uri).unwrap();
+        assert!(
+            info.path()
+                .as_os_str()
+                .as_bytes()
+                .ends_with(b"/caf\xe9.txt")
+        );
+    }
+
+    #[cfg(target_family = "unix")]
+    #[test]
+    fn file_uri_on_another_host_is_remote() {
+        let info = inspect_uri("file://nas/export/doc.pdf").unwrap();
+        assert!(info.is_remote());
+        assert_eq!(info.remote_type(), Some(&RemoteType::Unknown));
+    }
+}
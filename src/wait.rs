@@ -0,0 +1,420 @@
+use crate::{
+    InspectPathError, PathInfo, PathStatus,
+    clock::{Clock, SystemClock},
+    platform,
+    probe::status_with_timeout,
+};
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+use thiserror::Error;
+
+/// Why [`wait_for_status`] stopped without the path reaching the status.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum WaitError {
+    /// The deadline passed; `last` is what the final probe saw.
+    #[error("'{}' is still {last} after waiting for {desired}", path.display())]
+    TimedOut {
+        path: PathBuf,
+        desired: PathStatus,
+        last: PathStatus,
+    },
+    /// Waiting can't help: the path is malformed, or nothing can be probed on
+    /// this target.
+    #[error("Waiting is pointless: {0}")]
+    Pointless(#[source] InspectPathError),
+    /// The status was reached, but inspecting the path then failed.
+    #[error(transparent)]
+    Inspect(InspectPathError),
+}
+
+/// Blocks until `path` reports `desired`, then returns it inspected.
+///
+/// The path is probed like [`check_status_timeout`](crate::check_status_timeout),
+/// each probe bounded by the time left until `timeout`, and probed again
+/// `poll` after each answer that isn't `desired`. Once `desired` is seen the
+/// path is inspected, with [`PathInfo::status`] set to it.
+///
+/// For services that must not start before their data is reachable. Waiting
+/// for [`PathStatus::Disconnected`] or [`NotMounted`](PathStatus::NotMounted)
+/// works too, e.g. to wait out an unmount.
+///
+/// # Errors
+///
+/// - [`WaitError::TimedOut`] with the last observed status when `timeout`
+///   passes first.
+/// - [`WaitError::Pointless`] as soon as the probe fails in a way no amount
+///   of waiting fixes, such as [`InspectPathError::InvalidPath`].
+/// - [`WaitError::Inspect`] if inspecting the path fails once it is reached.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use inspect_path::{PathStatus, WaitError, wait_for_status};
+///
+/// match wait_for_status(
+///     "/mnt/data",
+///     PathStatus::Mounted,
+///     Duration::from_secs(120),
+///     Duration::from_secs(2),
+/// ) {
+///     Ok(info) => println!("starting on {}", info.fs_type_name().unwrap_or("?")),
+///     Err(WaitError::TimedOut { last, .. }) => eprintln!("/mnt/data still {last}"),
+///     Err(e) => eprintln!("{e}"),
+/// }
+/// ```
+pub fn wait_for_status(
+    path: impl AsRef<Path>,
+    desired: PathStatus,
+    timeout: Duration,
+    poll: Duration,
+) -> Result<PathInfo, WaitError> {
+    wait_with(
+        path.as_ref(),
+        Waiter::new(desired, Instant::now(), timeout, poll),
+        &SystemClock,
+        |path, budget| system_probe(path, budget, &SystemClock),
+        |path| platform::inspect_path(path),
+    )
+}
+
+/// [`wait_for_status`] for async code, built with the `tokio` feature.
+///
+/// Probes and the final inspection run on Tokio's blocking pool and the
+/// waits between them on its timer, so a runtime with the time driver
+/// enabled is required.
+///
+/// # Errors
+///
+/// Same as [`wait_for_status`].
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use inspect_path::{PathStatus, wait_for_status_async};
+///
+/// # async fn start() -> Result<(), inspect_path::WaitError> {
+/// let timeout = Duration::from_secs(120);
+/// wait_for_status_async("/mnt/data", PathStatus::Mounted, timeout, Duration::from_secs(2))
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "tokio")]
+pub async fn wait_for_status_async(
+    path: impl AsRef<Path>,
+    desired: PathStatus,
+    timeout: Duration,
+    poll: Duration,
+) -> Result<PathInfo, WaitError> {
+    let path = path.as_ref().to_path_buf();
+    let mut waiter = Waiter::new(desired, Instant::now(), timeout, poll);
+    loop {
+        let budget = waiter.budget(&path, Instant::now())?;
+        let observed = blocking(&path, move |path| system_probe(path, budget, &SystemClock)).await;
+        match waiter.observe(observed, Instant::now()) {
+            Next::Reached(status) => {
+                let inspected = blocking(&path, |path| platform::inspect_path(path)).await;
+                return reached(inspected, status);
+            }
+            Next::Sleep(delay) => tokio::time::sleep(delay).await,
+            Next::Fail(e) => return Err(e),
+        }
+    }
+}
+
+/// Runs `f` on Tokio's blocking pool, turning a panic into an error.
+#[cfg(feature = "tokio")]
+async fn blocking<T: Send + 'static>(
+    path: &Path,
+    f: impl FnOnce(&Path) -> Result<T, InspectPathError> + Send + 'static,
+) -> Result<T, InspectPathError> {
+    let owned = path.to_path_buf();
+    tokio::task::spawn_blocking(move || f(&owned))
+        .await
+        .unwrap_or_else(|e| {
+            Err(InspectPathError::General {
+                path: Some(path.to_path_buf()),
+                msg: format!("probe task failed: {e}"),
+            })
+        })
+}
+
+fn system_probe(
+    path: &Path,
+    budget: Duration,
+    clock: &dyn Clock,
+) -> Result<PathStatus, InspectPathError> {
+    platform::validate_path(path)?;
+    Ok(status_with_timeout(path, budget, clock, |p| {
+        platform::check_status(p)
+    }))
+}
+
+/// The blocking loop around [`Waiter`], with the clock, probe and inspection
+/// injected.
+pub(crate) fn wait_with(
+    path: &Path,
+    mut waiter: Waiter,
+    clock: &dyn Clock,
+    mut probe: impl FnMut(&Path, Duration) -> Result<PathStatus, InspectPathError>,
+    inspect: impl FnOnce(&Path) -> Result<PathInfo, InspectPathError>,
+) -> Result<PathInfo, WaitError> {
+    loop {
+        let budget = waiter.budget(path, clock.now())?;
+        match waiter.observe(probe(path, budget), clock.now()) {
+            Next::Reached(status) => return reached(inspect(path), status),
+            Next::Sleep(delay) => clock.sleep(delay),
+            Next::Fail(e) => return Err(e),
+        }
+    }
+}
+
+/// The decisions shared by the blocking and async waits: how long the next
+/// probe may take, and what to do with its answer.
+#[derive(Debug)]
+pub(crate) struct Waiter {
+    desired: PathStatus,
+    started: Instant,
+    timeout: Duration,
+    poll: Duration,
+    last: Option<PathStatus>,
+}
+
+#[derive(Debug)]
+pub(crate) enum Next {
+    Reached(PathStatus),
+    Sleep(Duration),
+    Fail(WaitError),
+}
+
+impl Waiter {
+    pub(crate) fn new(
+        desired: PathStatus,
+        now: Instant,
+        timeout: Duration,
+        poll: Duration,
+    ) -> Self {
+        Self {
+            desired,
+            started: now,
+            timeout,
+            poll,
+            last: None,
+        }
+    }
+
+    fn remaining(&self, now: Instant) -> Duration {
+        self.timeout
+            .saturating_sub(now.saturating_duration_since(self.started))
+    }
+
+    /// The time the next probe may take, or the timeout error once the
+    /// deadline has passed. The first probe always runs.
+    pub(crate) fn budget(&mut self, path: &Path, now: Instant) -> Result<Duration, WaitError> {
+        let remaining = self.remaining(now);
+        match self.last.take() {
+            Some(last) if remaining.is_zero() => Err(WaitError::TimedOut {
+                path: path.to_path_buf(),
+                desired: self.desired.clone(),
+                last,
+            }),
+            last => {
+                self.last = last;
+                Ok(remaining)
+            }
+        }
+    }
+
+    pub(crate) fn observe(
+        &mut self,
+        observed: Result<PathStatus, InspectPathError>,
+        now: Instant,
+    ) -> Next {
+        let status = match observed {
+            Ok(status) => status,
+            Err(e @ (InspectPathError::InvalidPath(_) | InspectPathError::Unsupported)) => {
+                return Next::Fail(WaitError::Pointless(e));
+            }
+            // e.g. a panicked probe task; the next probe may do better
+            Err(_) => PathStatus::Unknown,
+        };
+        if status == self.desired {
+            return Next::Reached(status);
+        }
+        self.last = Some(status);
+        Next::Sleep(self.poll.min(self.remaining(now)))
+    }
+}
+
+fn reached(
+    inspected: Result<PathInfo, InspectPathError>,
+    status: PathStatus,
+) -> Result<PathInfo, WaitError> {
+    match inspected {
+        Ok(mut info) => {
+            info.status = status;
+            Ok(info)
+        }
+        Err(e @ (InspectPathError::InvalidPath(_) | InspectPathError::Unsupported)) => {
+            Err(WaitError::Pointless(e))
+        }
+        Err(e) => Err(WaitError::Inspect(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PathType, clock::MockClock};
+
+    /// A probe answering `before` for its first `polls` calls, then `after`,
+    /// each call taking a second of `clock` time.
+    fn transitions(
+        clock: &MockClock,
+        polls: usize,
+        before: PathStatus,
+        after: Result<PathStatus, InspectPathError>,
+    ) -> impl FnMut(&Path, Duration) -> Result<PathStatus, InspectPathError> + '_ {
+        let mut after = Some(after);
+        let mut calls = 0;
+        move |_, _| {
+            clock.sleep(Duration::from_secs(1));
+            calls += 1;
+            if calls <= polls {
+                Ok(before.clone())
+            } else {
+                after.take().unwrap_or(Ok(PathStatus::Unknown))
+            }
+        }
+    }
+
+    fn synthetic(path: &Path) -> Result<PathInfo, InspectPathError> {
+        Ok(PathInfo::synthetic(
+            path.to_path_buf(),
+            PathType::Remote,
+            None,
+            PathStatus::Unknown,
+        ))
+    }
+
+    fn wait(
+        clock: &MockClock,
+        desired: PathStatus,
+        timeout: u64,
+        probe: impl FnMut(&Path, Duration) -> Result<PathStatus, InspectPathError>,
+    ) -> Result<PathInfo, WaitError> {
+        let waiter = Waiter::new(
+            desired,
+            clock.now(),
+            Duration::from_secs(timeout),
+            Duration::from_secs(4),
+        );
+        wait_with(Path::new("/mnt/data"), waiter, clock, probe, synthetic)
+    }
+
+    #[test]
+    fn returns_once_the_status_is_reached() {
+        let clock = MockClock::new();
+        let probe = transitions(&clock, 3, PathStatus::NotMounted, Ok(PathStatus::Mounted));
+
+        let info = wait(&clock, PathStatus::Mounted, 60, probe).unwrap();
+        assert_eq!(info.status(), &PathStatus::Mounted);
+        assert_eq!(info.kind(), &PathType::Remote);
+        // three failed probes with a poll after each, then the fourth probe
+        assert_eq!(clock.elapsed(), Duration::from_secs(4 + 3 * 4));
+    }
+
+    #[test]
+    fn times_out_with_the_last_status() {
+        let clock = MockClock::new();
+        let probe = transitions(&clock, 100, PathStatus::Stale, Ok(PathStatus::Mounted));
+
+        let err = wait(&clock, PathStatus::Mounted, 12, probe).unwrap_err();
+        assert!(matches!(
+            err,
+            WaitError::TimedOut {
+                last: PathStatus::Stale,
+                ..
+            }
+        ));
+        // probes at 0, 5 and 10 seconds, the last poll cut short at 12
+        assert_eq!(clock.elapsed(), Duration::from_secs(12));
+    }
+
+    #[test]
+    fn stops_when_waiting_is_pointless() {
+        let clock = MockClock::new();
+        let invalid = Err(InspectPathError::InvalidPath("/mnt/data".into()));
+        let probe = transitions(&clock, 2, PathStatus::Disconnected, invalid);
+
+        let err = wait(&clock, PathStatus::Mounted, 60, probe).unwrap_err();
+        assert!(matches!(
+            err,
+            WaitError::Pointless(InspectPathError::InvalidPath(_))
+        ));
+        assert_eq!(clock.elapsed(), Duration::from_secs(3 + 2 * 4));
+    }
+
+    #[test]
+    fn probes_get_the_time_left() {
+        let clock = MockClock::new();
+        let mut budgets = Vec::new();
+        let probe = |_: &Path, budget| {
+            budgets.push(budget);
+            clock.sleep(Duration::from_secs(1));
+            Ok(PathStatus::Disconnected)
+        };
+        let _ = wait(&clock, PathStatus::Mounted, 7, probe);
+        let secs: Vec<u64> = budgets.iter().map(Duration::as_secs).collect();
+        assert_eq!(secs, [7, 2]);
+    }
+
+    #[test]
+    fn waits_on_the_real_system() {
+        let tmp = std::env::temp_dir();
+        let info = wait_for_status(
+            &tmp,
+            PathStatus::Mounted,
+            Duration::from_secs(5),
+            Duration::ZERO,
+        )
+        .unwrap();
+        assert_eq!(info.status(), &PathStatus::Mounted);
+
+        let err = wait_for_status("", PathStatus::Mounted, Duration::ZERO, Duration::ZERO);
+        assert!(matches!(err, Err(WaitError::Pointless(_))));
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn async_wait_matches() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap();
+        let tmp = std::env::temp_dir();
+        let info = runtime
+            .block_on(wait_for_status_async(
+                &tmp,
+                PathStatus::Mounted,
+                Duration::from_secs(5),
+                Duration::from_millis(10),
+            ))
+            .unwrap();
+        assert_eq!(
+            info,
+            wait_for_status(
+                &tmp,
+                PathStatus::Mounted,
+                Duration::from_secs(5),
+                Duration::ZERO
+            )
+            .unwrap()
+        );
+    }
+}
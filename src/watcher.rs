@@ -0,0 +1,427 @@
+use crate::{
+    PathInfo, PathStatus,
+    clock::{Clock, SystemClock},
+    platform,
+    probe::status_with_timeout,
+};
+use std::{
+    collections::HashMap,
+    io,
+    path::{Path, PathBuf},
+    sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError, mpsc},
+    thread::{self, JoinHandle},
+    time::{Duration, SystemTime},
+};
+
+type Probe = Arc<dyn Fn(&Path) -> PathStatus + Send + Sync>;
+
+/// A status transition reported by a [`PathWatcher`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusChange {
+    pub path: PathBuf,
+    pub old: PathStatus,
+    pub new: PathStatus,
+    /// When the reading that confirmed the change was taken.
+    pub at: SystemTime,
+}
+
+/// Options controlling how a [`PathWatcher`] polls.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use inspect_path::WatchOptions;
+///
+/// let opts = WatchOptions::new()
+///     .interval(Duration::from_secs(10))
+///     .debounce(3);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchOptions {
+    interval: Duration,
+    timeout: Duration,
+    debounce: u32,
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(30),
+            timeout: Duration::from_secs(5),
+            debounce: 2,
+        }
+    }
+}
+
+impl WatchOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Time between two polls of all watched paths. Defaults to 30 seconds.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Deadline for a single probe, see [`check_status_timeout`](crate::check_status_timeout).
+    /// Defaults to 5 seconds.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Number of consecutive identical readings required before a change is
+    /// reported. Defaults to 2; values below 1 are treated as 1.
+    pub fn debounce(mut self, readings: u32) -> Self {
+        self.debounce = readings.max(1);
+        self
+    }
+}
+
+#[derive(Debug)]
+struct Tracked {
+    status: PathStatus,
+    candidate: Option<PathStatus>,
+    streak: u32,
+}
+
+impl Tracked {
+    fn new(status: PathStatus) -> Self {
+        Self {
+            status,
+            candidate: None,
+            streak: 0,
+        }
+    }
+
+    /// Feeds one reading, returning the old status once a change is confirmed.
+    fn observe(&mut self, reading: PathStatus, debounce: u32) -> Option<PathStatus> {
+        if reading == self.status {
+            self.candidate = None;
+            self.streak = 0;
+            return None;
+        }
+
+        if self.candidate.as_ref() == Some(&reading) {
+            self.streak += 1;
+        } else {
+            self.candidate = Some(reading);
+            self.streak = 1;
+        }
+
+        if self.streak < debounce {
+            return None;
+        }
+        self.streak = 0;
+        let new = self.candidate.take()?;
+        Some(std::mem::replace(&mut self.status, new))
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    paths: HashMap<PathBuf, Tracked>,
+    shutdown: bool,
+}
+
+#[derive(Debug, Default)]
+struct Shared {
+    state: Mutex<State>,
+    wake: Condvar,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Polls a set of paths on a background thread and reports status changes.
+///
+/// Every [`interval`](WatchOptions::interval) each watched path is probed
+/// with [`check_status_timeout`](crate::check_status_timeout). A change is
+/// sent as a [`StatusChange`] once the new status has been read
+/// [`debounce`](WatchOptions::debounce) times in a row, so a mount that
+/// flaps for a single poll is not reported.
+///
+/// Paths added with [`watch`](Self::watch) start out as
+/// [`PathStatus::Unknown`], so their first stable reading is reported as a
+/// change. [`watch_info`](Self::watch_info) starts from the status already
+/// stored in a [`PathInfo`].
+///
+/// Dropping the watcher stops the thread and waits for it to exit, which may
+/// take up to one probe timeout. The thread also exits once the receiver is
+/// dropped.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use inspect_path::{PathWatcher, WatchOptions};
+///
+/// let opts = WatchOptions::new().interval(Duration::from_secs(30));
+/// let (watcher, changes) = PathWatcher::spawn(["/mnt/nfs", "/mnt/smb"], &opts)?;
+///
+/// for change in changes {
+///     println!("{}: {} -> {}", change.path.display(), change.old, change.new);
+/// }
+/// # drop(watcher);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct PathWatcher {
+    shared: Arc<Shared>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl PathWatcher {
+    /// Starts watching `paths`, returning the watcher and the receiving end
+    /// of its change events.
+    ///
+    /// # Errors
+    ///
+    /// Returns the OS error if the polling thread can't be spawned.
+    pub fn spawn<I, P>(
+        paths: I,
+        opts: &WatchOptions,
+    ) -> io::Result<(Self, mpsc::Receiver<StatusChange>)>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let timeout = opts.timeout;
+        let probe: Probe = Arc::new(move |path: &Path| {
+            status_with_timeout(path, timeout, &SystemClock, |p| platform::check_status(p))
+        });
+        Self::spawn_with(paths, opts, probe, Arc::new(SystemClock))
+    }
+
+    fn spawn_with<I, P>(
+        paths: I,
+        opts: &WatchOptions,
+        probe: Probe,
+        clock: Arc<dyn Clock>,
+    ) -> io::Result<(Self, mpsc::Receiver<StatusChange>)>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let shared = Arc::new(Shared::default());
+        {
+            let mut state = shared.lock();
+            for path in paths {
+                state.paths.insert(
+                    path.as_ref().to_path_buf(),
+                    Tracked::new(PathStatus::Unknown),
+                );
+            }
+        }
+
+        let (tx, rx) = mpsc::channel();
+        let thread_shared = Arc::clone(&shared);
+        let opts = opts.clone();
+        let handle = thread::Builder::new()
+            .name("inspect_path-watcher".into())
+            .spawn(move || run(&thread_shared, &opts, &*probe, &*clock, &tx))?;
+
+        Ok((
+            Self {
+                shared,
+                handle: Some(handle),
+            },
+            rx,
+        ))
+    }
+
+    /// Adds a path, starting from [`PathStatus::Unknown`]. Does nothing if
+    /// the path is already watched.
+    pub fn watch(&self, path: impl AsRef<Path>) {
+        self.shared
+            .lock()
+            .paths
+            .entry(path.as_ref().to_path_buf())
+            .or_insert_with(|| Tracked::new(PathStatus::Unknown));
+    }
+
+    /// Adds the path of `info`, starting from its stored status.
+    pub fn watch_info(&self, info: &PathInfo) {
+        self.shared
+            .lock()
+            .paths
+            .entry(info.path().to_path_buf())
+            .or_insert_with(|| Tracked::new(info.status().clone()));
+    }
+
+    /// Stops watching a path. Returns whether it was being watched.
+    pub fn unwatch(&self, path: impl AsRef<Path>) -> bool {
+        self.shared.lock().paths.remove(path.as_ref()).is_some()
+    }
+}
+
+impl Drop for PathWatcher {
+    fn drop(&mut self) {
+        self.shared.lock().shutdown = true;
+        self.shared.wake.notify_all();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn run(
+    shared: &Shared,
+    opts: &WatchOptions,
+    probe: &(dyn Fn(&Path) -> PathStatus + Send + Sync),
+    clock: &dyn Clock,
+    tx: &mpsc::Sender<StatusChange>,
+) {
+    loop {
+        for change in poll_once(shared, opts.debounce, probe, clock) {
+            if tx.send(change).is_err() {
+                return;
+            }
+        }
+
+        let state = shared.lock();
+        let (state, _) = shared
+            .wake
+            .wait_timeout_while(state, opts.interval, |s| !s.shutdown)
+            .unwrap_or_else(PoisonError::into_inner);
+        if state.shutdown {
+            return;
+        }
+    }
+}
+
+/// Probes every watched path once, without holding the lock while probing.
+fn poll_once(
+    shared: &Shared,
+    debounce: u32,
+    probe: &dyn Fn(&Path) -> PathStatus,
+    clock: &dyn Clock,
+) -> Vec<StatusChange> {
+    let paths: Vec<PathBuf> = shared.lock().paths.keys().cloned().collect();
+    let mut changes = Vec::new();
+
+    for path in paths {
+        if shared.lock().shutdown {
+            break;
+        }
+        let reading = probe(&path);
+        let at = clock.system_time();
+
+        // the path may have been unwatched while probing
+        let mut state = shared.lock();
+        let Some(tracked) = state.paths.get_mut(&path) else {
+            continue;
+        };
+        if let Some(old) = tracked.observe(reading.clone(), debounce) {
+            changes.push(StatusChange {
+                path,
+                old,
+                new: reading,
+                at,
+            });
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+
+    fn scripted(readings: &'static [(&'static str, PathStatus)]) -> impl Fn(&Path) -> PathStatus {
+        move |path| {
+            readings
+                .iter()
+                .find(|(p, _)| Path::new(p) == path)
+                .map(|(_, s)| s.clone())
+                .unwrap_or(PathStatus::Unknown)
+        }
+    }
+
+    #[test]
+    fn debounce_suppresses_flapping() {
+        let mut tracked = Tracked::new(PathStatus::Mounted);
+
+        assert_eq!(tracked.observe(PathStatus::Disconnected, 3), None);
+        assert_eq!(tracked.observe(PathStatus::Mounted, 3), None);
+        assert_eq!(tracked.observe(PathStatus::Disconnected, 3), None);
+        assert_eq!(tracked.observe(PathStatus::Disconnected, 3), None);
+        assert_eq!(
+            tracked.observe(PathStatus::Disconnected, 3),
+            Some(PathStatus::Mounted)
+        );
+        assert_eq!(tracked.status, PathStatus::Disconnected);
+        assert_eq!(tracked.observe(PathStatus::Disconnected, 3), None);
+    }
+
+    #[test]
+    fn poll_reports_changes_with_clock_time() {
+        let shared = Shared::default();
+        shared
+            .lock()
+            .paths
+            .insert("/mnt/a".into(), Tracked::new(PathStatus::Unknown));
+        shared
+            .lock()
+            .paths
+            .insert("/mnt/b".into(), Tracked::new(PathStatus::Mounted));
+
+        let probe = scripted(&[
+            ("/mnt/a", PathStatus::Mounted),
+            ("/mnt/b", PathStatus::Stale),
+        ]);
+        let clock = MockClock::new();
+
+        assert!(poll_once(&shared, 2, &probe, &clock).is_empty());
+        clock.advance(Duration::from_secs(2));
+
+        let mut changes = poll_once(&shared, 2, &probe, &clock);
+        changes.sort_by(|a, b| a.path.cmp(&b.path));
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].path, Path::new("/mnt/a"));
+        assert_eq!(changes[0].old, PathStatus::Unknown);
+        assert_eq!(changes[0].new, PathStatus::Mounted);
+        assert_eq!(changes[1].old, PathStatus::Mounted);
+        assert_eq!(changes[1].new, PathStatus::Stale);
+        assert!(
+            changes
+                .iter()
+                .all(|c| c.at == SystemTime::UNIX_EPOCH + Duration::from_secs(2))
+        );
+
+        assert!(poll_once(&shared, 2, &probe, &clock).is_empty());
+    }
+
+    #[test]
+    fn watcher_delivers_and_shuts_down() {
+        let opts = WatchOptions::new()
+            .interval(Duration::from_millis(5))
+            .debounce(1);
+        let probe: Probe = Arc::new(scripted(&[
+            ("/mnt/a", PathStatus::Mounted),
+            ("/mnt/b", PathStatus::NoMedia),
+        ]));
+        let (watcher, changes) =
+            PathWatcher::spawn_with(["/mnt/a"], &opts, probe, Arc::new(SystemClock)).unwrap();
+
+        let change = changes.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(change.path, Path::new("/mnt/a"));
+        assert_eq!(change.new, PathStatus::Mounted);
+
+        assert!(watcher.unwatch("/mnt/a"));
+        assert!(!watcher.unwatch("/mnt/a"));
+        watcher.watch("/mnt/b");
+        let change = changes.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(change.path, Path::new("/mnt/b"));
+        assert_eq!(change.new, PathStatus::NoMedia);
+
+        drop(watcher);
+        assert!(changes.recv().is_err());
+    }
+}
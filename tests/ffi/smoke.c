@@ -0,0 +1,37 @@
+/* Exercises the `ffi` feature from C; see .github/workflows/ffi.yml. */
+#include <stdio.h>
+#include <string.h>
+
+#include "inspect_path.h"
+
+#define CHECK(cond)                                                        \
+    do {                                                                   \
+        if (!(cond)) {                                                     \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__,        \
+                    __LINE__, #cond);                                      \
+            return 1;                                                      \
+        }                                                                  \
+    } while (0)
+
+int main(int argc, char **argv) {
+    const char *path = argc > 1 ? argv[1] : ".";
+    IpInfo info;
+    char msg[128];
+
+    CHECK(ip_inspect(path, &info) == IP_OK);
+    /* 0 is Unknown in every code table */
+    CHECK(info.kind != 0);
+    CHECK(info.status == 0);
+    CHECK(memchr(info.fs_type, '\0', IP_FS_TYPE_LEN) != NULL);
+    printf("%s: kind %u, fs %s\n", path, info.kind, info.fs_type);
+
+    /* 1 is Mounted */
+    CHECK(ip_check_status(path, 5000) == 1);
+
+    CHECK(ip_inspect(NULL, &info) == IP_ERR_INVALID_ARGUMENT);
+    CHECK(ip_last_error_message(msg, sizeof msg) > 0);
+    CHECK(strcmp(msg, "path is null") == 0);
+    CHECK(ip_last_error_message(NULL, 0) == strlen("path is null"));
+
+    return 0;
+}
@@ -0,0 +1,30 @@
+use inspect_path::{PathStatus, PathType, RemoteType, SpaceInfo, inspect_path_and_status};
+
+#[test]
+fn schema_accepts_serialized_values() {
+    let schema = serde_json::to_value(schemars::schema_for!(inspect_path::PathInfo)).unwrap();
+    let validator = jsonschema::validator_for(&schema).unwrap();
+
+    let info = inspect_path_and_status(std::env::temp_dir()).unwrap();
+    let sample = serde_json::to_value(&info).unwrap();
+    assert!(validator.is_valid(&sample), "{sample:#}");
+
+    let mut other = sample.clone();
+    other["kind"] = serde_json::to_value(PathType::Virtual("proc".into())).unwrap();
+    other["remote_kind"] = serde_json::to_value(Some(RemoteType::NinePfs)).unwrap();
+    other["status"] = serde_json::to_value(PathStatus::PermissionLimited).unwrap();
+    assert!(validator.is_valid(&other), "{other:#}");
+
+    let mut wrong = sample.clone();
+    wrong["kind"] = "floppy".into();
+    assert!(!validator.is_valid(&wrong));
+
+    let space = schemars::schema_for!(SpaceInfo);
+    let space = jsonschema::validator_for(&serde_json::to_value(space).unwrap()).unwrap();
+    let sample = SpaceInfo {
+        total: 100,
+        free: 50,
+        available: 40,
+    };
+    assert!(space.is_valid(&serde_json::to_value(sample).unwrap()));
+}